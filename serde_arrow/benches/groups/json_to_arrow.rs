@@ -87,8 +87,9 @@ fn benchmark_json_to_arrow(c: &mut criterion::Criterion) {
                 .filter(|s| !s.is_empty())
             {
                 let mut deserializer = serde_json::Deserializer::from_slice(json_to_deserialize);
-                let transcoder = serde_transcode::Transcoder::new(&mut deserializer);
-                arrow_builder.push(&transcoder).unwrap();
+                arrow_builder
+                    .push_from_deserializer(&mut deserializer)
+                    .unwrap();
             }
 
             let arrays = arrow_builder.build_arrays().unwrap();
@@ -130,8 +131,9 @@ fn benchmark_json_to_arrow(c: &mut criterion::Criterion) {
             {
                 let mut deserializer =
                     simd_json::Deserializer::from_slice(json_to_deserialize).unwrap();
-                let transcoder = serde_transcode::Transcoder::new(&mut deserializer);
-                arrow_builder.push(&transcoder).unwrap();
+                arrow_builder
+                    .push_from_deserializer(&mut deserializer)
+                    .unwrap();
             }
 
             let arrays = arrow_builder.build_arrays().unwrap();
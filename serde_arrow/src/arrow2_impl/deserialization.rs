@@ -154,7 +154,9 @@ impl BufferExtract for dyn Array {
             T::F16 => convert_primitive!(f16, F16, push_u16_cast),
             T::F32 => convert_primitive!(f32, F32, push_u32_cast),
             T::F64 => convert_primitive!(f64, F64, push_u64_cast),
+            T::Date32 => convert_primitive!(i32, Date32, push_u32_cast),
             T::Date64 => convert_primitive!(i64, Date64, push_u64_cast),
+            T::Time64(_) => convert_primitive!(i64, Time64, push_u64_cast),
             T::Decimal128(_, _) => convert_primitive!(i128, Decimal128, push_u128_cast),
             T::Timestamp(_, _) => convert_primitive!(i64, Date64, push_u64_cast),
             T::Utf8 => convert_utf8!(i32, Utf8, push_u32_cast),
@@ -169,15 +171,23 @@ impl BufferExtract for dyn Array {
 
                 let validity = get_validity(self).map(|v| buffers.push_u1(v));
                 let mut fields = Vec::new();
-
-                for (field, col) in field.children.iter().zip(typed.values()) {
+                let mut names = Vec::new();
+
+                for ((field, col), array_field) in field
+                    .children
+                    .iter()
+                    .zip(typed.values())
+                    .zip(typed.fields())
+                {
                     fields.push(col.extract_buffers(field, buffers)?);
+                    names.push(buffers.push_u8(array_field.name.as_bytes()));
                 }
 
                 Ok(M::Struct {
                     field: field.clone(),
                     validity,
                     fields,
+                    names,
                 })
             }
             T::Map => {
@@ -214,10 +224,12 @@ impl BufferExtract for dyn Array {
                 let keys = typed_keys.extract_buffers(keys_field, buffers)?;
                 let values = typed_values.extract_buffers(values_field, buffers)?;
 
+                // map entries are compiled without their field names
                 let entries = Box::new(M::Struct {
                     field: entries_field.clone(),
                     validity: None,
                     fields: vec![keys, values],
+                    names: Vec::new(),
                 });
 
                 Ok(M::Map {
@@ -291,9 +303,18 @@ impl BufferExtract for dyn Array {
                     .ok_or_else(|| error!("cannot convert array to union array"))?;
 
                 let types = buffers.push_u8_cast(typed.types().as_slice())?;
+                let Some(offsets) = typed.offsets() else {
+                    fail!("only dense unions are supported");
+                };
+                let offsets = buffers.push_u32_cast(offsets.as_slice())?;
+                let array_fields = UnionArray::get_fields(typed.data_type());
                 let mut fields = Vec::new();
-                for (field, array) in field.children.iter().zip(typed.fields()) {
+                let mut names = Vec::new();
+                for ((field, array), array_field) in
+                    field.children.iter().zip(typed.fields()).zip(array_fields)
+                {
                     fields.push(array.extract_buffers(field, buffers)?);
+                    names.push(buffers.push_u8(array_field.name.as_bytes()));
                 }
 
                 Ok(M::Union {
@@ -301,6 +322,8 @@ impl BufferExtract for dyn Array {
                     validity: None,
                     fields,
                     types,
+                    offsets,
+                    names,
                 })
             }
         }
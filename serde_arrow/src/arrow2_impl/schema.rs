@@ -4,7 +4,7 @@ use crate::{
         error::{error, fail, Error, Result},
         schema::{
             GenericDataType, GenericField, GenericTimeUnit, SchemaLike, Sealed, SerdeArrowSchema,
-            Strategy, STRATEGY_KEY,
+            Strategy, EXTENSION_NAME_KEY, STRATEGY_KEY,
         },
     },
 };
@@ -76,16 +76,23 @@ impl SchemaLike for Vec<Field> {
     ) -> Result<Self> {
         SerdeArrowSchema::from_samples(samples, options)?.to_arrow2_fields()
     }
+
+    fn from_type_and_samples<'de, T: serde::Deserialize<'de>, S: serde::Serialize + ?Sized>(
+        samples: &S,
+        options: crate::schema::TracingOptions,
+    ) -> Result<Self> {
+        SerdeArrowSchema::from_type_and_samples::<T, S>(samples, options)?.to_arrow2_fields()
+    }
 }
 
 impl TryFrom<&Field> for GenericField {
     type Error = Error;
 
     fn try_from(field: &Field) -> Result<Self> {
-        let strategy: Option<Strategy> = match field.metadata.get(STRATEGY_KEY) {
-            Some(strategy_str) => Some(strategy_str.parse::<Strategy>()?),
-            None => None,
-        };
+        let strategy = Strategy::from_metadata(
+            field.metadata.get(STRATEGY_KEY),
+            field.metadata.get(EXTENSION_NAME_KEY),
+        )?;
         let name = field.name.to_owned();
         let nullable = field.is_nullable;
 
@@ -106,7 +113,14 @@ impl TryFrom<&Field> for GenericField {
             DataType::Float64 => GenericDataType::F64,
             DataType::Utf8 => GenericDataType::Utf8,
             DataType::LargeUtf8 => GenericDataType::LargeUtf8,
+            DataType::Date32 => GenericDataType::Date32,
             DataType::Date64 => GenericDataType::Date64,
+            DataType::Time64(TimeUnit::Microsecond) => {
+                GenericDataType::Time64(GenericTimeUnit::Microsecond)
+            }
+            DataType::Time64(TimeUnit::Nanosecond) => {
+                GenericDataType::Time64(GenericTimeUnit::Nanosecond)
+            }
             DataType::Decimal(precision, scale) => {
                 if *precision > u8::MAX as usize || *scale > i8::MAX as usize {
                     fail!("cannot represent precision / scale of the decimal");
@@ -208,7 +222,15 @@ impl TryFrom<&GenericField> for Field {
             GenericDataType::F16 => DataType::Float16,
             GenericDataType::F32 => DataType::Float32,
             GenericDataType::F64 => DataType::Float64,
+            GenericDataType::Date32 => DataType::Date32,
             GenericDataType::Date64 => DataType::Date64,
+            GenericDataType::Time64(GenericTimeUnit::Microsecond) => {
+                DataType::Time64(TimeUnit::Microsecond)
+            }
+            GenericDataType::Time64(GenericTimeUnit::Nanosecond) => {
+                DataType::Time64(TimeUnit::Nanosecond)
+            }
+            GenericDataType::Time64(unit) => fail!("Time64 does not support the unit {unit}"),
             GenericDataType::Timestamp(GenericTimeUnit::Second, tz) => {
                 DataType::Timestamp(TimeUnit::Second, tz.clone())
             }
@@ -8,14 +8,14 @@ use crate::{
         },
         bitmap::Bitmap,
         buffer::Buffer,
-        datatypes::{DataType, Field},
+        datatypes::{DataType, Field, TimeUnit},
         offset::OffsetsBuffer,
         types::{f16, NativeType, Offset},
     },
     internal::{
         common::MutableBitBuffer,
         error::{fail, Result},
-        schema::GenericField,
+        schema::{GenericField, GenericTimeUnit},
         serialization_ng::{ArrayBuilder, OuterSequenceBuilder},
     },
 };
@@ -65,6 +65,16 @@ fn build_array(builder: ArrayBuilder) -> Result<Box<dyn Array>> {
         ),
         A::F32(builder) => build_primitive_array(T::Float32, builder.buffer, builder.validity),
         A::F64(builder) => build_primitive_array(T::Float64, builder.buffer, builder.validity),
+        A::Date32(builder) => build_primitive_array(T::Date32, builder.buffer, builder.validity),
+        A::Time64(builder) => build_primitive_array(
+            T::Time64(match builder.unit {
+                GenericTimeUnit::Microsecond => TimeUnit::Microsecond,
+                GenericTimeUnit::Nanosecond => TimeUnit::Nanosecond,
+                unit => fail!("Time64 does not support the unit {unit}"),
+            }),
+            builder.buffer,
+            builder.validity,
+        ),
         A::Date64(builder) => build_primitive_array(
             Field::try_from(&builder.field)?.data_type,
             builder.buffer,
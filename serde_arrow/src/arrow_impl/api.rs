@@ -1,5 +1,7 @@
 #![deny(missing_docs)]
-use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::{
     _impl::arrow::{
@@ -7,9 +9,11 @@ use crate::{
         datatypes::Field,
     },
     internal::{
-        error::Result,
+        conversions::NumericConversion,
+        deserialization::{self, row_iterator::RowIterator, Interpreter},
+        error::{fail, Result},
         schema::{GenericField, SerdeArrowSchema},
-        serialization_ng::OuterSequenceBuilder,
+        serialization_ng::{outer_sequence_builder::DictionaryDelta, OuterSequenceBuilder},
         source::deserialize_from_source,
     },
 };
@@ -89,6 +93,99 @@ impl ArrowBuilder {
         self.0.extend(items)
     }
 
+    /// Add a single record read from a deserializer
+    ///
+    /// The record is serialized while it is deserialized without constructing
+    /// an intermediate value. The deserializer must be self-describing (e.g.,
+    /// JSON), as the data is read with `deserialize_any`.
+    ///
+    /// Example: converting newline-delimited JSON
+    ///
+    /// ```rust
+    /// # fn main() -> serde_arrow::Result<()> {
+    /// # use serde_arrow::_impl::arrow as arrow;
+    /// use arrow::datatypes::{DataType, Field};
+    /// use serde_arrow::ArrowBuilder;
+    ///
+    /// let ndjson = "{\"a\": 1, \"b\": \"foo\"}\n{\"a\": 2, \"b\": null}\n";
+    ///
+    /// let mut builder = ArrowBuilder::new(&[
+    ///     Field::new("a", DataType::Int64, false),
+    ///     Field::new("b", DataType::LargeUtf8, true),
+    /// ])?;
+    /// for line in ndjson.lines() {
+    ///     builder.push_from_deserializer(&mut serde_json::Deserializer::from_str(line))?;
+    /// }
+    ///
+    /// let arrays = builder.build_arrays()?;
+    /// #
+    /// # assert_eq!(arrays.len(), 2);
+    /// # assert_eq!(arrays[0].len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn push_from_deserializer<'de, D: Deserializer<'de>>(
+        &mut self,
+        deserializer: D,
+    ) -> Result<()> {
+        self.0.push_from_deserializer(deserializer)
+    }
+
+    /// Add multiple records read from a deserializer
+    ///
+    /// The deserializer must describe a sequence of records, e.g., a JSON
+    /// array. See [`push_from_deserializer`][Self::push_from_deserializer] for
+    /// details.
+    pub fn extend_from_deserializer<'de, D: Deserializer<'de>>(
+        &mut self,
+        deserializer: D,
+    ) -> Result<()> {
+        self.0.extend_from_deserializer(deserializer)
+    }
+
+    /// Append the records of another builder
+    ///
+    /// Both builders must have been constructed from the same fields. The
+    /// records of `other` are added after the records of `self`. This allows
+    /// to fill multiple builders independently, e.g., on different threads,
+    /// and to combine them afterwards.
+    ///
+    pub fn append(&mut self, other: ArrowBuilder) -> Result<()> {
+        self.0.append(other.0)
+    }
+
+    /// Keep the dictionaries of dictionary encoded fields between batches
+    ///
+    /// Per default, each call to `build_arrays` starts a new dictionary. If
+    /// `value` is `true`, the values of previous batches are kept and the
+    /// codes of known values are stable across batches. Each batch contains
+    /// the full dictionary. The values added in the last batch can be
+    /// queried with [`dictionary_deltas`][Self::dictionary_deltas], e.g., to
+    /// emit delta dictionaries in an IPC stream.
+    ///
+    pub fn persistent_dictionaries(mut self, value: bool) -> Self {
+        self.0.set_persistent_dictionaries(value);
+        self
+    }
+
+    /// Set how numeric values are converted to the type of the array
+    ///
+    /// Per default, [`NumericConversion::Lossy`] is used. See
+    /// [`NumericConversion`] for details.
+    ///
+    pub fn numeric_conversion(mut self, value: NumericConversion) -> Self {
+        self.0.set_numeric_conversion(value);
+        self
+    }
+
+    /// The dictionary values added in the last call to `build_arrays`
+    ///
+    /// One entry is returned for each dictionary encoded field.
+    ///
+    pub fn dictionary_deltas(&self) -> Vec<DictionaryDelta> {
+        self.0.dictionary_deltas()
+    }
+
     /// Build the arrays from the rows pushed to far.
     ///
     /// This operation will reset the underlying buffers and start a new batch.
@@ -169,6 +266,90 @@ pub fn to_arrow<T: Serialize + ?Sized>(fields: &[Field], items: &T) -> Result<Ve
     builder.build_arrays()
 }
 
+/// Build arrow arrays from the given items using multiple threads (*requires
+/// one of the `arrow-*` features and the `rayon` feature*)
+///
+/// The items are split into chunks that are serialized on the rayon thread
+/// pool. The resulting arrays are identical to the ones built by
+/// [`to_arrow`].
+///
+/// ```rust
+/// # fn main() -> serde_arrow::Result<()> {
+/// # use serde_arrow::_impl::arrow;
+/// use arrow::datatypes::Field;
+/// use serde::{Serialize, Deserialize};
+/// use serde_arrow::schema::{SchemaLike, TracingOptions};
+///
+/// ##[derive(Serialize, Deserialize)]
+/// struct Record {
+///     a: Option<f32>,
+///     b: u64,
+/// }
+///
+/// let items = (0..1000)
+///     .map(|i| Record { a: Some(i as f32), b: i })
+///     .collect::<Vec<_>>();
+///
+/// let fields = Vec::<Field>::from_type::<Record>(TracingOptions::default())?;
+/// let arrays = serde_arrow::to_arrow_parallel(&fields, &items)?;
+/// #
+/// # assert_eq!(arrays.len(), 2);
+/// # assert_eq!(arrays[0].len(), 1000);
+/// # Ok(())
+/// # }
+/// ```
+///
+#[cfg(feature = "rayon")]
+pub fn to_arrow_parallel<T: Serialize + Sync>(
+    fields: &[Field],
+    items: &[T],
+) -> Result<Vec<ArrayRef>> {
+    let mut builder = ArrowBuilder::new(fields)?;
+    builder.0.extend_parallel(items)?;
+    builder.build_arrays()
+}
+
+/// Build arrow arrays from a struct of columns (*requires one of the
+/// `arrow-*` features*)
+///
+/// Each field of `value` (e.g., a struct of vectors or a map of sequences)
+/// is serialized into the array of the same name. All columns must have the
+/// same number of values.
+///
+/// ```rust
+/// # fn main() -> serde_arrow::Result<()> {
+/// # use serde_arrow::_impl::arrow;
+/// use arrow::datatypes::{DataType, Field};
+/// use serde::Serialize;
+///
+/// ##[derive(Serialize)]
+/// struct Columns {
+///     a: Vec<Option<f32>>,
+///     b: Vec<u64>,
+/// }
+///
+/// let fields = vec![
+///     Field::new("a", DataType::Float32, true),
+///     Field::new("b", DataType::UInt64, false),
+/// ];
+/// let columns = Columns { a: vec![Some(1.0), None], b: vec![2, 3] };
+/// let arrays = serde_arrow::to_arrow_columns(&fields, &columns)?;
+/// #
+/// # assert_eq!(arrays.len(), 2);
+/// # assert_eq!(arrays[0].len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+///
+pub fn to_arrow_columns<T: Serialize + ?Sized>(
+    fields: &[Field],
+    value: &T,
+) -> Result<Vec<ArrayRef>> {
+    let mut builder = ArrowBuilder::new(fields)?;
+    builder.0.extend_columns(value)?;
+    builder.build_arrays()
+}
+
 /// Deserialize items from arrow arrays (*requires one of the `arrow-*`
 /// features*)
 ///
@@ -176,6 +357,13 @@ pub fn to_arrow<T: Serialize + ?Sized>(fields: &[Field], items: &T) -> Result<Ve
 /// deserialize items encoding single values consider the
 /// [`Items`][crate::utils::Items] wrapper.
 ///
+/// Values of `Utf8`, `LargeUtf8` and dictionary encoded string arrays are
+/// borrowed from the arrays without copying, if the type requests it. This
+/// includes `&'de str`, `&'de [u8]` (the UTF-8 bytes of the strings) and
+/// fields of type `Cow<'de, str>` or `Cow<'de, [u8]>` marked with
+/// `#[serde(borrow)]`. Note that serde deserializes `Cow` values nested in
+/// other types, e.g., `Option<Cow<'de, str>>`, always as owned values.
+///
 /// ```rust
 /// # fn main() -> serde_arrow::Result<()> {
 /// # use serde_arrow::_impl::arrow;
@@ -203,28 +391,353 @@ where
     T: Deserialize<'de>,
     A: AsRef<dyn Array>,
 {
-    use crate::internal::{
-        common::{BufferExtract, Buffers},
-        deserialization,
-    };
+    let (_, interpreter) = compile_arrays(fields, arrays)?;
+    deserialize_from_source(interpreter)
+}
 
-    let fields = fields
+/// Lazily deserialize items from arrow arrays, one row at a time (*requires
+/// one of the `arrow-*` features*)
+///
+/// In contrast to [`from_arrow`][crate::from_arrow], the items are not collected into
+/// a container. Each row is deserialized when the iterator is advanced, rows
+/// skipped with `nth` or `skip` are not deserialized at all. The rows may
+/// borrow strings from the arrays.
+///
+/// ```rust
+/// # fn main() -> serde_arrow::Result<()> {
+/// # use serde_arrow::_impl::arrow;
+/// use arrow::datatypes::Field;
+/// use serde::{Deserialize, Serialize};
+/// use serde_arrow::schema::{SchemaLike, TracingOptions};
+///
+/// ##[derive(Deserialize, Serialize)]
+/// struct Record<'a> {
+///     a: Option<f32>,
+///     b: &'a str,
+/// }
+///
+/// let fields = Vec::<Field>::from_type::<Record>(TracingOptions::default())?;
+/// # let items = &[Record { a: Some(1.0), b: "foo" }, Record { a: None, b: "bar" }];
+/// # let arrays = serde_arrow::to_arrow(&fields, &items)?;
+/// #
+/// let mut rows = serde_arrow::from_arrow_iter::<Record, _>(&fields, &arrays)?;
+/// assert_eq!(rows.len(), 2);
+///
+/// let record = rows.nth(1).unwrap()?;
+/// assert_eq!(record.b, "bar");
+/// # Ok(())
+/// # }
+/// ```
+///
+pub fn from_arrow_iter<'de, T, A>(
+    fields: &'de [Field],
+    arrays: &'de [A],
+) -> Result<RowIterator<'de, T>>
+where
+    T: Deserialize<'de>,
+    A: AsRef<dyn Array>,
+{
+    let (num_items, interpreter) = compile_arrays(fields, arrays)?;
+    Ok(RowIterator::new(interpreter, num_items))
+}
+
+/// Deserialize a single row from arrow arrays (*requires one of the
+/// `arrow-*` features*)
+///
+/// Only the requested row is deserialized. The type should be a single record
+/// (e.g., a struct), not a list of records.
+///
+/// ```rust
+/// # fn main() -> serde_arrow::Result<()> {
+/// # use serde_arrow::_impl::arrow;
+/// use arrow::datatypes::Field;
+/// use serde::{Deserialize, Serialize};
+/// use serde_arrow::schema::{SchemaLike, TracingOptions};
+///
+/// ##[derive(Debug, PartialEq, Deserialize, Serialize)]
+/// struct Record {
+///     a: Option<f32>,
+///     b: Vec<u64>,
+/// }
+///
+/// let fields = Vec::<Field>::from_type::<Record>(TracingOptions::default())?;
+/// # let items = (0..10).map(|i| Record { a: Some(i as f32), b: vec![i; i as usize] }).collect::<Vec<_>>();
+/// # let arrays = serde_arrow::to_arrow(&fields, &items)?;
+/// #
+/// let record: Record = serde_arrow::from_arrow_row(&fields, &arrays, 3)?;
+/// assert_eq!(record, Record { a: Some(3.0), b: vec![3, 3, 3] });
+/// # Ok(())
+/// # }
+/// ```
+///
+pub fn from_arrow_row<'de, T, A>(fields: &'de [Field], arrays: &'de [A], index: usize) -> Result<T>
+where
+    T: Deserialize<'de>,
+    A: AsRef<dyn Array>,
+{
+    let (num_items, mut interpreter) = compile_arrays(fields, arrays)?;
+    if index >= num_items {
+        fail!("Cannot get row {index} of arrays with {num_items} rows");
+    }
+    interpreter.seek(index)?;
+    deserialize_from_source(interpreter.row_events())
+}
+
+/// Deserialize a range of rows from arrow arrays (*requires one of the
+/// `arrow-*` features*)
+///
+/// Similar to [`from_arrow`][crate::from_arrow], but only the rows in the given range are
+/// deserialized. The type should be a list of records.
+///
+/// ```rust
+/// # fn main() -> serde_arrow::Result<()> {
+/// # use serde_arrow::_impl::arrow;
+/// use arrow::datatypes::Field;
+/// use serde::{Deserialize, Serialize};
+/// use serde_arrow::schema::{SchemaLike, TracingOptions};
+///
+/// ##[derive(Debug, PartialEq, Deserialize, Serialize)]
+/// struct Record {
+///     a: Option<f32>,
+///     b: Vec<u64>,
+/// }
+///
+/// let fields = Vec::<Field>::from_type::<Record>(TracingOptions::default())?;
+/// # let items = (0..10).map(|i| Record { a: Some(i as f32), b: vec![i; i as usize] }).collect::<Vec<_>>();
+/// # let arrays = serde_arrow::to_arrow(&fields, &items)?;
+/// #
+/// let records: Vec<Record> = serde_arrow::from_arrow_range(&fields, &arrays, 2..4)?;
+/// assert_eq!(records, vec![
+///     Record { a: Some(2.0), b: vec![2, 2] },
+///     Record { a: Some(3.0), b: vec![3, 3, 3] },
+/// ]);
+/// # Ok(())
+/// # }
+/// ```
+///
+pub fn from_arrow_range<'de, T, A>(
+    fields: &'de [Field],
+    arrays: &'de [A],
+    rows: Range<usize>,
+) -> Result<T>
+where
+    T: Deserialize<'de>,
+    A: AsRef<dyn Array>,
+{
+    let (_, mut interpreter) = compile_arrays(fields, arrays)?;
+    interpreter.select_rows(rows)?;
+    deserialize_from_source(interpreter)
+}
+
+/// Deserialize items from arrow arrays using multiple threads (*requires one of
+/// the `arrow-*` features and the `rayon` feature*)
+///
+/// The rows are split into ranges that are deserialized on the rayon thread
+/// pool. The results are concatenated in order. In contrast to
+/// [`from_arrow`][crate::from_arrow], the type should be a single record and
+/// the result is a vector of records.
+///
+/// ```rust
+/// # fn main() -> serde_arrow::Result<()> {
+/// # use serde_arrow::_impl::arrow;
+/// use arrow::datatypes::Field;
+/// use serde::{Deserialize, Serialize};
+/// use serde_arrow::schema::{SchemaLike, TracingOptions};
+///
+/// ##[derive(Debug, PartialEq, Deserialize, Serialize)]
+/// struct Record {
+///     a: Option<f32>,
+///     b: Vec<u64>,
+/// }
+///
+/// let fields = Vec::<Field>::from_type::<Record>(TracingOptions::default())?;
+/// # let items = (0..1000).map(|i| Record { a: Some(i as f32), b: vec![i; (i % 5) as usize] }).collect::<Vec<_>>();
+/// # let arrays = serde_arrow::to_arrow(&fields, &items)?;
+/// #
+/// let records: Vec<Record> = serde_arrow::from_arrow_parallel(&fields, &arrays)?;
+/// # assert_eq!(records, items);
+/// # Ok(())
+/// # }
+/// ```
+///
+#[cfg(feature = "rayon")]
+pub fn from_arrow_parallel<'de, T, A>(fields: &'de [Field], arrays: &'de [A]) -> Result<Vec<T>>
+where
+    T: Deserialize<'de> + Send,
+    A: AsRef<dyn Array>,
+{
+    let (_, interpreter) = compile_arrays(fields, arrays)?;
+    interpreter.deserialize_parallel()
+}
+
+/// Deserialize a struct of columns from arrow arrays (*requires one of the
+/// `arrow-*` features*)
+///
+/// Each field of `T` is deserialized from the whole array of the same name,
+/// e.g., a struct of vectors. The values are not transposed into rows.
+///
+/// ```rust
+/// # fn main() -> serde_arrow::Result<()> {
+/// # use serde_arrow::_impl::arrow;
+/// use arrow::datatypes::{DataType, Field};
+/// use serde::{Deserialize, Serialize};
+///
+/// ##[derive(Debug, PartialEq, Deserialize, Serialize)]
+/// struct Columns {
+///     a: Vec<Option<f32>>,
+///     b: Vec<u64>,
+/// }
+///
+/// let fields = vec![
+///     Field::new("a", DataType::Float32, true),
+///     Field::new("b", DataType::UInt64, false),
+/// ];
+/// # let columns = Columns { a: vec![Some(1.0), None], b: vec![2, 3] };
+/// # let arrays = serde_arrow::to_arrow_columns(&fields, &columns)?;
+/// #
+/// let columns: Columns = serde_arrow::from_arrow_columns(&fields, &arrays)?;
+/// assert_eq!(columns, Columns { a: vec![Some(1.0), None], b: vec![2, 3] });
+/// # Ok(())
+/// # }
+/// ```
+///
+pub fn from_arrow_columns<'de, T, A>(fields: &'de [Field], arrays: &'de [A]) -> Result<T>
+where
+    T: Deserialize<'de>,
+    A: AsRef<dyn Array>,
+{
+    let arrays = fields
+        .iter()
+        .zip(arrays)
+        .map(|(field, array)| (field, Some(array.as_ref())))
+        .collect();
+    let (_, interpreter) = compile(arrays, true)?;
+    deserialize_from_source(interpreter)
+}
+
+/// Deserialize items from arrow arrays that may lack some fields of the
+/// target schema (*requires one of the `arrow-*` features*)
+///
+/// The arrays are described by `fields` and matched to the fields of
+/// `target` by name. Fields of `target` without array are filled in: nullable
+/// fields are deserialized as null in each row, non-nullable fields are not
+/// emitted at all, so that serde's default handling applies (e.g., for fields
+/// marked with `#[serde(default)]`). Arrays without matching field in `target`
+/// are ignored.
+///
+/// ```rust
+/// # fn main() -> serde_arrow::Result<()> {
+/// # use serde_arrow::_impl::arrow;
+/// use arrow::datatypes::Field;
+/// use serde::{Deserialize, Serialize};
+/// use serde_arrow::schema::{SchemaLike, TracingOptions};
+///
+/// ##[derive(Deserialize, Serialize)]
+/// struct OldRecord {
+///     a: u64,
+/// }
+///
+/// ##[derive(Debug, PartialEq, Deserialize, Serialize)]
+/// struct Record {
+///     a: u64,
+///     b: Option<String>,
+///     ##[serde(default)]
+///     c: u32,
+/// }
+///
+/// let fields = Vec::<Field>::from_type::<OldRecord>(TracingOptions::default())?;
+/// let arrays = serde_arrow::to_arrow(&fields, &[OldRecord { a: 1 }])?;
+///
+/// let target = Vec::<Field>::from_type::<Record>(TracingOptions::default())?;
+/// let items: Vec<Record> = serde_arrow::from_arrow_with_defaults(&target, &fields, &arrays)?;
+/// assert_eq!(items, vec![Record { a: 1, b: None, c: 0 }]);
+/// # Ok(())
+/// # }
+/// ```
+///
+pub fn from_arrow_with_defaults<'de, T, A>(
+    target: &'de [Field],
+    fields: &'de [Field],
+    arrays: &'de [A],
+) -> Result<T>
+where
+    T: Deserialize<'de>,
+    A: AsRef<dyn Array>,
+{
+    let mut matched = Vec::new();
+    for target_field in target {
+        let position = fields
+            .iter()
+            .position(|field| field.name() == target_field.name());
+        match position {
+            Some(idx) => {
+                let Some(array) = arrays.get(idx) else {
+                    fail!("Missing array for field {}", fields[idx].name());
+                };
+                matched.push((&fields[idx], Some(array.as_ref())));
+            }
+            None if target_field.is_nullable() => matched.push((target_field, None)),
+            None => {}
+        }
+    }
+
+    let (_, interpreter) = compile(matched, false)?;
+    deserialize_from_source(interpreter)
+}
+
+fn compile_arrays<'de, A>(
+    fields: &'de [Field],
+    arrays: &'de [A],
+) -> Result<(usize, Interpreter<'de>)>
+where
+    A: AsRef<dyn Array>,
+{
+    let arrays = fields
         .iter()
-        .map(GenericField::try_from)
-        .collect::<Result<Vec<_>>>()?;
+        .zip(arrays)
+        .map(|(field, array)| (field, Some(array.as_ref())))
+        .collect();
+    compile(arrays, false)
+}
+
+fn compile<'de>(
+    arrays: Vec<(&'de Field, Option<&'de (dyn Array + 'static)>)>,
+    as_columns: bool,
+) -> Result<(usize, Interpreter<'de>)> {
+    use crate::internal::common::{ArrayMapping, BufferExtract, Buffers};
 
     let num_items = arrays
         .iter()
-        .map(|a| a.as_ref().len())
+        .filter_map(|(_, array)| Some(array.as_ref()?.len()))
         .min()
         .unwrap_or_default();
 
     let mut buffers = Buffers::new();
     let mut mappings = Vec::new();
-    for (field, array) in fields.iter().zip(arrays.iter()) {
-        mappings.push(array.as_ref().extract_buffers(field, &mut buffers)?);
+    let mut field_names = Vec::new();
+    for (field, array) in arrays {
+        let generic_field = GenericField::try_from(field)?;
+        let mapping = match array {
+            Some(array) => array.extract_buffers(&generic_field, &mut buffers)?,
+            None => ArrayMapping::Null {
+                field: generic_field,
+                validity: None,
+                buffer: usize::MAX,
+            },
+        };
+        mappings.push(mapping);
+        field_names.push(buffers.push_u8(field.name().as_bytes()));
     }
 
-    let interpreter = deserialization::compile_deserialization(num_items, &mappings, buffers)?;
-    deserialize_from_source(interpreter)
+    let interpreter = if as_columns {
+        deserialization::compile_column_deserialization(
+            num_items,
+            &mappings,
+            &field_names,
+            buffers,
+        )?
+    } else {
+        deserialization::compile_deserialization(num_items, &mappings, &field_names, buffers)?
+    };
+    Ok((num_items, interpreter))
 }
@@ -12,10 +12,10 @@ use crate::_impl::arrow::{
         PrimitiveArray, StringArray, StructArray,
     },
     datatypes::{
-        DataType, Date64Type, Decimal128Type, Float16Type, Float32Type, Float64Type, Int16Type,
-        Int32Type, Int64Type, Int8Type, TimestampMicrosecondType, TimestampMillisecondType,
-        TimestampNanosecondType, TimestampSecondType, UInt16Type, UInt32Type, UInt64Type,
-        UInt8Type,
+        DataType, Date32Type, Date64Type, Decimal128Type, Float16Type, Float32Type, Float64Type,
+        Int16Type, Int32Type, Int64Type, Int8Type, Time64MicrosecondType, Time64NanosecondType,
+        TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+        TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
     },
 };
 
@@ -147,7 +147,15 @@ impl BufferExtract for dyn Array {
             T::F16 => convert_primitive!(Float16Type, F16, push_u16_cast),
             T::F32 => convert_primitive!(Float32Type, F32, push_u32_cast),
             T::F64 => convert_primitive!(Float64Type, F64, push_u64_cast),
+            T::Date32 => convert_primitive!(Date32Type, Date32, push_u32_cast),
             T::Date64 => convert_primitive!(Date64Type, Date64, push_u64_cast),
+            T::Time64(U::Microsecond) => {
+                convert_primitive!(Time64MicrosecondType, Time64, push_u64_cast)
+            }
+            T::Time64(U::Nanosecond) => {
+                convert_primitive!(Time64NanosecondType, Time64, push_u64_cast)
+            }
+            T::Time64(unit) => fail!("Time64 does not support the unit {unit}"),
             T::Decimal128(_, _) => convert_primitive!(Decimal128Type, Decimal128, push_u128_cast),
             T::Timestamp(U::Second, _) => {
                 convert_primitive!(TimestampSecondType, Date64, push_u64_cast)
@@ -172,17 +180,28 @@ impl BufferExtract for dyn Array {
                         self.data_type()
                     )
                 })?;
+                let DataType::Struct(array_fields) = self.data_type() else {
+                    fail!("cannot get fields of struct array");
+                };
                 let validity = get_validity(self).map(|v| buffers.push_u1(v));
                 let mut fields = Vec::new();
-
-                for (field, col) in field.children.iter().zip(typed.columns()) {
+                let mut names = Vec::new();
+
+                for ((field, col), array_field) in field
+                    .children
+                    .iter()
+                    .zip(typed.columns())
+                    .zip(array_fields.iter())
+                {
                     fields.push(col.extract_buffers(field, buffers)?);
+                    names.push(buffers.push_u8(array_field.name().as_bytes()));
                 }
 
                 Ok(M::Struct {
                     field: field.clone(),
                     validity,
                     fields,
+                    names,
                 })
             }
             T::Map => {
@@ -210,10 +229,12 @@ impl BufferExtract for dyn Array {
                 let keys = typed.keys().extract_buffers(keys_field, buffers)?;
                 let values = typed.values().extract_buffers(values_field, buffers)?;
 
+                // map entries are compiled without their field names
                 let entries = Box::new(M::Struct {
                     field: entries_field.clone(),
                     validity: None,
                     fields: vec![keys, values],
+                    names: Vec::new(),
                 });
 
                 Ok(M::Map {
@@ -288,12 +309,23 @@ impl BufferExtract for dyn Array {
                     error!("cannot convert {} array to union array", self.data_type())
                 })?;
 
+                let DataType::Union(array_fields, _) = self.data_type() else {
+                    fail!("cannot get fields of union array");
+                };
                 let types = buffers.push_u8_cast(typed.type_ids())?;
+                let Some(offsets) = typed.offsets() else {
+                    fail!("only dense unions are supported");
+                };
+                let offsets = buffers.push_u32_cast(offsets)?;
 
                 let mut fields = Vec::new();
-                for (idx, field) in field.children.iter().enumerate() {
+                let mut names = Vec::new();
+                for ((idx, field), (_, array_field)) in
+                    field.children.iter().enumerate().zip(array_fields.iter())
+                {
                     let array = typed.child(idx.try_into()?);
                     fields.push(array.extract_buffers(field, buffers)?);
+                    names.push(buffers.push_u8(array_field.name().as_bytes()));
                 }
 
                 Ok(M::Union {
@@ -301,6 +333,8 @@ impl BufferExtract for dyn Array {
                     validity: None,
                     fields,
                     types,
+                    offsets,
+                    names,
                 })
             }
         }
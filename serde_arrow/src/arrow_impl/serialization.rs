@@ -5,11 +5,12 @@ use crate::{
     _impl::arrow::{
         array::{make_array, Array, ArrayData, ArrayRef, NullArray},
         buffer::{Buffer, ScalarBuffer},
-        datatypes::{ArrowNativeType, ArrowPrimitiveType, DataType, Field, Float16Type},
+        datatypes::{ArrowNativeType, ArrowPrimitiveType, DataType, Field, Float16Type, TimeUnit},
     },
     internal::{
         common::MutableBitBuffer,
         error::{fail, Result},
+        schema::GenericTimeUnit,
         serialization_ng::{ArrayBuilder, OuterSequenceBuilder},
     },
 };
@@ -60,6 +61,18 @@ fn build_array_data(builder: ArrayBuilder) -> Result<ArrayData> {
         ),
         A::F32(builder) => build_array_data_primitive(T::Float32, builder.buffer, builder.validity),
         A::F64(builder) => build_array_data_primitive(T::Float64, builder.buffer, builder.validity),
+        A::Date32(builder) => {
+            build_array_data_primitive(T::Date32, builder.buffer, builder.validity)
+        }
+        A::Time64(builder) => build_array_data_primitive(
+            T::Time64(match builder.unit {
+                GenericTimeUnit::Microsecond => TimeUnit::Microsecond,
+                GenericTimeUnit::Nanosecond => TimeUnit::Nanosecond,
+                unit => fail!("Time64 does not support the unit {unit}"),
+            }),
+            builder.buffer,
+            builder.validity,
+        ),
         A::Date64(builder) => build_array_data_primitive(
             Field::try_from(&builder.field)?.data_type().clone(),
             builder.buffer,
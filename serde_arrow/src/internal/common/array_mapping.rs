@@ -31,6 +31,8 @@ macro_rules! define_array_mapping {
         pub enum ArrayMapping {
             $(
                 $variant {
+                    // not read for all variants, but kept to simplify debugging
+                    #[allow(dead_code)]
                     field: GenericField,
                     validity: Option<usize>,
                     $( $field:$ty, )*
@@ -39,12 +41,6 @@ macro_rules! define_array_mapping {
         }
 
         impl ArrayMapping {
-            pub fn get_field(&self) -> &GenericField {
-                match self {
-                    $(  ArrayMapping::$variant { field, .. } => field, )*
-                }
-            }
-
             pub fn get_validity(&self) -> Option<usize> {
                 match self {
                     $(  ArrayMapping::$variant { validity, .. } => *validity, )*
@@ -106,9 +102,15 @@ define_array_mapping!(
         buffer: usize,
         offsets: usize,
     },
+    Date32 {
+        buffer: usize,
+    },
     Date64 {
         buffer: usize,
     },
+    Time64 {
+        buffer: usize,
+    },
     List {
         item: Box<ArrayMapping>,
         offsets: usize,
@@ -123,10 +125,15 @@ define_array_mapping!(
     },
     Struct {
         fields: Vec<ArrayMapping>,
+        // the u8 buffers containing the field names
+        names: Vec<usize>,
     },
     Union {
         fields: Vec<ArrayMapping>,
         types: usize,
+        offsets: usize,
+        // the u8 buffers containing the variant names
+        names: Vec<usize>,
     },
     Map {
         offsets: usize,
@@ -3,7 +3,10 @@ use std::sync::Arc;
 use bytemuck::NoUninit;
 
 use super::array_mapping::ArrayMapping;
-use crate::internal::{error::Result, schema::GenericField};
+use crate::internal::{
+    error::{fail, Result},
+    schema::GenericField,
+};
 
 pub trait BufferExtract {
     fn len(&self) -> usize;
@@ -43,7 +46,7 @@ impl<T: BufferExtract + ?Sized> BufferExtract for Arc<T> {
 }
 
 /// Readonly buffers
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct Buffers<'a> {
     pub u0: Vec<usize>,
     pub u1: Vec<BitBuffer<'a>>,
@@ -209,15 +212,42 @@ impl MutableBitBuffer {
     pub fn clear(&mut self) {
         *self = Self::default();
     }
+
+    pub fn is_set(&self, idx: usize) -> bool {
+        let flag = 1 << (idx % 8);
+        (self.buffer[idx / 8] & flag) == flag
+    }
+
+    /// Append the bits of another buffer
+    pub fn append(&mut self, other: &MutableBitBuffer) {
+        for idx in 0..other.len {
+            self.push(other.is_set(idx));
+        }
+    }
 }
 
 pub trait Offset: std::ops::Add<Self, Output = Self> + Clone + Default {
     fn try_form_usize(val: usize) -> Result<Self>;
+    /// Add the offsets, fail if the result cannot be represented
+    fn try_add(&self, other: &Self) -> Result<Self>;
 }
 
 impl Offset for i32 {
     fn try_form_usize(val: usize) -> Result<Self> {
-        Ok(i32::try_from(val)?)
+        match i32::try_from(val) {
+            Ok(val) => Ok(val),
+            Err(_) => fail!("{}", offset_overflow_message(val)),
+        }
+    }
+
+    fn try_add(&self, other: &Self) -> Result<Self> {
+        match self.checked_add(*other) {
+            Some(val) => Ok(val),
+            None => fail!(
+                "{}",
+                offset_overflow_message(*self as usize + *other as usize)
+            ),
+        }
     }
 }
 
@@ -225,6 +255,25 @@ impl Offset for i64 {
     fn try_form_usize(val: usize) -> Result<Self> {
         Ok(i64::try_from(val)?)
     }
+
+    fn try_add(&self, other: &Self) -> Result<Self> {
+        match self.checked_add(*other) {
+            Some(val) => Ok(val),
+            None => fail!("Offset overflow: the offsets exceed the range of i64"),
+        }
+    }
+}
+
+fn offset_overflow_message(offset: usize) -> String {
+    format!(
+        concat!(
+            "Offset overflow: the offset {offset} exceeds the maximum of 32 bit offsets ({max}). ",
+            "Consider using types with 64 bit offsets (`LargeUtf8`, `LargeList`), e.g., by ",
+            "setting `large_utf8` / `large_list` in `TracingOptions`",
+        ),
+        offset = offset,
+        max = i32::MAX,
+    )
 }
 
 #[derive(Debug, Clone)]
@@ -251,7 +300,9 @@ impl<O: Offset> MutableOffsetBuffer<O> {
 
     // push a new item with the given number of children
     pub fn push(&mut self, num_children: usize) -> Result<()> {
-        self.current_items = self.current_items.clone() + O::try_form_usize(num_children)?;
+        self.current_items = self
+            .current_items
+            .try_add(&O::try_form_usize(num_children)?)?;
         self.offsets.push(self.current_items.clone());
 
         Ok(())
@@ -262,7 +313,17 @@ impl<O: Offset> MutableOffsetBuffer<O> {
     }
 
     pub fn inc_current_items(&mut self) -> Result<()> {
-        self.current_items = self.current_items.clone() + O::try_form_usize(1)?;
+        self.current_items = self.current_items.try_add(&O::try_form_usize(1)?)?;
+        Ok(())
+    }
+
+    /// Append the offsets of another buffer, shifted by the current items
+    pub fn append(&mut self, other: MutableOffsetBuffer<O>) -> Result<()> {
+        let current_items = self.current_items.try_add(&other.current_items)?;
+        for offset in other.offsets.into_iter().skip(1) {
+            self.offsets.push(self.current_items.clone() + offset);
+        }
+        self.current_items = current_items;
         Ok(())
     }
 
@@ -270,3 +331,42 @@ impl<O: Offset> MutableOffsetBuffer<O> {
         *self = Self::default();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::MutableOffsetBuffer;
+
+    #[test]
+    fn i32_offsets_overflow() {
+        let mut offsets = MutableOffsetBuffer::<i32>::default();
+        offsets.push(i32::MAX as usize).unwrap();
+
+        let err = offsets.push(1).unwrap_err();
+        assert!(err.to_string().contains("Offset overflow"), "{err}");
+        assert!(err.to_string().contains("64 bit offsets"), "{err}");
+
+        let err = offsets.inc_current_items().unwrap_err();
+        assert!(err.to_string().contains("Offset overflow"), "{err}");
+
+        let mut other = MutableOffsetBuffer::<i32>::default();
+        other.push(1).unwrap();
+        let err = offsets.append(other).unwrap_err();
+        assert!(err.to_string().contains("Offset overflow"), "{err}");
+
+        let err = MutableOffsetBuffer::<i32>::default()
+            .push(i32::MAX as usize + 1)
+            .unwrap_err();
+        assert!(err.to_string().contains("Offset overflow"), "{err}");
+    }
+
+    #[test]
+    fn i64_offsets() {
+        let mut offsets = MutableOffsetBuffer::<i64>::default();
+        offsets.push(i32::MAX as usize).unwrap();
+        offsets.push(1).unwrap();
+        assert_eq!(
+            offsets.offsets,
+            vec![0, i32::MAX as i64, i32::MAX as i64 + 1]
+        );
+    }
+}
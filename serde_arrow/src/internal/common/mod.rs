@@ -2,6 +2,7 @@
 mod array_mapping;
 mod buffers;
 mod checks;
+pub mod string_formats;
 
 pub use array_mapping::{ArrayMapping, DictionaryIndex, DictionaryValue};
 #[allow(unused)]
@@ -0,0 +1,372 @@
+//! Matchers for the string formats understood by tracing and serialization
+
+/// The maximum precision of `Decimal128` fields
+pub const MAX_DECIMAL_PRECISION: u8 = 38;
+
+mod parsing {
+    pub const DIGIT: &[char] = &['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+
+    pub fn match_optional_sign(s: &str) -> Result<&str, &str> {
+        Ok(s.strip_prefix(['+', '-']).unwrap_or(s))
+    }
+
+    pub fn match_one_or_more_digits(s: &str) -> Result<&str, &str> {
+        let mut s = s.strip_prefix(DIGIT).ok_or(s)?;
+        while let Some(new_s) = s.strip_prefix(DIGIT) {
+            s = new_s;
+        }
+        Ok(s)
+    }
+
+    pub fn match_one_or_two_digits(s: &str) -> Result<&str, &str> {
+        let s = s.strip_prefix(DIGIT).ok_or(s)?;
+        Ok(s.strip_prefix(DIGIT).unwrap_or(s))
+    }
+
+    pub fn match_char(s: &str, c: char) -> Result<&str, &str> {
+        s.strip_prefix(c).ok_or(s)
+    }
+
+    pub fn matches_naive_datetime_with_sep<'a>(
+        s: &'a str,
+        sep: &'_ [char],
+    ) -> Result<&'a str, &'a str> {
+        let s = s.trim();
+        let s = match_optional_sign(s)?;
+        let s = match_one_or_more_digits(s)?;
+        let s = match_char(s, '-')?;
+        let s = match_one_or_two_digits(s)?;
+        let s = match_char(s, '-')?;
+        let s = match_one_or_two_digits(s)?;
+        let s = s.strip_prefix(sep).ok_or(s)?;
+        let s = match_one_or_two_digits(s)?;
+        let s = match_char(s, ':')?;
+        let s = match_one_or_two_digits(s)?;
+        let s = match_char(s, ':')?;
+        let s = match_one_or_two_digits(s)?;
+
+        if let Some(s) = s.strip_prefix('.') {
+            match_one_or_more_digits(s)
+        } else {
+            Ok(s)
+        }
+    }
+
+    pub fn matches_naive_datetime(s: &str) -> Result<&str, &str> {
+        matches_naive_datetime_with_sep(s, &['T', ' '])
+    }
+
+    pub fn matches_utc_datetime(s: &str) -> Result<&str, &str> {
+        let s = matches_naive_datetime_with_sep(s, &['T', ' '])?;
+
+        if let Some(s) = s.strip_prefix('Z') {
+            Ok(s)
+        } else if let Some(s) = s.strip_prefix("+0000") {
+            Ok(s)
+        } else if let Some(s) = s.strip_prefix("+00:00") {
+            Ok(s)
+        } else {
+            Err(s)
+        }
+    }
+
+    pub fn matches_offset_datetime(s: &str) -> Result<&str, &str> {
+        let s = matches_naive_datetime_with_sep(s, &['T', ' '])?;
+
+        if let Some(s) = s.strip_prefix('Z') {
+            return Ok(s);
+        }
+        let s = s.strip_prefix(['+', '-']).ok_or(s)?;
+        let s = match_two_digits(s)?;
+        let s = s.strip_prefix(':').unwrap_or(s);
+        match_two_digits(s)
+    }
+
+    pub fn matches_date(s: &str) -> Result<&str, &str> {
+        let s = s.trim();
+        let s = match_optional_sign(s)?;
+        let s = match_one_or_more_digits(s)?;
+        let s = match_char(s, '-')?;
+        let s = match_one_or_two_digits(s)?;
+        let s = match_char(s, '-')?;
+        match_one_or_two_digits(s)
+    }
+
+    pub fn matches_time(s: &str) -> Result<&str, &str> {
+        let s = s.trim();
+        let s = match_one_or_two_digits(s)?;
+        let s = match_char(s, ':')?;
+        let s = match_one_or_two_digits(s)?;
+
+        let Some(s) = s.strip_prefix(':') else {
+            return Ok(s);
+        };
+        let s = match_one_or_two_digits(s)?;
+
+        if let Some(s) = s.strip_prefix('.') {
+            match_one_or_more_digits(s)
+        } else {
+            Ok(s)
+        }
+    }
+
+    pub fn match_two_digits(s: &str) -> Result<&str, &str> {
+        let s = s.strip_prefix(DIGIT).ok_or(s)?;
+        s.strip_prefix(DIGIT).ok_or(s)
+    }
+}
+
+pub fn matches_naive_datetime(s: &str) -> bool {
+    parsing::matches_naive_datetime(s)
+        .map(|s| s.is_empty())
+        .unwrap_or_default()
+}
+
+pub fn matches_utc_datetime(s: &str) -> bool {
+    parsing::matches_utc_datetime(s)
+        .map(|s| s.is_empty())
+        .unwrap_or_default()
+}
+
+/// Check whether the string is an integer that can be serialized as `I64`
+pub fn matches_integer(s: &str) -> bool {
+    s.parse::<i64>().is_ok()
+}
+
+/// Check whether the string is a float that can be serialized as `F64`
+///
+/// Special values such as `inf` or `NaN` are not considered numbers, as they
+/// are more likely to be regular strings.
+pub fn matches_float(s: &str) -> bool {
+    s.contains(parsing::DIGIT) && s.parse::<f64>().is_ok()
+}
+
+/// Check whether the string is a datetime with offset, e.g.,
+/// `2024-01-31T12:00:00+02:00`
+pub fn matches_offset_datetime(s: &str) -> bool {
+    parsing::matches_offset_datetime(s)
+        .map(|s| s.is_empty())
+        .unwrap_or_default()
+}
+
+/// Check whether the string is a date without time, e.g., `2024-01-31`
+pub fn matches_date(s: &str) -> bool {
+    parsing::matches_date(s)
+        .map(|s| s.is_empty())
+        .unwrap_or_default()
+}
+
+/// Check whether the string is a time without date, e.g., `12:30:00`
+pub fn matches_time(s: &str) -> bool {
+    parsing::matches_time(s)
+        .map(|s| s.is_empty())
+        .unwrap_or_default()
+}
+
+/// Check whether the string is a decimal number without exponent, e.g.,
+/// `-12.50`, and return its precision and scale
+///
+/// Decimals with more than 38 digits are not matched, as they cannot be stored
+/// in a `Decimal128` field.
+pub fn matches_decimal(s: &str) -> Option<(u8, i8)> {
+    let s = s.strip_prefix(['+', '-']).unwrap_or(s);
+    let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+
+    if integer.is_empty() || !integer.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    if s.contains('.') && fraction.is_empty() {
+        return None;
+    }
+    if !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let integer_digits = integer.trim_start_matches('0').len();
+    let precision = std::cmp::max(integer_digits + fraction.len(), 1);
+    if precision > MAX_DECIMAL_PRECISION as usize {
+        return None;
+    }
+    Some((precision as u8, fraction.len() as i8))
+}
+
+/// Check whether the string is a UUID in its hyphenated form, e.g.,
+/// `67e55044-10b1-426f-9247-bb680e5fe0c8`
+pub fn matches_uuid(s: &str) -> bool {
+    s.len() == 36
+        && s.char_indices().all(|(idx, c)| match idx {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+#[cfg(test)]
+mod test_matches_naive_datetime {
+    macro_rules! test {
+        ($( ( $name:ident, $s:expr, $expected:expr ), )*) => {
+            $(
+                #[test]
+                fn $name() {
+                    if $expected {
+                        assert_eq!(super::parsing::matches_naive_datetime($s), Ok(""));
+                    }
+                    assert_eq!(super::matches_naive_datetime($s), $expected);
+                }
+            )*
+        };
+    }
+
+    test!(
+        (example_chrono_docs_1, "2015-09-18T23:56:04", true),
+        (example_chrono_docs_2, "+12345-6-7T7:59:60.5", true),
+        (surrounding_space, "   2015-09-18T23:56:04   ", true),
+        (space_separator, "2015-09-18 23:56:04", true),
+        (fractional_seconds, "2015-09-18 23:56:04.123456", true),
+        (date_only, "2015-09-18", false),
+        (with_offset, "2015-09-18T23:56:04+02:00", false),
+    );
+}
+
+#[cfg(test)]
+mod test_matches_utc_datetime {
+    macro_rules! test {
+        ($( ( $name:ident, $s:expr, $expected:expr ), )*) => {
+            $(
+                #[test]
+                fn $name() {
+                    if $expected {
+                        assert_eq!(super::parsing::matches_utc_datetime($s), Ok(""));
+                    }
+                    assert_eq!(super::matches_utc_datetime($s), $expected);
+                }
+            )*
+        };
+    }
+
+    test!(
+        (example_chrono_docs_1, "2012-12-12T12:12:12Z", true),
+        (example_chrono_docs_2, "2012-12-12 12:12:12Z", true),
+        (example_chrono_docs_3, "2012-12-12 12:12:12+0000", true),
+        (example_chrono_docs_4, "2012-12-12 12:12:12+00:00", true),
+    );
+}
+
+#[cfg(test)]
+mod test_matches_numbers {
+    macro_rules! test {
+        ($( ( $name:ident, $s:expr, $integer:expr, $float:expr ), )*) => {
+            $(
+                #[test]
+                fn $name() {
+                    assert_eq!(super::matches_integer($s), $integer);
+                    assert_eq!(super::matches_float($s), $float);
+                }
+            )*
+        };
+    }
+
+    test!(
+        (integer, "42", true, true),
+        (negative_integer, "-13", true, true),
+        (signed_integer, "+7", true, true),
+        (too_large_integer, "18446744073709551615", false, true),
+        (float, "3.5", false, true),
+        (float_with_exponent, "1e3", false, true),
+        (infinity, "inf", false, false),
+        (nan, "NaN", false, false),
+        (surrounding_space, " 42 ", false, false),
+        (empty, "", false, false),
+        (text, "foo", false, false),
+    );
+}
+
+#[cfg(test)]
+mod test_matches_offset_datetime {
+    macro_rules! test {
+        ($( ( $name:ident, $s:expr, $expected:expr ), )*) => {
+            $(
+                #[test]
+                fn $name() {
+                    assert_eq!(super::matches_offset_datetime($s), $expected);
+                }
+            )*
+        };
+    }
+
+    test!(
+        (utc, "2012-12-12T12:12:12Z", true),
+        (positive_offset, "2012-12-12T12:12:12+02:00", true),
+        (negative_offset, "2012-12-12 12:12:12.5-03:30", true),
+        (offset_without_colon, "2012-12-12T12:12:12+0200", true),
+        (naive, "2012-12-12T12:12:12", false),
+        (incomplete_offset, "2012-12-12T12:12:12+2", false),
+    );
+}
+
+#[cfg(test)]
+mod test_matches_dates_and_times {
+    macro_rules! test {
+        ($( ( $name:ident, $s:expr, $date:expr, $time:expr ), )*) => {
+            $(
+                #[test]
+                fn $name() {
+                    assert_eq!(super::matches_date($s), $date);
+                    assert_eq!(super::matches_time($s), $time);
+                }
+            )*
+        };
+    }
+
+    test!(
+        (date, "2024-01-31", true, false),
+        (short_date, "2024-1-5", true, false),
+        (datetime, "2024-01-31T12:00:00", false, false),
+        (time, "12:30:00", false, true),
+        (time_without_seconds, "12:30", false, true),
+        (time_with_fraction, "12:30:00.123", false, true),
+        (version, "1.2.3", false, false),
+        (number, "42", false, false),
+    );
+}
+
+#[cfg(test)]
+mod test_matches_decimal {
+    macro_rules! test {
+        ($( ( $name:ident, $s:expr, $expected:expr ), )*) => {
+            $(
+                #[test]
+                fn $name() {
+                    assert_eq!(super::matches_decimal($s), $expected);
+                }
+            )*
+        };
+    }
+
+    test!(
+        (decimal, "12.50", Some((4, 2))),
+        (negative, "-0.125", Some((3, 3))),
+        (integer, "42", Some((2, 0))),
+        (zero, "0", Some((1, 0))),
+        (leading_zeros, "007.5", Some((2, 1))),
+        (exponent, "1e3", None),
+        (trailing_dot, "1.", None),
+        (leading_dot, ".5", None),
+        (
+            too_many_digits,
+            "1234567890123456789012345678901234567.89",
+            None
+        ),
+        (text, "foo", None),
+    );
+}
+
+#[cfg(test)]
+mod test_matches_uuid {
+    #[test]
+    fn uuids() {
+        assert!(super::matches_uuid("67e55044-10b1-426f-9247-bb680e5fe0c8"));
+        assert!(super::matches_uuid("67E55044-10B1-426F-9247-BB680E5FE0C8"));
+        assert!(!super::matches_uuid("67e5504410b1426f9247bb680e5fe0c8"));
+        assert!(!super::matches_uuid("67e55044-10b1-426f-9247-bb680e5fe0cg"));
+    }
+}
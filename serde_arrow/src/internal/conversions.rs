@@ -1,6 +1,183 @@
 use half::f16;
 
-use crate::Error;
+use crate::{internal::error::fail, Error, Result};
+
+/// How to handle numeric values that cannot be represented exactly in the
+/// data type of the array
+///
+/// The policy applies to all numeric conversions during serialization: float
+/// narrowing (`f64` to `f32` or `F16`), integers to floats, floats to
+/// integers or decimals, and integers to integers of a different width. NaN
+/// and infinite values are passed through unchanged for float arrays.
+///
+/// Usage:
+///
+/// ```rust
+/// # #[cfg(has_arrow)]
+/// # fn main() -> serde_arrow::Result<()> {
+/// # use serde_arrow::_impl::arrow::datatypes::{DataType, Field};
+/// use serde_arrow::{utils::{Item, NumericConversion}, ArrowBuilder};
+///
+/// let fields = vec![Field::new("item", DataType::Float32, false)];
+/// let mut builder = ArrowBuilder::new(&fields)?;
+/// builder.push(&Item(0.1_f64))?;
+///
+/// let mut builder = ArrowBuilder::new(&fields)?
+///     .numeric_conversion(NumericConversion::Strict);
+/// builder.push(&Item(0.5_f64))?;
+/// assert!(builder.push(&Item(0.1_f64)).is_err());
+/// # Ok(())
+/// # }
+/// # #[cfg(not(has_arrow))]
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NumericConversion {
+    /// Only allow conversions that preserve the value exactly
+    ///
+    /// Floats with a fractional part cannot be converted to integers, integers
+    /// that cannot be represented exactly as floats result in an error, and
+    /// floats are only narrowed or stored as decimals if no precision is lost.
+    Strict,
+    /// Allow to lose precision, but fail for values out of the range of the
+    /// target type (the default)
+    ///
+    /// Floats are rounded to the nearest representable value, also when
+    /// converted to decimals, and truncated towards zero when converted to
+    /// integers.
+    #[default]
+    Lossy,
+    /// Allow to lose precision and clamp values out of range to the minimum
+    /// or maximum of the target type
+    ///
+    /// This option follows the semantics of Rust's `as` casts, except that
+    /// floats are clamped to the largest finite value instead of infinity.
+    /// NaN is converted to `0` for integers.
+    Saturating,
+}
+
+/// Convert numeric values according to a [`NumericConversion`] policy
+///
+/// Integer sources are passed as `i128` and float sources as `f64`, as both
+/// represent all source values exactly.
+pub trait ConvertNumber: Sized {
+    const NAME: &'static str;
+
+    fn from_integer(value: i128, policy: NumericConversion) -> Result<Self>;
+
+    fn from_float(value: f64, policy: NumericConversion) -> Result<Self>;
+}
+
+fn out_of_range<T>(value: impl std::fmt::Display, target: &str) -> Result<T> {
+    fail!("Cannot convert {value} to {target}: value out of range")
+}
+
+macro_rules! impl_convert_number_for_int {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl ConvertNumber for $ty {
+                const NAME: &'static str = stringify!($ty);
+
+                fn from_integer(value: i128, policy: NumericConversion) -> Result<Self> {
+                    match $ty::try_from(value) {
+                        Ok(value) => Ok(value),
+                        Err(_) if policy == NumericConversion::Saturating => {
+                            Ok(if value < 0 { $ty::MIN } else { $ty::MAX })
+                        }
+                        Err(_) => out_of_range(value, Self::NAME),
+                    }
+                }
+
+                fn from_float(value: f64, policy: NumericConversion) -> Result<Self> {
+                    if policy == NumericConversion::Saturating {
+                        return Ok(value as $ty);
+                    }
+                    if policy == NumericConversion::Strict && value.fract() != 0.0 {
+                        fail!(
+                            "Cannot convert {value} to {}: value has a fractional part",
+                            Self::NAME,
+                        );
+                    }
+
+                    let truncated = value.trunc();
+                    if !(truncated >= $ty::MIN as f64 && truncated < $ty::MAX as f64 + 1.0) {
+                        return out_of_range(value, Self::NAME);
+                    }
+                    Ok(truncated as $ty)
+                }
+            }
+        )*
+    };
+}
+
+impl_convert_number_for_int!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+/// Shared logic for float targets: `convert` performs the rounding conversion,
+/// `back` converts the result back to `f64` to check for exactness
+fn convert_to_float<T: Copy>(
+    value: f64,
+    policy: NumericConversion,
+    name: &str,
+    convert: impl Fn(f64) -> T,
+    back: impl Fn(T) -> f64,
+    max: T,
+    min: T,
+) -> Result<T> {
+    let result = convert(value);
+    let result_f64 = back(result);
+
+    if value.is_finite() && result_f64.is_infinite() {
+        match policy {
+            NumericConversion::Saturating if value > 0.0 => return Ok(max),
+            NumericConversion::Saturating => return Ok(min),
+            _ => return out_of_range(value, name),
+        }
+    }
+    if policy == NumericConversion::Strict && !value.is_nan() && result_f64 != value {
+        fail!("Cannot convert {value} to {name} without loss of precision");
+    }
+    Ok(result)
+}
+
+macro_rules! impl_convert_number_for_float {
+    ($ty:ident, $name:expr, $convert:expr, $back:expr) => {
+        impl ConvertNumber for $ty {
+            const NAME: &'static str = $name;
+
+            fn from_integer(value: i128, policy: NumericConversion) -> Result<Self> {
+                let float_policy = match policy {
+                    NumericConversion::Strict => NumericConversion::Lossy,
+                    policy => policy,
+                };
+                let result = Self::from_float(value as f64, float_policy)?;
+                if policy == NumericConversion::Strict && ($back)(result) as i128 != value {
+                    fail!(
+                        "Cannot convert {value} to {} without loss of precision",
+                        Self::NAME
+                    );
+                }
+                Ok(result)
+            }
+
+            fn from_float(value: f64, policy: NumericConversion) -> Result<Self> {
+                convert_to_float(
+                    value,
+                    policy,
+                    Self::NAME,
+                    $convert,
+                    $back,
+                    $ty::MAX,
+                    $ty::MIN,
+                )
+            }
+        }
+    };
+}
+
+impl_convert_number_for_float!(f16, "f16", f16::from_f64, |v: f16| v.to_f64());
+impl_convert_number_for_float!(f32, "f32", |v: f64| v as f32, |v: f32| v as f64);
+impl_convert_number_for_float!(f64, "f64", |v: f64| v, |v: f64| v);
 
 pub trait ToBytes: Sized {
     type Bytes;
@@ -409,6 +409,48 @@ pub fn format_decimal(buffer: &mut [u8], val: i128, scale: i8) -> &str {
     std::str::from_utf8(res).unwrap()
 }
 
+/// Convert a decimal into the closest float
+pub fn decimal_to_f64(val: i128, scale: i8) -> Result<f64> {
+    // parse the formatted value to ensure correct rounding
+    let mut buffer = [0; BUFFER_SIZE_I128];
+    Ok(format_decimal(&mut buffer, val, scale).parse()?)
+}
+
+/// Convert a decimal into an integer, fail if it has a fractional part
+pub fn decimal_to_i128(val: i128, scale: i8) -> Result<i128> {
+    let Some(factor) = 10_i128.checked_pow(scale.unsigned_abs() as u32) else {
+        fail!("Cannot convert decimal with scale {scale} to an integer");
+    };
+
+    if scale <= 0 {
+        let Some(res) = val.checked_mul(factor) else {
+            fail!("Decimal {val}e{} is out of range for integers", -scale);
+        };
+        Ok(res)
+    } else if val % factor == 0 {
+        Ok(val / factor)
+    } else {
+        let mut buffer = [0; BUFFER_SIZE_I128];
+        fail!(
+            "Cannot convert decimal {} to an integer without loss",
+            format_decimal(&mut buffer, val, scale)
+        );
+    }
+}
+
+#[test]
+fn test_decimal_conversions() {
+    assert_eq!(decimal_to_f64(123, 2).unwrap(), 1.23);
+    assert_eq!(decimal_to_f64(-5, 1).unwrap(), -0.5);
+    assert_eq!(decimal_to_f64(12, -2).unwrap(), 1200.0);
+
+    assert_eq!(decimal_to_i128(1200, 2).unwrap(), 12);
+    assert_eq!(decimal_to_i128(-1200, 2).unwrap(), -12);
+    assert_eq!(decimal_to_i128(12, -2).unwrap(), 1200);
+    assert!(decimal_to_i128(1201, 2).is_err());
+    assert!(decimal_to_i128(i128::MAX, -1).is_err());
+}
+
 #[test]
 fn test_format_decimal() {
     fn format_decimal_str(val: i128, scale: i8) -> String {
@@ -1,11 +1,14 @@
-use std::collections::HashMap;
+pub mod row_iterator;
+
+use std::{collections::HashMap, ops::Range};
 
 use crate::{
     internal::{
         error::{error, fail, Result},
         event::Event,
-        schema::GenericDataType,
+        schema::{GenericDataType, GenericTimeUnit},
         source::EventSource,
+        timestamp::{self, Timestamp},
     },
     schema::Strategy,
 };
@@ -24,50 +27,231 @@ const NEXT_INSTR: usize = usize::MAX - 1;
 #[derive(Debug, Default, Clone)]
 pub struct CompilationOptions {}
 
+/// Compile the program to deserialize the given arrays
+///
+/// `field_names` contains the u8 buffers with the names of the top-level
+/// fields, one per array.
 pub fn compile_deserialization<'a>(
     num_items: usize,
-    arrays: &'a [ArrayMapping],
+    arrays: &[ArrayMapping],
+    field_names: &[usize],
     buffers: Buffers<'a>,
 ) -> Result<Interpreter<'a>> {
-    let mut compiler = Compiler {
-        num_items,
-        arrays,
-        buffers,
-        num_positions: 0,
-        program: Vec::new(),
-    };
-    compiler.compile()?;
+    if arrays.len() != field_names.len() {
+        fail!(
+            "inconsistent number of arrays ({}) and field names ({})",
+            arrays.len(),
+            field_names.len()
+        );
+    }
+
+    let mut compiler = Compiler::new(num_items, buffers);
+    compiler.compile(arrays, field_names)?;
+    compiler.finish()
+}
+
+/// Compile the program to deserialize the given arrays as columns
+///
+/// The program emits a single struct that maps the name of each top-level
+/// field to the sequence of its values.
+pub fn compile_column_deserialization<'a>(
+    num_items: usize,
+    arrays: &[ArrayMapping],
+    field_names: &[usize],
+    buffers: Buffers<'a>,
+) -> Result<Interpreter<'a>> {
+    if arrays.len() != field_names.len() {
+        fail!(
+            "inconsistent number of arrays ({}) and field names ({})",
+            arrays.len(),
+            field_names.len()
+        );
+    }
+
+    let mut compiler = Compiler::new(num_items, buffers);
+    compiler.compile_columns(arrays, field_names)?;
+    compiler.fix_redirects()?;
+    compiler.finish()
+}
 
-    let current_config = CONFIGURATION.read().unwrap().clone();
-    if current_config.debug_print_program {
-        println!("Program: {program:?}", program = compiler.program);
+impl<'a> Compiler<'a> {
+    fn new(num_items: usize, buffers: Buffers<'a>) -> Self {
+        Compiler {
+            num_items,
+            buffers,
+            positions: Vec::new(),
+            program: Vec::new(),
+            row_start: UNSET_INSTR,
+            row_end: UNSET_INSTR,
+            outer_sequence_count: UNSET_INSTR,
+        }
     }
 
-    Ok(compiler.into_program())
+    fn finish(self) -> Result<Interpreter<'a>> {
+        let current_config = CONFIGURATION.read().unwrap().clone();
+        if current_config.debug_print_program {
+            println!("Program: {program:?}", program = self.program);
+        }
+
+        Ok(self.into_program())
+    }
 }
 
 struct Compiler<'a> {
     num_items: usize,
-    arrays: &'a [ArrayMapping],
     buffers: Buffers<'a>,
-    num_positions: usize,
+    /// How to derive each position counter from the current row
+    positions: Vec<PositionSource>,
     program: Vec<Bytecode>,
+    row_start: usize,
+    row_end: usize,
+    outer_sequence_count: usize,
+}
+
+/// How the value of a position counter at the start of a row is determined
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PositionSource {
+    /// The counter of the outer sequence, i.e., the row itself
+    Outer,
+    /// The counter is aligned with the given parent counter
+    SameAs(usize),
+    /// The counter is given by the offset at the parent counter
+    Offsets {
+        parent: usize,
+        offsets: usize,
+        is_large: bool,
+    },
+    /// The counter is set by the program, before it is used
+    Runtime,
+}
+
+impl PositionSource {
+    /// Derive the value of the counter, `None` if it cannot be derived from
+    /// other counters
+    fn derive(&self, positions: &[usize], buffers: &Buffers<'_>) -> Result<Option<usize>> {
+        match *self {
+            Self::Outer | Self::Runtime => Ok(None),
+            Self::SameAs(parent) => Ok(Some(positions[parent])),
+            Self::Offsets {
+                parent,
+                offsets,
+                is_large,
+            } => {
+                let pos = positions[parent];
+                let offset = if is_large {
+                    buffers
+                        .get_i64(offsets)
+                        .get(pos)
+                        .copied()
+                        .ok_or_else(|| error!("Cannot get offset"))?
+                        .try_into()?
+                } else {
+                    buffers
+                        .get_i32(offsets)
+                        .get(pos)
+                        .copied()
+                        .ok_or_else(|| error!("Cannot get offset"))?
+                        .try_into()?
+                };
+                Ok(Some(offset))
+            }
+        }
+    }
+}
+
+/// The position counters of a child array that start at a given offset
+///
+/// For sliced arrays, null list entries with non-empty segments, or dense
+/// unions, the child counters may not be at the start of the segment. In
+/// that case, they are moved to the start before the segment is emitted.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct SegmentPositions {
+    /// The counters that are set to the start of the segment
+    roots: Vec<usize>,
+    /// The counters derived from the roots, in order of derivation
+    derived: Vec<(usize, PositionSource)>,
+}
+
+impl SegmentPositions {
+    fn sync(&self, positions: &mut [usize], buffers: &Buffers<'_>, start: usize) -> Result<()> {
+        if self.roots.iter().all(|&idx| positions[idx] == start) {
+            return Ok(());
+        }
+        for &idx in &self.roots {
+            positions[idx] = start;
+        }
+        for (idx, source) in &self.derived {
+            if let Some(value) = source.derive(positions, buffers)? {
+                positions[*idx] = value;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<'a> Compiler<'a> {
-    fn compile(&mut self) -> Result<()> {
-        self.compile_fields()?;
+    fn compile(&mut self, arrays: &[ArrayMapping], field_names: &[usize]) -> Result<()> {
+        self.compile_fields(arrays, field_names)?;
         self.fix_redirects()?;
         Ok(())
     }
 
-    fn compile_fields(&mut self) -> Result<()> {
+    /// Compile a struct with one sequence per field, each with its own loop
+    /// over the rows
+    fn compile_columns(&mut self, arrays: &[ArrayMapping], field_names: &[usize]) -> Result<()> {
+        self.outer_sequence_count = self.buffers.push_u0(self.num_items);
+        self.row_start = 0;
+        self.push_instr(EmitStartOuterStruct { next: NEXT_INSTR });
+
+        for (array, &name_buffer) in arrays.iter().zip(field_names) {
+            self.push_instr(EmitConstantString {
+                next: NEXT_INSTR,
+                buffer: name_buffer,
+            });
+            self.push_instr(EmitOuterStartSequence { next: NEXT_INSTR });
+
+            let position = self.new_position(PositionSource::Outer);
+            let item_pos = self.push_instr(EmitOuterItem {
+                next: NEXT_INSTR,
+                if_end: UNSET_INSTR,
+                position,
+                count: self.outer_sequence_count,
+            });
+
+            let content_pos = self.program.len();
+            self.compile_field(array, PositionSource::SameAs(position), &mut Vec::new())?;
+            self.push_instr(EmitOuterEndSequence {
+                next: NEXT_INSTR,
+                position,
+                if_item: content_pos,
+                count: self.outer_sequence_count,
+            });
+
+            let after_column = self.program.len();
+            if let Bytecode::EmitOuterItem(item) = &mut self.program[item_pos] {
+                item.if_end = after_column;
+            } else {
+                fail!("invalid state in compilation")
+            }
+        }
+
+        self.row_end = self.push_instr(EmitEndOuterStruct { next: NEXT_INSTR });
+
+        let end_of_program = self.program.len();
+        self.push_instr(EndOfProgram {
+            next: end_of_program,
+        });
+        Ok(())
+    }
+
+    fn compile_fields(&mut self, arrays: &[ArrayMapping], field_names: &[usize]) -> Result<()> {
         self.push_instr(EmitOuterStartSequence { next: 1 });
 
         let outer_sequence_count = self.buffers.push_u0(self.num_items);
+        self.outer_sequence_count = outer_sequence_count;
         let outer_sequence_item_pos = self.program.len();
 
-        let outer_sequence_position = self.new_position();
+        let outer_sequence_position = self.new_position(PositionSource::Outer);
         self.push_instr(EmitOuterItem {
             next: NEXT_INSTR,
             if_end: UNSET_INSTR,
@@ -76,23 +260,26 @@ impl<'a> Compiler<'a> {
         });
 
         let outer_sequence_content_pos = self.program.len();
+        self.row_start = outer_sequence_content_pos;
         self.push_instr(EmitStartOuterStruct { next: NEXT_INSTR });
 
         let mut child_positions = Vec::new();
-        for array in self.arrays {
-            let field = array.get_field();
-            let name_buffer = self.buffers.push_u8(field.name.as_bytes());
+        for (array, &name_buffer) in arrays.iter().zip(field_names) {
             self.push_instr(EmitConstantString {
                 next: NEXT_INSTR,
                 buffer: name_buffer,
             });
 
-            self.compile_field(array, &mut child_positions)?;
+            self.compile_field(
+                array,
+                PositionSource::SameAs(outer_sequence_position),
+                &mut child_positions,
+            )?;
         }
         // The top-level struct cannot be null
         drop(child_positions);
 
-        self.push_instr(EmitEndOuterStruct { next: NEXT_INSTR });
+        self.row_end = self.push_instr(EmitEndOuterStruct { next: NEXT_INSTR });
 
         self.push_instr(EmitOuterEndSequence {
             next: NEXT_INSTR,
@@ -119,10 +306,11 @@ impl<'a> Compiler<'a> {
     ///
     fn compile_field(
         &mut self,
-        array: &'a ArrayMapping,
+        array: &ArrayMapping,
+        source: PositionSource,
         child_positions: &mut Vec<usize>,
     ) -> Result<()> {
-        let position = self.new_position();
+        let position = self.new_position(source);
 
         let option_instr;
         if let Some(validity) = array.get_validity() {
@@ -158,7 +346,7 @@ impl<'a> Compiler<'a> {
 
     fn compile_field_inner(
         &mut self,
-        array: &'a ArrayMapping,
+        array: &ArrayMapping,
         position: usize,
         child_positions: &mut Vec<usize>,
     ) -> Result<()> {
@@ -231,29 +419,68 @@ impl<'a> Compiler<'a> {
                     GenericDataType::Decimal128(_, scale) => *scale,
                     _ => fail!("inconsistent state for Decimal128 in compile_deserialzation"),
                 };
-                self.push_instr(EmitDecimal128 {
-                    next: NEXT_INSTR,
-                    buffer: *buffer,
-                    position,
-                    scale,
-                })
+                match field.strategy.as_ref() {
+                    None => self.push_instr(EmitDecimal128 {
+                        next: NEXT_INSTR,
+                        buffer: *buffer,
+                        position,
+                        scale,
+                    }),
+                    Some(Strategy::Decimal128AsStr) => self.push_instr(EmitDecimal128Str {
+                        next: NEXT_INSTR,
+                        buffer: *buffer,
+                        position,
+                        scale,
+                    }),
+                    Some(Strategy::Decimal128AsF64) => self.push_instr(EmitDecimal128F64 {
+                        next: NEXT_INSTR,
+                        buffer: *buffer,
+                        position,
+                        scale,
+                    }),
+                    Some(strategy) => {
+                        fail!("compilation of decimals with strategy {strategy} is not supported")
+                    }
+                }
             }
-            &M::Utf8 {
-                buffer, offsets, ..
-            } => self.push_instr(EmitStr32 {
-                next: NEXT_INSTR,
+            M::Utf8 {
+                field,
                 buffer,
                 offsets,
-                position,
-            }),
-            &M::LargeUtf8 {
-                buffer, offsets, ..
-            } => self.push_instr(EmitStr64 {
-                next: NEXT_INSTR,
+                ..
+            } => match field.strategy.as_ref() {
+                Some(Strategy::JsonAsUtf8) => self.push_instr(EmitJson32 {
+                    next: NEXT_INSTR,
+                    buffer: *buffer,
+                    offsets: *offsets,
+                    position,
+                }),
+                _ => self.push_instr(EmitStr32 {
+                    next: NEXT_INSTR,
+                    buffer: *buffer,
+                    offsets: *offsets,
+                    position,
+                }),
+            },
+            M::LargeUtf8 {
+                field,
                 buffer,
                 offsets,
-                position,
-            }),
+                ..
+            } => match field.strategy.as_ref() {
+                Some(Strategy::JsonAsUtf8) => self.push_instr(EmitJson64 {
+                    next: NEXT_INSTR,
+                    buffer: *buffer,
+                    offsets: *offsets,
+                    position,
+                }),
+                _ => self.push_instr(EmitStr64 {
+                    next: NEXT_INSTR,
+                    buffer: *buffer,
+                    offsets: *offsets,
+                    position,
+                }),
+            },
             &M::Dictionary {
                 dictionary,
                 indices,
@@ -264,41 +491,65 @@ impl<'a> Compiler<'a> {
                 value: dictionary,
                 index: indices,
             }),
-            M::Date64 { field, buffer, .. } => match field.strategy.as_ref() {
-                Some(Strategy::NaiveStrAsDate64) => self.push_instr(EmitDate64NaiveStr {
-                    next: NEXT_INSTR,
-                    buffer: *buffer,
-                    position,
-                }),
-                Some(Strategy::UtcStrAsDate64) => self.push_instr(EmitDate64UtcStr {
+            &M::Date32 { buffer, .. } => self.push_instr(EmitDate32 {
+                next: NEXT_INSTR,
+                buffer,
+                position,
+            }),
+            M::Time64 { field, buffer, .. } => {
+                let GenericDataType::Time64(unit) = &field.data_type else {
+                    fail!("inconsistent state for Time64 in compile_deserialzation");
+                };
+                self.push_instr(EmitTime64 {
                     next: NEXT_INSTR,
                     buffer: *buffer,
                     position,
-                }),
-                None => self.push_instr(EmitI64 {
+                    unit: *unit,
+                })
+            }
+            M::Date64 { field, buffer, .. } => {
+                let (unit, timezone) = match &field.data_type {
+                    GenericDataType::Date64 => (GenericTimeUnit::Millisecond, false),
+                    GenericDataType::Timestamp(unit, tz) => (*unit, tz.is_some()),
+                    _ => fail!("inconsistent state for Date64 in compile_deserialzation"),
+                };
+                let (utc, prefer_str) = match field.strategy.as_ref() {
+                    Some(Strategy::NaiveStrAsDate64) => (false, true),
+                    Some(Strategy::UtcStrAsDate64) => (true, true),
+                    None => (timezone, false),
+                    Some(strategy) => {
+                        fail!("compilation of date64 with strategy {strategy} is not yet supported")
+                    }
+                };
+                self.push_instr(EmitTimestamp {
                     next: NEXT_INSTR,
                     buffer: *buffer,
                     position,
-                }),
-                Some(strategy) => {
-                    fail!("compilation of date64 with strategy {strategy} is not yet supported")
-                }
-            },
+                    unit,
+                    utc,
+                    prefer_str,
+                })
+            }
             M::List { item, offsets, .. } => self
                 .compile_list(item, position, *offsets, false)
                 .map(|_| 0)?,
             M::LargeList { item, offsets, .. } => self
                 .compile_list(item, position, *offsets, true)
                 .map(|_| 0)?,
-            M::Struct { field, fields, .. } => match field.strategy.as_ref() {
+            M::Struct {
+                field,
+                fields,
+                names,
+                ..
+            } => match field.strategy.as_ref() {
                 None => self
-                    .compile_struct(fields, position, child_positions)
+                    .compile_struct(fields, names, position, child_positions)
                     .map(|_| 0)?,
                 Some(Strategy::TupleAsStruct) => self
                     .compile_tuple_struct(fields, position, child_positions)
                     .map(|_| 0)?,
                 Some(Strategy::MapAsStruct) => self
-                    .compile_map_struct(fields, position, child_positions)
+                    .compile_map_struct(fields, names, position, child_positions)
                     .map(|_| 0)?,
                 Some(strategy) => {
                     fail!("compilation of structs with strategy {strategy} is not yet supported")
@@ -324,8 +575,17 @@ impl<'a> Compiler<'a> {
                 self.compile_map(key_field, values_field, position, *offsets)
                     .map(|_| 0)?
             }
-            M::Union { fields, types, .. } => {
-                self.compile_union(fields, position, *types).map(|_| 0)?
+            M::Union {
+                field,
+                fields,
+                types,
+                offsets,
+                names,
+                ..
+            } => {
+                let untagged = matches!(field.strategy, Some(Strategy::InconsistentTypes));
+                self.compile_union(fields, names, untagged, position, *types, *offsets)
+                    .map(|_| 0)?
             }
         };
         Ok(())
@@ -336,12 +596,12 @@ impl<'a> Compiler<'a> {
 impl<'a> Compiler<'a> {
     fn compile_list(
         &mut self,
-        item: &'a ArrayMapping,
+        item: &ArrayMapping,
         position: usize,
         offsets: usize,
         is_large: bool,
     ) -> Result<()> {
-        let inner_position = self.new_position();
+        let inner_position = self.new_position(PositionSource::Runtime);
         let emit_start_instr = self.push_instr(EmitStartSequence {
             next: NEXT_INSTR,
             if_end: UNSET_INSTR,
@@ -349,6 +609,7 @@ impl<'a> Compiler<'a> {
             inner_position,
             offsets,
             is_large,
+            item_positions: SegmentPositions::default(),
         });
 
         let if_item_instr = self.program.len() + 1;
@@ -362,8 +623,15 @@ impl<'a> Compiler<'a> {
         });
 
         let mut child_positions = Vec::new();
-        self.compile_field(item, &mut child_positions)?;
+        let item_source = PositionSource::Offsets {
+            parent: position,
+            offsets,
+            is_large,
+        };
+        let item_position = self.positions.len();
+        self.compile_field(item, item_source, &mut child_positions)?;
         drop(child_positions);
+        let item_positions = self.segment_positions(vec![item_position]);
 
         let if_end_instr = self.program.len() + 1;
         self.push_instr(EmitEndSequence {
@@ -382,6 +650,7 @@ impl<'a> Compiler<'a> {
         }
         if let Some(Bytecode::EmitStartSequence(instr)) = self.program.get_mut(emit_start_instr) {
             instr.if_end = if_end_instr;
+            instr.item_positions = item_positions;
         } else {
             fail!("invalid state during compilation");
         }
@@ -394,21 +663,20 @@ impl<'a> Compiler<'a> {
 impl<'a> Compiler<'a> {
     fn compile_struct(
         &mut self,
-        arrays: &'a [ArrayMapping],
+        arrays: &[ArrayMapping],
+        names: &[usize],
         position: usize,
         child_positions: &mut Vec<usize>,
     ) -> Result<()> {
         self.push_instr(EmitStartStruct { next: NEXT_INSTR });
 
-        for array in arrays {
-            let field = array.get_field();
-            let name_buffer = self.buffers.push_u8(field.name.as_bytes());
+        for (array, &name_buffer) in arrays.iter().zip(names) {
             self.push_instr(EmitConstantString {
                 next: NEXT_INSTR,
                 buffer: name_buffer,
             });
 
-            self.compile_field(array, child_positions)?;
+            self.compile_field(array, PositionSource::SameAs(position), child_positions)?;
         }
 
         self.push_instr(EmitEndStruct {
@@ -420,7 +688,7 @@ impl<'a> Compiler<'a> {
 
     fn compile_tuple_struct(
         &mut self,
-        arrays: &'a [ArrayMapping],
+        arrays: &[ArrayMapping],
         position: usize,
         child_positions: &mut Vec<usize>,
     ) -> Result<()> {
@@ -428,7 +696,7 @@ impl<'a> Compiler<'a> {
 
         for array in arrays {
             self.push_instr(EmitItemTuple { next: NEXT_INSTR });
-            self.compile_field(array, child_positions)?;
+            self.compile_field(array, PositionSource::SameAs(position), child_positions)?;
         }
 
         self.push_instr(EmitEndTuple {
@@ -440,20 +708,19 @@ impl<'a> Compiler<'a> {
 
     fn compile_map_struct(
         &mut self,
-        arrays: &'a [ArrayMapping],
+        arrays: &[ArrayMapping],
+        names: &[usize],
         position: usize,
         child_positions: &mut Vec<usize>,
     ) -> Result<()> {
         self.push_instr(EmitStartMapStruct { next: NEXT_INSTR });
 
-        for array in arrays {
-            let field = array.get_field();
-            let name_buffer = self.buffers.push_u8(field.name.as_bytes());
+        for (array, &name_buffer) in arrays.iter().zip(names) {
             self.push_instr(EmitConstantString {
                 next: NEXT_INSTR,
                 buffer: name_buffer,
             });
-            self.compile_field(array, child_positions)?;
+            self.compile_field(array, PositionSource::SameAs(position), child_positions)?;
         }
 
         self.push_instr(EmitEndMapStruct {
@@ -468,12 +735,12 @@ impl<'a> Compiler<'a> {
 impl<'a> Compiler<'a> {
     fn compile_map(
         &mut self,
-        key_field: &'a ArrayMapping,
-        value_field: &'a ArrayMapping,
+        key_field: &ArrayMapping,
+        value_field: &ArrayMapping,
         position: usize,
         offsets: usize,
     ) -> Result<()> {
-        let inner_position = self.new_position();
+        let inner_position = self.new_position(PositionSource::Runtime);
 
         let emit_start_instr = self.push_instr(EmitStartMap {
             next: NEXT_INSTR,
@@ -481,6 +748,7 @@ impl<'a> Compiler<'a> {
             position,
             inner_position,
             offsets,
+            entry_positions: SegmentPositions::default(),
         });
 
         let if_item_instr = self.program.len() + 1;
@@ -494,14 +762,21 @@ impl<'a> Compiler<'a> {
 
         let mut dummy_positions = Vec::new();
 
-        let keys_position = self.new_position();
+        let entries_source = PositionSource::Offsets {
+            parent: position,
+            offsets,
+            is_large: false,
+        };
+
+        let keys_position = self.new_position(entries_source);
         self.compile_field_inner(key_field, keys_position, &mut dummy_positions)?;
 
-        let values_position = self.new_position();
+        let values_position = self.new_position(entries_source);
         self.compile_field_inner(value_field, values_position, &mut dummy_positions)?;
 
-        // null maps entries with non-empty segments are not supported
+        // the entry positions are moved to the start of each segment
         drop(dummy_positions);
+        let entry_positions = self.segment_positions(vec![keys_position, values_position]);
 
         let if_end_instr = self.program.len() + 1;
         self.push_instr(EmitEndMap {
@@ -519,6 +794,7 @@ impl<'a> Compiler<'a> {
         }
         if let Some(Bytecode::EmitStartMap(instr)) = self.program.get_mut(emit_start_instr) {
             instr.if_end = if_end_instr;
+            instr.entry_positions = entry_positions;
         } else {
             fail!("invalid state during compilation");
         }
@@ -531,33 +807,41 @@ impl<'a> Compiler<'a> {
 impl<'a> Compiler<'a> {
     fn compile_union(
         &mut self,
-        fields: &'a [ArrayMapping],
+        fields: &[ArrayMapping],
+        names: &[usize],
+        untagged: bool,
         position: usize,
         types: usize,
+        offsets: usize,
     ) -> Result<()> {
-        let mut field_names = Vec::new();
-        for field in fields {
-            let idx = self.buffers.push_u8(field.get_field().name.as_bytes());
-            field_names.push(idx);
+        if fields.len() != names.len() {
+            fail!("inconsistent number of union fields and names");
         }
+        let field_names = names.to_vec();
         let dispatch_instr = self.push_instr(UnionDispatch {
             next: UNSET_INSTR,
             field_instr: Vec::new(),
             position,
             types,
+            offsets,
             field_names,
+            field_positions: Vec::new(),
+            untagged,
         });
 
         let mut field_instr = Vec::new();
+        let mut field_positions = Vec::new();
         let mut redirect_instrs = Vec::new();
 
         for field in fields {
             field_instr.push(self.program.len());
 
             let mut dummy_positions = Vec::new();
-            self.compile_field(field, &mut dummy_positions)?;
+            let field_position = self.positions.len();
+            self.compile_field(field, PositionSource::Runtime, &mut dummy_positions)?;
             // unions in nullable structs are currently not supported
             drop(dummy_positions);
+            field_positions.push(self.segment_positions(vec![field_position]));
 
             let redirect_instr = self.push_instr(Redirect { next: UNSET_INSTR });
             redirect_instrs.push(redirect_instr);
@@ -567,6 +851,7 @@ impl<'a> Compiler<'a> {
             fail!("internal error: did not find union dispatch")
         };
         instr.field_instr = field_instr;
+        instr.field_positions = field_positions;
 
         let instr_after_union = self.program.len();
         for redirect_instr in redirect_instrs {
@@ -627,16 +912,32 @@ impl<'a> Compiler<'a> {
         instr_idx
     }
 
-    fn new_position(&mut self) -> usize {
-        self.num_positions += 1;
-        self.num_positions - 1
+    fn new_position(&mut self, source: PositionSource) -> usize {
+        self.positions.push(source);
+        self.positions.len() - 1
+    }
+
+    /// Collect the positions of a segment, given by the roots and all
+    /// positions created after the first root
+    fn segment_positions(&self, roots: Vec<usize>) -> SegmentPositions {
+        let first = roots.iter().copied().min().unwrap_or(self.positions.len());
+        let derived = (first..self.positions.len())
+            .filter(|idx| !roots.contains(idx))
+            .map(|idx| (idx, self.positions[idx]))
+            .collect();
+        SegmentPositions { roots, derived }
     }
 
     fn into_program(self) -> Interpreter<'a> {
         Interpreter {
             current_instr: 0,
             program: self.program,
-            positions: vec![0; self.num_positions],
+            positions: vec![0; self.positions.len()],
+            position_sources: self.positions,
+            row_start: self.row_start,
+            row_end: self.row_end,
+            num_items: self.num_items,
+            outer_sequence_count: self.outer_sequence_count,
             buffers: self.buffers,
         }
     }
@@ -674,6 +975,8 @@ define_bytecode!{
         offsets: usize,
         /// whether to use i64 offsets (`true`) or i32 offsets (`false )`
         is_large: bool,
+        /// the positions of the item array
+        item_positions: SegmentPositions,
     },
     /// Handle the end-of-sequence / item case
     EmitEndSequence {
@@ -709,6 +1012,8 @@ define_bytecode!{
         inner_position: usize,
         /// the buffer that contains the offsets
         offsets: usize,
+        /// the positions of the keys and values arrays
+        entry_positions: SegmentPositions,
     },
     /// Handle the end-of-sequence / item case
     EmitEndMap {
@@ -822,19 +1127,49 @@ define_bytecode!{
         buffer: usize,
         offsets: usize,
     },
+    EmitJson32 {
+        position: usize,
+        buffer: usize,
+        offsets: usize,
+    },
+    EmitJson64 {
+        position: usize,
+        buffer: usize,
+        offsets: usize,
+    },
     EmitDecimal128 {
         position: usize,
         buffer: usize,
         scale: i8,
     },
-    EmitDate64NaiveStr {
+    EmitDecimal128Str {
+        position: usize,
+        buffer: usize,
+        scale: i8,
+    },
+    EmitDecimal128F64 {
+        position: usize,
+        buffer: usize,
+        scale: i8,
+    },
+    EmitTimestamp {
         position: usize,
         buffer: usize,
+        unit: GenericTimeUnit,
+        utc: bool,
+        prefer_str: bool,
     },
-    EmitDate64UtcStr {
+    /// Emit a date (days since the epoch) as a string
+    EmitDate32 {
         position: usize,
         buffer: usize,
     },
+    /// Emit a time (units since midnight) as a string
+    EmitTime64 {
+        position: usize,
+        buffer: usize,
+        unit: GenericTimeUnit,
+    },
     EmitDictionaryStr {
         position: usize,
         value: DictionaryValue,
@@ -843,8 +1178,13 @@ define_bytecode!{
     UnionDispatch {
         position: usize,
         types: usize,
+        /// the buffer that contains the offsets into the child arrays
+        offsets: usize,
         field_instr: Vec<usize>,
         field_names: Vec<usize>,
+        field_positions: Vec<SegmentPositions>,
+        /// if true, the values are emitted without a variant event
+        untagged: bool,
     },
 }
 
@@ -1112,6 +1452,7 @@ impl Instruction for EmitStartSequence {
         };
 
         positions[self.inner_position] = start;
+        self.item_positions.sync(positions, buffers, start)?;
 
         Ok((self.next, Some(Event::StartSequence)))
     }
@@ -1212,13 +1553,16 @@ impl Instruction for EmitStartMap {
     ) -> Result<(usize, Option<Event<'a>>)> {
         let outer_pos = positions[self.position];
 
-        positions[self.inner_position] = buffers
+        let start: usize = buffers
             .get_i32(self.offsets)
             .get(outer_pos)
             .copied()
             .ok_or_else(|| error!("attempting to to get non existing list"))?
             .try_into()?;
 
+        positions[self.inner_position] = start;
+        self.entry_positions.sync(positions, buffers, start)?;
+
         Ok((self.next, Some(Event::StartMap)))
     }
 
@@ -1345,9 +1689,16 @@ impl Instruction for UnionDispatch {
         let name = buffers.get_u8(self.field_names[ty]);
         let name = std::str::from_utf8(name)?;
 
+        let offset: usize = buffers.get_i32(self.offsets)[pos].try_into()?;
+        self.field_positions[ty].sync(positions, buffers, offset)?;
+
         let next = self.field_instr[ty];
 
-        Ok((next, Some(Event::Variant(name, ty))))
+        if self.untagged {
+            Ok((next, None))
+        } else {
+            Ok((next, Some(Event::Variant(name, ty))))
+        }
     }
 
     fn update_targets(&mut self, redirects: &HashMap<usize, usize>) -> Result<()> {
@@ -1641,16 +1992,72 @@ impl Instruction for EmitStr64 {
     }
 }
 
-impl Instruction for EmitDecimal128 {
+impl Instruction for EmitJson32 {
     fn emit<'a>(
         &self,
         positions: &mut [usize],
         buffers: &Buffers<'a>,
     ) -> Result<(usize, Option<Event<'a>>)> {
-        let val =
-            i128::from_ne_bytes(buffers.u128[self.buffer][positions[self.position]].to_ne_bytes());
+        let pos = positions[self.position];
         positions[self.position] += 1;
 
+        let start = usize::try_from(buffers.get_i32(self.offsets)[pos])?;
+        let end = usize::try_from(buffers.get_i32(self.offsets)[pos + 1])?;
+        let s = std::str::from_utf8(&buffers.u8[self.buffer][start..end])?;
+        Ok((self.next, Some(Event::Json(s))))
+    }
+
+    fn update_targets(&mut self, redirects: &HashMap<usize, usize>) -> Result<()> {
+        self.next = get_target_update(redirects, self.next);
+        Ok(())
+    }
+}
+
+impl Instruction for EmitJson64 {
+    fn emit<'a>(
+        &self,
+        positions: &mut [usize],
+        buffers: &Buffers<'a>,
+    ) -> Result<(usize, Option<Event<'a>>)> {
+        let pos = positions[self.position];
+        positions[self.position] += 1;
+
+        let start = usize::try_from(buffers.get_i64(self.offsets)[pos])?;
+        let end = usize::try_from(buffers.get_i64(self.offsets)[pos + 1])?;
+        let s = std::str::from_utf8(&buffers.u8[self.buffer][start..end])?;
+        Ok((self.next, Some(Event::Json(s))))
+    }
+
+    fn update_targets(&mut self, redirects: &HashMap<usize, usize>) -> Result<()> {
+        self.next = get_target_update(redirects, self.next);
+        Ok(())
+    }
+}
+
+impl Instruction for EmitDecimal128 {
+    fn emit<'a>(
+        &self,
+        positions: &mut [usize],
+        buffers: &Buffers<'a>,
+    ) -> Result<(usize, Option<Event<'a>>)> {
+        let val = get_decimal128(buffers, self.buffer, positions, self.position);
+        Ok((self.next, Some(Event::Decimal128(val, self.scale))))
+    }
+
+    fn update_targets(&mut self, redirects: &HashMap<usize, usize>) -> Result<()> {
+        self.next = get_target_update(redirects, self.next);
+        Ok(())
+    }
+}
+
+impl Instruction for EmitDecimal128Str {
+    fn emit<'a>(
+        &self,
+        positions: &mut [usize],
+        buffers: &Buffers<'a>,
+    ) -> Result<(usize, Option<Event<'a>>)> {
+        let val = get_decimal128(buffers, self.buffer, positions, self.position);
+
         let mut buffer = [0; decimal::BUFFER_SIZE_I128];
         let ev = Event::OwnedStr(decimal::format_decimal(&mut buffer, val, self.scale).to_owned());
 
@@ -1663,26 +2070,51 @@ impl Instruction for EmitDecimal128 {
     }
 }
 
-impl Instruction for EmitDate64NaiveStr {
+impl Instruction for EmitDecimal128F64 {
     fn emit<'a>(
         &self,
         positions: &mut [usize],
         buffers: &Buffers<'a>,
     ) -> Result<(usize, Option<Event<'a>>)> {
-        use chrono::NaiveDateTime;
+        let val = get_decimal128(buffers, self.buffer, positions, self.position);
+        let ev = Event::F64(decimal::decimal_to_f64(val, self.scale)?);
+        Ok((self.next, Some(ev)))
+    }
 
-        let val =
+    fn update_targets(&mut self, redirects: &HashMap<usize, usize>) -> Result<()> {
+        self.next = get_target_update(redirects, self.next);
+        Ok(())
+    }
+}
+
+fn get_decimal128(
+    buffers: &Buffers<'_>,
+    buffer: usize,
+    positions: &mut [usize],
+    position: usize,
+) -> i128 {
+    let val = i128::from_ne_bytes(buffers.u128[buffer][positions[position]].to_ne_bytes());
+    positions[position] += 1;
+    val
+}
+
+impl Instruction for EmitTimestamp {
+    fn emit<'a>(
+        &self,
+        positions: &mut [usize],
+        buffers: &Buffers<'a>,
+    ) -> Result<(usize, Option<Event<'a>>)> {
+        let value =
             i64::from_ne_bytes(buffers.u64[self.buffer][positions[self.position]].to_ne_bytes());
         positions[self.position] += 1;
 
-        let Some(val) =
-            NaiveDateTime::from_timestamp_opt(val / 1000, (val % 1000) as u32 * 100_000)
-        else {
-            fail!("Unsupported timestamp value: {val}");
-        };
-
-        // NOTE: chrono documents that Debug, not Display, can be parsed
-        Ok((self.next, Some(format!("{:?}", val).into())))
+        let ev = Event::Timestamp(Timestamp {
+            value,
+            unit: self.unit,
+            utc: self.utc,
+            prefer_str: self.prefer_str,
+        });
+        Ok((self.next, Some(ev)))
     }
 
     fn update_targets(&mut self, redirects: &HashMap<usize, usize>) -> Result<()> {
@@ -1691,27 +2123,38 @@ impl Instruction for EmitDate64NaiveStr {
     }
 }
 
-impl Instruction for EmitDate64UtcStr {
+impl Instruction for EmitDate32 {
     fn emit<'a>(
         &self,
         positions: &mut [usize],
         buffers: &Buffers<'a>,
     ) -> Result<(usize, Option<Event<'a>>)> {
-        use chrono::{TimeZone, Utc};
+        let value =
+            i32::from_ne_bytes(buffers.u32[self.buffer][positions[self.position]].to_ne_bytes());
+        positions[self.position] += 1;
 
-        let val =
+        let ev = Event::OwnedStr(timestamp::format_date32(value)?);
+        Ok((self.next, Some(ev)))
+    }
+
+    fn update_targets(&mut self, redirects: &HashMap<usize, usize>) -> Result<()> {
+        self.next = get_target_update(redirects, self.next);
+        Ok(())
+    }
+}
+
+impl Instruction for EmitTime64 {
+    fn emit<'a>(
+        &self,
+        positions: &mut [usize],
+        buffers: &Buffers<'a>,
+    ) -> Result<(usize, Option<Event<'a>>)> {
+        let value =
             i64::from_ne_bytes(buffers.u64[self.buffer][positions[self.position]].to_ne_bytes());
         positions[self.position] += 1;
 
-        let Some(val) = Utc
-            .timestamp_opt(val / 1000, (val % 1000) as u32 * 100_000)
-            .earliest()
-        else {
-            fail!("Unsupported timestamp value: {val}");
-        };
-
-        // NOTE: chrono documents that Debug, not Display, can be parsed
-        Ok((self.next, Some(format!("{:?}", val).into())))
+        let ev = Event::OwnedStr(timestamp::format_time64(value, self.unit)?);
+        Ok((self.next, Some(ev)))
     }
 
     fn update_targets(&mut self, redirects: &HashMap<usize, usize>) -> Result<()> {
@@ -1764,19 +2207,132 @@ impl Instruction for EmitDictionaryStr {
     }
 }
 
+#[derive(Clone)]
 pub struct Interpreter<'a> {
     current_instr: usize,
     program: Vec<Bytecode>,
     positions: Vec<usize>,
+    position_sources: Vec<PositionSource>,
+    /// The first instruction of each row
+    row_start: usize,
+    /// The last instruction of each row
+    row_end: usize,
+    num_items: usize,
+    /// The u0 buffer with the number of items of the outer sequence
+    outer_sequence_count: usize,
     buffers: Buffers<'a>,
 }
 
+impl<'a> Interpreter<'a> {
+    /// Move the interpreter to the start of the given row
+    ///
+    /// All position counters are derived from the row, following list and map
+    /// offsets as required. Afterwards, the interpreter
+    /// restarts the program as if the rows before had already been emitted.
+    pub fn seek(&mut self, row: usize) -> Result<()> {
+        for idx in 0..self.positions.len() {
+            self.positions[idx] = match self.position_sources[idx] {
+                PositionSource::Outer => row,
+                source => source
+                    .derive(&self.positions, &self.buffers)?
+                    .unwrap_or_default(),
+            };
+        }
+        self.current_instr = 0;
+        Ok(())
+    }
+
+    /// Restrict the outer sequence to the given rows
+    ///
+    /// The interpreter is moved to the start of the range and the sequence
+    /// ends after the last row of the range.
+    pub fn select_rows(&mut self, rows: Range<usize>) -> Result<()> {
+        if rows.start > rows.end || rows.end > self.num_items {
+            fail!(
+                "Invalid row range {start}..{end} for {num_items} rows",
+                start = rows.start,
+                end = rows.end,
+                num_items = self.num_items,
+            );
+        }
+        self.buffers.u0[self.outer_sequence_count] = rows.end;
+        self.seek(rows.start)
+    }
+
+    /// Deserialize the rows in chunks on the rayon thread pool and concatenate
+    /// the results in order
+    ///
+    /// Each chunk is deserialized by a copy of the interpreter restricted to
+    /// the rows of the chunk.
+    #[cfg(feature = "rayon")]
+    pub fn deserialize_parallel<T: serde::Deserialize<'a> + Send>(&self) -> Result<Vec<T>> {
+        use rayon::prelude::*;
+
+        let num_threads = rayon::current_num_threads().max(1);
+        let chunk_size = self.num_items.div_ceil(num_threads).max(1);
+
+        let chunks = (0..self.num_items)
+            .step_by(chunk_size)
+            .map(|start| start..usize::min(start + chunk_size, self.num_items))
+            .collect::<Vec<_>>();
+
+        let results = chunks
+            .into_par_iter()
+            .map(|rows| {
+                let mut interpreter = self.clone();
+                interpreter.select_rows(rows)?;
+                crate::internal::source::deserialize_from_source::<Vec<T>, _>(interpreter)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    /// Get an event source that emits the events of the row at the current
+    /// positions
+    ///
+    /// The source stops after the end of the row. Afterwards, the positions
+    /// point to the start of the next row.
+    pub fn row_events(&mut self) -> RowEvents<'_, 'a> {
+        self.current_instr = self.row_start;
+        RowEvents {
+            interpreter: self,
+            done: false,
+        }
+    }
+}
+
 impl<'a> EventSource<'a> for Interpreter<'a> {
     fn next(&mut self) -> Result<Option<Event<'a>>> {
-        let (next_instr, ev) =
-            self.program[self.current_instr].emit(&mut self.positions, &self.buffers)?;
-        self.current_instr = next_instr;
-        Ok(ev)
+        loop {
+            let current_instr = self.current_instr;
+            let (next_instr, ev) =
+                self.program[current_instr].emit(&mut self.positions, &self.buffers)?;
+            self.current_instr = next_instr;
+
+            // instructions may not emit an event (e.g., untagged unions), only the end of
+            // program, which points to itself, terminates the stream
+            if ev.is_some() || next_instr == current_instr {
+                return Ok(ev);
+            }
+        }
+    }
+}
+
+pub struct RowEvents<'i, 'a> {
+    interpreter: &'i mut Interpreter<'a>,
+    done: bool,
+}
+
+impl<'i, 'a> EventSource<'a> for RowEvents<'i, 'a> {
+    fn next(&mut self) -> Result<Option<Event<'a>>> {
+        if self.done {
+            return Ok(None);
+        }
+        if self.interpreter.current_instr == self.interpreter.row_end {
+            self.done = true;
+        }
+        self.interpreter.next()
     }
 }
 
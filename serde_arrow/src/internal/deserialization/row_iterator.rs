@@ -0,0 +1,81 @@
+use std::marker::PhantomData;
+
+use serde::Deserialize;
+
+use crate::internal::{error::Result, source::deserialize_from_source};
+
+use super::Interpreter;
+
+/// An iterator that deserializes the rows of arrays one at a time
+///
+/// It is created by `from_arrow_iter` or `from_arrow2_iter`. The
+/// deserialization program is compiled once and each call to `next`
+/// deserializes a single row. Rows that are skipped, e.g., via `nth` or
+/// `skip`, are not deserialized.
+///
+/// If the rows allow it, strings are borrowed from the arrays. After an
+/// error, the iterator continues with the next row.
+pub struct RowIterator<'de, T> {
+    interpreter: Interpreter<'de>,
+    next_row: usize,
+    num_rows: usize,
+    /// Whether the interpreter has to be moved to `next_row` before use
+    needs_seek: bool,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<'de, T> RowIterator<'de, T> {
+    pub(crate) fn new(interpreter: Interpreter<'de>, num_rows: usize) -> Self {
+        Self {
+            interpreter,
+            next_row: 0,
+            num_rows,
+            needs_seek: true,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> RowIterator<'de, T> {
+    fn deserialize_row(&mut self, row: usize) -> Result<T> {
+        if self.needs_seek {
+            self.interpreter.seek(row)?;
+        }
+
+        // a failed row leaves the positions inside the row
+        self.needs_seek = true;
+        let item = deserialize_from_source(self.interpreter.row_events())?;
+        self.needs_seek = false;
+
+        Ok(item)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Iterator for RowIterator<'de, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_row >= self.num_rows {
+            return None;
+        }
+        let row = self.next_row;
+        self.next_row += 1;
+
+        Some(self.deserialize_row(row))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.num_rows.saturating_sub(self.next_row);
+        (remaining, Some(remaining))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n != 0 {
+            self.next_row = self.next_row.saturating_add(n);
+            self.needs_seek = true;
+        }
+        self.next()
+    }
+}
+
+impl<'de, T: Deserialize<'de>> ExactSizeIterator for RowIterator<'de, T> {}
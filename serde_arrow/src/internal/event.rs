@@ -1,7 +1,11 @@
 //! The underlying data format used to interact with serde
 //!
 
-use crate::internal::error::{fail, Error, Result};
+use crate::internal::{
+    decimal,
+    error::{fail, Error, Result},
+    timestamp::Timestamp,
+};
 
 /// The events used to interact with serde
 ///
@@ -59,6 +63,26 @@ pub enum Event<'a> {
     U64(u64),
     F32(f32),
     F64(f64),
+    /// A decimal given by its unscaled value and its scale
+    ///
+    /// This event is only emitted during deserialization. Depending on the
+    /// requested type, it is converted into a string, a float, an integer, or
+    /// the parts of the decimal.
+    Decimal128(i128, i8),
+    /// A timestamp read from a `Date64` or `Timestamp` array
+    ///
+    /// This event is only emitted during deserialization. Depending on the
+    /// requested type, it is converted into a string, into an integer, or into
+    /// a tuple of its components.
+    Timestamp(Timestamp),
+    /// A value encoded as JSON, read from a field with the
+    /// [`JsonAsUtf8`][crate::internal::schema::Strategy::JsonAsUtf8] strategy
+    ///
+    /// This event is only emitted during deserialization. The value is parsed
+    /// when it is deserialized.
+    Json(&'a str),
+    /// The owned variant of `Json`
+    OwnedJson(String),
 }
 
 impl<'a> std::fmt::Display for Event<'a> {
@@ -87,10 +111,14 @@ impl<'a> std::fmt::Display for Event<'a> {
             Event::U64(v) => write!(f, "U64({v})"),
             Event::F32(v) => write!(f, "F32({v})"),
             Event::F64(v) => write!(f, "F64({v})"),
+            Event::Decimal128(v, s) => write!(f, "Decimal128({v}, {s})"),
+            Event::Timestamp(ts) => write!(f, "Timestamp({}, {})", ts.value, ts.unit),
             Event::Variant(n, i) => write!(f, "Variant({n:?}, {i})"),
             Event::OwnedVariant(n, i) => write!(f, "OwnedVariant({n:?}, {i})"),
             Event::Str(v) => write!(f, "Str({v:?})"),
             Event::OwnedStr(v) => write!(f, "String({v:?})"),
+            Event::Json(v) => write!(f, "Json({v:?})"),
+            Event::OwnedJson(v) => write!(f, "OwnedJson({v:?})"),
         }
     }
 }
@@ -130,6 +158,16 @@ impl<'this, 'other> std::cmp::PartialEq<Event<'other>> for Event<'this> {
                 OwnedStr(o) => s == o,
                 _ => false,
             },
+            Json(s) => match other {
+                Json(o) => s == o,
+                OwnedJson(o) => s == o,
+                _ => false,
+            },
+            OwnedJson(s) => match other {
+                Json(o) => s == o,
+                OwnedJson(o) => s == o,
+                _ => false,
+            },
             Some => matches!(other, Some),
             Bool(s) => matches!(other, Bool(o) if s == o),
             I8(s) => matches!(other, I8(o) if s == o),
@@ -142,6 +180,8 @@ impl<'this, 'other> std::cmp::PartialEq<Event<'other>> for Event<'this> {
             U64(s) => matches!(other, U64(o) if s == o),
             F32(s) => matches!(other, F32(o) if s == o),
             F64(s) => matches!(other, F64(o) if s == o),
+            Decimal128(v, s) => matches!(other, Decimal128(ov, os) if v == ov && s == os),
+            Timestamp(ts) => matches!(other, Timestamp(ots) if ts == ots),
         }
     }
 }
@@ -159,6 +199,8 @@ impl<'a> Event<'a> {
         match self {
             Event::OwnedStr(s) => Event::Str(s),
             Event::Str(s) => Event::Str(s),
+            Event::OwnedJson(s) => Event::Json(s),
+            Event::Json(s) => Event::Json(s),
             Event::Variant(n, i) => Event::Variant(n, *i),
             Event::OwnedVariant(n, i) => Event::Variant(n, *i),
             Event::StartSequence => Event::StartSequence,
@@ -183,6 +225,8 @@ impl<'a> Event<'a> {
             &Event::U64(v) => Event::U64(v),
             &Event::F32(v) => Event::F32(v),
             &Event::F64(v) => Event::F64(v),
+            &Event::Decimal128(v, s) => Event::Decimal128(v, s),
+            &Event::Timestamp(ts) => Event::Timestamp(ts),
             Event::Null => Event::Null,
         }
     }
@@ -193,6 +237,8 @@ impl<'a> Event<'a> {
         match self {
             &Event::Str(s) => Event::OwnedStr(s.to_owned()),
             Event::OwnedStr(v) => Event::OwnedStr(v.clone()),
+            &Event::Json(s) => Event::OwnedJson(s.to_owned()),
+            Event::OwnedJson(v) => Event::OwnedJson(v.clone()),
             &Event::Variant(n, i) => Event::OwnedVariant(n.to_owned(), i),
             Event::OwnedVariant(n, i) => Event::OwnedVariant(n.clone(), *i),
             Event::StartSequence => Event::StartSequence,
@@ -217,6 +263,8 @@ impl<'a> Event<'a> {
             &Event::U64(v) => Event::U64(v),
             &Event::F32(v) => Event::F32(v),
             &Event::F64(v) => Event::F64(v),
+            &Event::Decimal128(v, s) => Event::Decimal128(v, s),
+            &Event::Timestamp(ts) => Event::Timestamp(ts),
             Event::Null => Event::Null,
         }
     }
@@ -274,6 +322,8 @@ impl<'a> Event<'a> {
                 | Event::U64(_)
                 | Event::F32(_)
                 | Event::F64(_)
+                | Event::Decimal128(_, _)
+                | Event::Timestamp(_)
         )
     }
 
@@ -328,6 +378,8 @@ macro_rules! event_implement_try_from_from_event {
             fn try_from(val: Event<'_>) -> Result<$ty> {
                 match val {
                     $(Event::$variant(val) => Ok(val.try_into()?),)*
+                    Event::Decimal128(val, scale) => Ok(decimal::decimal_to_i128(val, scale)?.try_into()?),
+                    Event::Timestamp(ts) => Ok(ts.value.try_into()?),
                     event => fail!("invalid conversion from {} to {}", event, stringify!($ty)),
                 }
             }
@@ -341,12 +393,21 @@ impl<'a> TryFrom<Event<'a>> for f32 {
         match val {
             Event::F32(val) => Ok(val),
             Event::F64(val) => Ok(val as f32),
+            Event::Decimal128(val, scale) => Ok(decimal::decimal_to_f64(val, scale)? as f32),
             event => fail!("invalid conversion from {} to f32", event),
         }
     }
 }
 
-event_implement_try_from_from_event!(bool, Bool);
+impl<'a> TryFrom<Event<'a>> for bool {
+    type Error = Error;
+    fn try_from(val: Event<'_>) -> Result<bool> {
+        match val {
+            Event::Bool(val) => Ok(val),
+            event => fail!("invalid conversion from {} to bool", event),
+        }
+    }
+}
 
 event_implement_try_from_from_event!(i8, U8, U16, U32, U64, I8, I16, I32, I64, Bool);
 event_implement_try_from_from_event!(i16, U8, U16, U32, U64, I8, I16, I32, I64, Bool);
@@ -358,6 +419,33 @@ event_implement_try_from_from_event!(u16, U8, U16, U32, U64, I8, I16, I32, I64,
 event_implement_try_from_from_event!(u32, U8, U16, U32, U64, I8, I16, I32, I64, Bool);
 event_implement_try_from_from_event!(u64, U8, U16, U32, U64, I8, I16, I32, I64, Bool);
 
-event_implement_try_from_from_event!(f64, F32, F64);
+event_implement_try_from_from_event!(i128, U8, U16, U32, U64, I8, I16, I32, I64, Bool);
+event_implement_try_from_from_event!(u128, U8, U16, U32, U64, I8, I16, I32, I64, Bool);
 
-event_implement_try_from_from_event!(String, Str, OwnedStr);
+impl<'a> TryFrom<Event<'a>> for f64 {
+    type Error = Error;
+    fn try_from(val: Event<'_>) -> Result<f64> {
+        match val {
+            Event::F32(val) => Ok(val.into()),
+            Event::F64(val) => Ok(val),
+            Event::Decimal128(val, scale) => decimal::decimal_to_f64(val, scale),
+            event => fail!("invalid conversion from {} to f64", event),
+        }
+    }
+}
+
+impl<'a> TryFrom<Event<'a>> for String {
+    type Error = Error;
+    fn try_from(val: Event<'_>) -> Result<String> {
+        match val {
+            Event::Str(val) => Ok(val.to_owned()),
+            Event::OwnedStr(val) => Ok(val),
+            Event::Decimal128(val, scale) => {
+                let mut buffer = [0; decimal::BUFFER_SIZE_I128];
+                Ok(decimal::format_decimal(&mut buffer, val, scale).to_owned())
+            }
+            Event::Timestamp(ts) => ts.format(),
+            event => fail!("invalid conversion from {} to String", event),
+        }
+    }
+}
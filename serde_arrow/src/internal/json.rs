@@ -0,0 +1,110 @@
+//! Support for the [`JsonAsUtf8`][crate::internal::schema::Strategy::JsonAsUtf8]
+//! strategy
+//!
+//! Without the `json` feature, all functions fail.
+use serde::{
+    de::{DeserializeSeed, Visitor},
+    Serialize,
+};
+
+use crate::internal::error::Result;
+
+#[cfg(feature = "json")]
+mod imp {
+    use serde::{
+        de::{DeserializeSeed, Visitor},
+        Deserializer, Serialize,
+    };
+
+    use crate::internal::error::{Error, Result};
+
+    fn json_error(err: serde_json::Error) -> Error {
+        Error::custom_from(format!("serde_json::Error: {err}"), err)
+    }
+
+    pub fn ensure_available() -> Result<()> {
+        Ok(())
+    }
+
+    pub fn write_json<V: Serialize + ?Sized>(buffer: &mut Vec<u8>, value: &V) -> Result<()> {
+        serde_json::to_writer(buffer, value).map_err(json_error)
+    }
+
+    /// Parse the string into a value that can be deserialized with any
+    /// lifetime
+    fn parse(s: &str) -> Result<serde_json::Value> {
+        serde_json::from_str(s).map_err(json_error)
+    }
+
+    pub fn deserialize_json<'de, T: DeserializeSeed<'de>>(s: &str, seed: T) -> Result<T::Value> {
+        seed.deserialize(parse(s)?).map_err(json_error)
+    }
+
+    pub fn deserialize_json_any<'de, V: Visitor<'de>>(s: &str, visitor: V) -> Result<V::Value> {
+        parse(s)?.deserialize_any(visitor).map_err(json_error)
+    }
+
+    pub fn deserialize_json_option<'de, V: Visitor<'de>>(s: &str, visitor: V) -> Result<V::Value> {
+        parse(s)?.deserialize_option(visitor).map_err(json_error)
+    }
+}
+
+#[cfg(not(feature = "json"))]
+mod imp {
+    use serde::{
+        de::{DeserializeSeed, Visitor},
+        Serialize,
+    };
+
+    use crate::internal::error::{fail, Result};
+
+    const MISSING_FEATURE: &str = "The JsonAsUtf8 strategy requires the `json` feature";
+
+    pub fn ensure_available() -> Result<()> {
+        fail!("{MISSING_FEATURE}");
+    }
+
+    pub fn write_json<V: Serialize + ?Sized>(_buffer: &mut Vec<u8>, _value: &V) -> Result<()> {
+        fail!("{MISSING_FEATURE}");
+    }
+
+    pub fn deserialize_json<'de, T: DeserializeSeed<'de>>(_s: &str, _seed: T) -> Result<T::Value> {
+        fail!("{MISSING_FEATURE}");
+    }
+
+    pub fn deserialize_json_any<'de, V: Visitor<'de>>(_s: &str, _visitor: V) -> Result<V::Value> {
+        fail!("{MISSING_FEATURE}");
+    }
+
+    pub fn deserialize_json_option<'de, V: Visitor<'de>>(
+        _s: &str,
+        _visitor: V,
+    ) -> Result<V::Value> {
+        fail!("{MISSING_FEATURE}");
+    }
+}
+
+/// Fail, if the `json` feature is not enabled
+pub fn ensure_available() -> Result<()> {
+    imp::ensure_available()
+}
+
+/// Append the JSON representation of the value to the buffer
+pub fn write_json<V: Serialize + ?Sized>(buffer: &mut Vec<u8>, value: &V) -> Result<()> {
+    imp::write_json(buffer, value)
+}
+
+/// Deserialize a value from its JSON representation
+pub fn deserialize_json<'de, T: DeserializeSeed<'de>>(s: &str, seed: T) -> Result<T::Value> {
+    imp::deserialize_json(s, seed)
+}
+
+/// Deserialize a value of unknown type from its JSON representation
+pub fn deserialize_json_any<'de, V: Visitor<'de>>(s: &str, visitor: V) -> Result<V::Value> {
+    imp::deserialize_json_any(s, visitor)
+}
+
+/// Deserialize an optional value from its JSON representation
+pub fn deserialize_json_option<'de, V: Visitor<'de>>(s: &str, visitor: V) -> Result<V::Value> {
+    imp::deserialize_json_option(s, visitor)
+}
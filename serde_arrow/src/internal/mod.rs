@@ -6,8 +6,10 @@ pub mod deserialization;
 pub mod error;
 pub mod event;
 pub mod generic;
+pub mod json;
 pub mod schema;
 pub mod serialization_ng;
 pub mod sink;
 pub mod source;
+pub mod timestamp;
 pub mod tracing;
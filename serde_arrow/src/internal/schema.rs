@@ -3,11 +3,15 @@ mod deserialization;
 use std::{
     collections::{BTreeMap, HashMap},
     str::FromStr,
+    sync::Arc,
 };
 
 use crate::internal::{
     error::{fail, Error, Result},
-    tracing::{Tracer, TracingMode, TracingOptions},
+    tracing::{
+        from_type::record_sample_events, statistics::TracingStatistics, Tracer, TracingMode,
+        TracingOptions,
+    },
 };
 
 use serde::{Deserialize, Serialize};
@@ -18,6 +22,12 @@ use serde::{Deserialize, Serialize};
 ///
 pub const STRATEGY_KEY: &str = "SERDE_ARROW:strategy";
 
+/// The metadata key under which arrow stores the name of extension types
+pub(crate) const EXTENSION_NAME_KEY: &str = "ARROW:extension:name";
+
+/// The name of the canonical JSON extension type
+const JSON_EXTENSION_NAME: &str = "arrow.json";
+
 pub trait Sealed {}
 
 /// A sealed trait to add support for constructing schema-like objects
@@ -127,9 +137,17 @@ pub trait SchemaLike: Sized + Sealed {
     ///
     /// - auto detection of date time strings
     /// - non self-describing types such as `serde_json::Value`
-    /// - flattened structure (`#[serde(flatten)]`)
+    /// - flattened structure (`#[serde(flatten)]`): serde deserializes structs
+    ///   with flattened fields as maps and buffers the flattened fields in an
+    ///   internal representation, which is deserialized without calling back
+    ///   into the tracer. Therefore, neither the names nor the types of the
+    ///   flattened fields can be determined from the type. Use
+    ///   [`from_type_and_samples`][SchemaLike::from_type_and_samples] to trace
+    ///   these structs from the samples
     ///
-    /// Consider using [`from_samples`][SchemaLike::from_samples] in these cases.
+    /// Consider using [`from_samples`][SchemaLike::from_samples] or
+    /// [`from_type_and_samples`][SchemaLike::from_type_and_samples] in these
+    /// cases.
     ///
     /// ```rust
     /// # #[cfg(feature = "has_arrow")]
@@ -233,6 +251,13 @@ pub trait SchemaLike: Sized + Sealed {
     /// # fn main() { }
     /// ```
     ///
+    /// With
+    /// [`ConflictingTypes::Drop`][crate::schema::ConflictingTypes::Drop],
+    /// fields with conflicting types are traced as `Null` fields and their
+    /// values are discarded during serialization without any notice. Use
+    /// [`SerdeArrowSchema::from_samples_with_statistics`] to find the dropped
+    /// fields.
+    ///
     /// Note, the samples must encode "rows" in the resulting data frame. When
     /// encoding single arrays, consider using the
     /// [`Items`][crate::utils::Items] wrapper.
@@ -256,6 +281,53 @@ pub trait SchemaLike: Sized + Sealed {
     /// # fn main() { }
     /// ```
     fn from_samples<T: Serialize + ?Sized>(samples: &T, options: TracingOptions) -> Result<Self>;
+
+    /// Determine the schema from the given record type and samples
+    ///
+    /// The structure is traced from the type `T` as in
+    /// [`from_type`][SchemaLike::from_type]. Values that are not self
+    /// describing, such as `serde_json::Value`, and structs with flattened
+    /// fields (`#[serde(flatten)]`) are traced from the samples as in
+    /// [`from_samples`][SchemaLike::from_samples]. Values without samples are
+    /// described by
+    /// [`TracingOptions::any_fallback`][TracingOptions::any_fallback], if
+    /// given, otherwise an error is raised.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "has_arrow")]
+    /// # fn main() -> serde_arrow::_impl::PanicOnError<()> {
+    /// # use serde_arrow::_impl::arrow;
+    /// use arrow::datatypes::{DataType, Field};
+    /// use serde::{Deserialize, Serialize};
+    /// use serde_arrow::schema::{SchemaLike, TracingOptions};
+    ///
+    /// ##[derive(Serialize, Deserialize)]
+    /// struct Record {
+    ///     id: u32,
+    ///     value: serde_json::Value,
+    /// }
+    ///
+    /// let samples = vec![
+    ///     Record { id: 1, value: serde_json::json!(13.0) },
+    ///     Record { id: 2, value: serde_json::json!(21.5) },
+    /// ];
+    ///
+    /// let fields = Vec::<Field>::from_type_and_samples::<Record, _>(
+    ///     &samples,
+    ///     TracingOptions::default(),
+    /// )?;
+    ///
+    /// assert_eq!(fields[0].data_type(), &DataType::UInt32);
+    /// assert_eq!(fields[1].data_type(), &DataType::Float64);
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "has_arrow"))]
+    /// # fn main() { }
+    /// ```
+    fn from_type_and_samples<'de, T: Deserialize<'de>, S: Serialize + ?Sized>(
+        samples: &S,
+        options: TracingOptions,
+    ) -> Result<Self>;
 }
 
 /// A collection of fields as understood by `serde_arrow`
@@ -271,6 +343,64 @@ impl SerdeArrowSchema {
     }
 }
 
+impl SerdeArrowSchema {
+    /// Determine the schema from samples and return a profile of the observed
+    /// values
+    ///
+    /// The schema is traced as in
+    /// [`from_samples`][SchemaLike::from_samples]. The statistics (null
+    /// counts, value ranges, string and list lengths, distinct counts, variant
+    /// frequencies) are reported per field path, see [`TracingStatistics`].
+    ///
+    /// ```rust
+    /// # fn main() -> serde_arrow::Result<()> {
+    /// use serde::Serialize;
+    /// use serde_arrow::schema::{SerdeArrowSchema, StatisticsValue, TracingOptions};
+    ///
+    /// ##[derive(Serialize)]
+    /// struct Record {
+    ///     country: String,
+    ///     age: Option<u32>,
+    /// }
+    ///
+    /// let samples = [
+    ///     Record { country: String::from("DE"), age: Some(42) },
+    ///     Record { country: String::from("FR"), age: None },
+    ///     Record { country: String::from("DE"), age: Some(7) },
+    /// ];
+    ///
+    /// let (_schema, statistics) =
+    ///     SerdeArrowSchema::from_samples_with_statistics(&samples, TracingOptions::default())?;
+    ///
+    /// let country = statistics.get("$.country").unwrap();
+    /// assert_eq!(country.string_values, 3);
+    /// assert_eq!(country.distinct_strings, 2);
+    ///
+    /// let age = statistics.get("$.age").unwrap();
+    /// assert_eq!(age.nulls, 1);
+    /// assert_eq!(age.min, Some(StatisticsValue::Int(7)));
+    /// assert_eq!(age.max, Some(StatisticsValue::Int(42)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_samples_with_statistics<T: Serialize + ?Sized>(
+        samples: &T,
+        options: TracingOptions,
+    ) -> Result<(Self, TracingStatistics)> {
+        let options = options
+            .tracing_mode(TracingMode::FromSamples)
+            .collect_statistics(true);
+
+        let mut tracer = Tracer::new(String::from("$"), options);
+        tracer.trace_samples(samples)?;
+
+        let mut statistics = TracingStatistics::default();
+        tracer.collect_statistics(&mut statistics);
+
+        Ok((tracer.to_schema()?, statistics))
+    }
+}
+
 impl Sealed for SerdeArrowSchema {}
 
 impl SchemaLike for SerdeArrowSchema {
@@ -297,6 +427,27 @@ impl SchemaLike for SerdeArrowSchema {
         tracer.trace_samples(samples)?;
         tracer.to_schema()
     }
+
+    fn from_type_and_samples<'de, T: Deserialize<'de>, S: Serialize + ?Sized>(
+        samples: &S,
+        options: TracingOptions,
+    ) -> Result<Self> {
+        let mut sample_tracer = Tracer::new(
+            String::from("$"),
+            options.clone().tracing_mode(TracingMode::FromSamples),
+        );
+        sample_tracer.trace_samples(samples)?;
+
+        let options = options
+            .tracing_mode(TracingMode::FromType)
+            .defer_any(true)
+            .sample_events(Some(Arc::new(record_sample_events(samples)?)));
+
+        let mut tracer = Tracer::new(String::from("$"), options);
+        tracer.trace_type::<T>()?;
+        tracer.graft_samples(&sample_tracer);
+        tracer.to_schema()
+    }
 }
 
 /// Strategies for handling types without direct match between arrow and serde
@@ -312,6 +463,16 @@ impl SchemaLike for SerdeArrowSchema {
 pub enum Strategy {
     /// Marker that the type of the field could not be determined during tracing
     ///
+    /// Fields with values of conflicting types (see
+    /// [`TracingOptions::conflicting_types`][crate::schema::TracingOptions::conflicting_types])
+    /// are marked with this strategy. The data type determines how the values
+    /// are stored:
+    ///
+    /// - `LargeUtf8` / `Utf8`: booleans and numbers are stored as strings
+    /// - `Union`: each value is stored in the variant of its type, the values
+    ///   are deserialized without variant names
+    /// - `Null`: the values are ignored
+    ///
     InconsistentTypes,
     /// Serialize Rust strings containing UTC datetimes with timezone as Arrows
     /// Date64
@@ -355,6 +516,63 @@ pub enum Strategy {
     /// serialization or deserialization of such a field is attempted, it will
     /// result in an error.
     UnknownVariant,
+    /// Parse Rust strings into the primitive data type of the field
+    ///
+    /// This strategy applies to `Bool`, integer and float fields. It allows to
+    /// serialize data from loosely typed sources (e.g., CSV or JSON) that
+    /// encode numbers as strings, e.g., `"42"`, `"3.5"`, or `"true"`. Strings
+    /// that cannot be parsed result in an error. Values of the primitive type
+    /// itself are still accepted.
+    StrAsPrimitive,
+    /// Always deserialize Arrow `Decimal128` values as strings
+    ///
+    /// Without a strategy, decimals are converted into the type requested by
+    /// the deserialized type: strings, floats, integers (if the value has no
+    /// fractional part), `(i128, i8)` tuples of the unscaled value and the
+    /// scale, or maps in the representation of `serde_json`'s
+    /// `arbitrary_precision` feature. Self-describing types, e.g.,
+    /// `serde_json::Value`, receive strings. This strategy only affects
+    /// deserialization.
+    Decimal128AsStr,
+    /// Always deserialize Arrow `Decimal128` values as `f64`
+    ///
+    /// With this strategy, self-describing types, e.g., `serde_json::Value`,
+    /// receive numbers instead of strings. This strategy only affects
+    /// deserialization.
+    Decimal128AsF64,
+    /// Mark a `Utf8` or `LargeUtf8` field as containing UUIDs in their
+    /// hyphenated form, e.g., `67e55044-10b1-426f-9247-bb680e5fe0c8`
+    ///
+    /// The values are stored as strings. During serialization, strings that
+    /// are not UUIDs result in an error.
+    UuidStr,
+    /// Store arbitrary values as JSON strings in `Utf8` or `LargeUtf8` fields
+    ///
+    /// During serialization, the values are encoded as JSON, during
+    /// deserialization the strings are parsed. This way values with highly
+    /// variable structure can be stored without describing them in the
+    /// schema. Nulls of nullable fields are stored as nulls. The fields carry
+    /// the canonical `arrow.json` extension type and fields of this extension
+    /// type without strategy use this strategy. Requires the `json` feature.
+    ///
+    /// To use it in tracing, override the field with `{"strategy":
+    /// "JsonAsUtf8"}` (see
+    /// [`TracingOptions::override_field`][crate::schema::TracingOptions::override_field]).
+    JsonAsUtf8,
+}
+
+impl Strategy {
+    /// Determine the strategy from the metadata of a field
+    pub(crate) fn from_metadata(
+        strategy: Option<&String>,
+        extension_name: Option<&String>,
+    ) -> Result<Option<Strategy>> {
+        match (strategy, extension_name) {
+            (Some(strategy), _) => Ok(Some(strategy.parse()?)),
+            (None, Some(name)) if name == JSON_EXTENSION_NAME => Ok(Some(Strategy::JsonAsUtf8)),
+            (None, _) => Ok(None),
+        }
+    }
 }
 
 impl std::fmt::Display for Strategy {
@@ -366,6 +584,11 @@ impl std::fmt::Display for Strategy {
             Self::TupleAsStruct => write!(f, "TupleAsStruct"),
             Self::MapAsStruct => write!(f, "MapAsStruct"),
             Self::UnknownVariant => write!(f, "UnknownVariant"),
+            Self::StrAsPrimitive => write!(f, "StrAsPrimitive"),
+            Self::Decimal128AsStr => write!(f, "Decimal128AsStr"),
+            Self::Decimal128AsF64 => write!(f, "Decimal128AsF64"),
+            Self::UuidStr => write!(f, "UuidStr"),
+            Self::JsonAsUtf8 => write!(f, "JsonAsUtf8"),
         }
     }
 }
@@ -395,6 +618,11 @@ impl FromStr for Strategy {
             "TupleAsStruct" => Ok(Self::TupleAsStruct),
             "MapAsStruct" => Ok(Self::MapAsStruct),
             "UnknownVariant" => Ok(Self::UnknownVariant),
+            "StrAsPrimitive" => Ok(Self::StrAsPrimitive),
+            "Decimal128AsStr" => Ok(Self::Decimal128AsStr),
+            "Decimal128AsF64" => Ok(Self::Decimal128AsF64),
+            "UuidStr" => Ok(Self::UuidStr),
+            "JsonAsUtf8" => Ok(Self::JsonAsUtf8),
             _ => fail!("Unknown strategy {s}"),
         }
     }
@@ -403,6 +631,12 @@ impl FromStr for Strategy {
 impl From<Strategy> for BTreeMap<String, String> {
     fn from(value: Strategy) -> Self {
         let mut res = BTreeMap::new();
+        if matches!(value, Strategy::JsonAsUtf8) {
+            res.insert(
+                EXTENSION_NAME_KEY.to_string(),
+                JSON_EXTENSION_NAME.to_string(),
+            );
+        }
         res.insert(STRATEGY_KEY.to_string(), value.to_string());
         res
     }
@@ -411,12 +645,18 @@ impl From<Strategy> for BTreeMap<String, String> {
 impl From<Strategy> for HashMap<String, String> {
     fn from(value: Strategy) -> Self {
         let mut res = HashMap::new();
+        if matches!(value, Strategy::JsonAsUtf8) {
+            res.insert(
+                EXTENSION_NAME_KEY.to_string(),
+                JSON_EXTENSION_NAME.to_string(),
+            );
+        }
         res.insert(STRATEGY_KEY.to_string(), value.to_string());
         res
     }
 }
 
-#[derive(Debug, Clone, PartialEq, PartialOrd, Ord, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Serialize, Deserialize)]
 pub enum GenericTimeUnit {
     Second,
     Millisecond,
@@ -462,6 +702,8 @@ pub enum GenericDataType {
     Dictionary,
     Timestamp(GenericTimeUnit, Option<String>),
     Decimal128(u8, i8),
+    Date32,
+    Time64(GenericTimeUnit),
 }
 
 impl std::fmt::Display for GenericDataType {
@@ -498,6 +740,8 @@ impl std::fmt::Display for GenericDataType {
                 }
             }
             Decimal128(precision, scale) => write!(f, "Decimal128({precision}, {scale})"),
+            Date32 => write!(f, "Date32"),
+            Time64(unit) => write!(f, "Time64({unit})"),
         }
     }
 }
@@ -538,6 +782,8 @@ impl std::str::FromStr for GenericDataType {
             Ok(GenericDataType::F32)
         } else if s == "F64" || s == "Float64" {
             Ok(GenericDataType::F64)
+        } else if s == "Date32" {
+            Ok(GenericDataType::Date32)
         } else if s == "Date64" {
             Ok(GenericDataType::Date64)
         } else if s == "Struct" {
@@ -591,6 +837,15 @@ impl std::str::FromStr for GenericDataType {
             let scale = i8::from_str(scale.trim())?;
 
             Ok(GenericDataType::Decimal128(precision, scale))
+        } else if let Some(s) = s.strip_prefix("Time64(") {
+            let unit = match s {
+                "Second)" => GenericTimeUnit::Second,
+                "Millisecond)" => GenericTimeUnit::Millisecond,
+                "Microsecond)" => GenericTimeUnit::Microsecond,
+                "Nanosecond)" => GenericTimeUnit::Nanosecond,
+                _ => fail!("invalid Time64 data type"),
+            };
+            Ok(GenericDataType::Time64(unit))
         } else {
             fail!("cannot parse data type {s}");
         }
@@ -654,20 +909,20 @@ impl GenericField {
     pub fn validate(&self) -> Result<()> {
         match self.data_type {
             GenericDataType::Null => self.validate_null(),
-            GenericDataType::Bool => self.validate_primitive(),
-            GenericDataType::U8 => self.validate_primitive(),
-            GenericDataType::U16 => self.validate_primitive(),
-            GenericDataType::U32 => self.validate_primitive(),
-            GenericDataType::U64 => self.validate_primitive(),
-            GenericDataType::I8 => self.validate_primitive(),
-            GenericDataType::I16 => self.validate_primitive(),
-            GenericDataType::I32 => self.validate_primitive(),
-            GenericDataType::I64 => self.validate_primitive(),
-            GenericDataType::F16 => self.validate_primitive(),
-            GenericDataType::F32 => self.validate_primitive(),
-            GenericDataType::F64 => self.validate_primitive(),
-            GenericDataType::Utf8 => self.validate_primitive(),
-            GenericDataType::LargeUtf8 => self.validate_primitive(),
+            GenericDataType::Bool => self.validate_parsable_primitive(),
+            GenericDataType::U8 => self.validate_parsable_primitive(),
+            GenericDataType::U16 => self.validate_parsable_primitive(),
+            GenericDataType::U32 => self.validate_parsable_primitive(),
+            GenericDataType::U64 => self.validate_parsable_primitive(),
+            GenericDataType::I8 => self.validate_parsable_primitive(),
+            GenericDataType::I16 => self.validate_parsable_primitive(),
+            GenericDataType::I32 => self.validate_parsable_primitive(),
+            GenericDataType::I64 => self.validate_parsable_primitive(),
+            GenericDataType::F16 => self.validate_parsable_primitive(),
+            GenericDataType::F32 => self.validate_parsable_primitive(),
+            GenericDataType::F64 => self.validate_parsable_primitive(),
+            GenericDataType::Utf8 => self.validate_utf8(),
+            GenericDataType::LargeUtf8 => self.validate_utf8(),
             GenericDataType::Date64 => self.validate_date64(),
             GenericDataType::Struct => self.validate_struct(),
             GenericDataType::Map => self.validate_map(),
@@ -676,7 +931,9 @@ impl GenericField {
             GenericDataType::Union => self.validate_union(),
             GenericDataType::Dictionary => self.validate_dictionary(),
             GenericDataType::Timestamp(_, _) => self.validate_timestamp(),
-            GenericDataType::Decimal128(_, _) => self.validate_primitive(),
+            GenericDataType::Decimal128(_, _) => self.validate_decimal128(),
+            GenericDataType::Date32 => self.validate_date32(),
+            GenericDataType::Time64(_) => self.validate_time64(),
         }
     }
 
@@ -732,8 +989,42 @@ impl GenericField {
         Ok(())
     }
 
-    pub(crate) fn validate_primitive(&self) -> Result<()> {
-        if self.strategy.is_some() {
+    pub(crate) fn validate_utf8(&self) -> Result<()> {
+        if !matches!(
+            self.strategy,
+            None | Some(Strategy::InconsistentTypes | Strategy::UuidStr | Strategy::JsonAsUtf8)
+        ) {
+            fail!(
+                "invalid strategy for {}: {}",
+                self.data_type,
+                self.strategy.as_ref().unwrap()
+            );
+        }
+        if !self.children.is_empty() {
+            fail!("{} field must not have children", self.data_type);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn validate_parsable_primitive(&self) -> Result<()> {
+        if !matches!(self.strategy, None | Some(Strategy::StrAsPrimitive)) {
+            fail!(
+                "invalid strategy for {}: {}",
+                self.data_type,
+                self.strategy.as_ref().unwrap()
+            );
+        }
+        if !self.children.is_empty() {
+            fail!("{} field must not have children", self.data_type);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn validate_decimal128(&self) -> Result<()> {
+        if !matches!(
+            self.strategy,
+            None | Some(Strategy::Decimal128AsStr) | Some(Strategy::Decimal128AsF64)
+        ) {
             fail!(
                 "invalid strategy for {}: {}",
                 self.data_type,
@@ -759,6 +1050,35 @@ impl GenericField {
         Ok(())
     }
 
+    pub(crate) fn validate_date32(&self) -> Result<()> {
+        if let Some(strategy) = self.strategy.as_ref() {
+            fail!("invalid strategy for Date32 field: {strategy}");
+        }
+        if !self.children.is_empty() {
+            fail!("Date32 field must not have children");
+        }
+        Ok(())
+    }
+
+    pub(crate) fn validate_time64(&self) -> Result<()> {
+        if !matches!(
+            self.data_type,
+            GenericDataType::Time64(GenericTimeUnit::Microsecond | GenericTimeUnit::Nanosecond)
+        ) {
+            fail!(
+                "invalid unit for {}: only Microsecond and Nanosecond are supported",
+                self.data_type
+            );
+        }
+        if let Some(strategy) = self.strategy.as_ref() {
+            fail!("invalid strategy for {} field: {strategy}", self.data_type);
+        }
+        if !self.children.is_empty() {
+            fail!("{} field must not have children", self.data_type);
+        }
+        Ok(())
+    }
+
     pub(crate) fn validate_timestamp(&self) -> Result<()> {
         match &self.strategy {
             None => Ok(()),
@@ -862,7 +1182,7 @@ impl GenericField {
     }
 
     pub(crate) fn validate_union(&self) -> Result<()> {
-        if self.strategy.is_some() {
+        if !matches!(self.strategy, None | Some(Strategy::InconsistentTypes)) {
             fail!(
                 "invalid strategy for Union field: {}",
                 self.strategy.as_ref().unwrap()
@@ -185,12 +185,32 @@ impl<'de> Deserialize<'de> for GenericOrArrowDataType {
             }
 
             fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                use serde::de::IntoDeserializer;
+
                 match GenericDataType::from_str(v) {
                     Ok(res) => Ok(GenericOrArrowDataType::Generic(res)),
-                    Err(err) => Err(E::custom(err.to_string())),
+                    // unit variants of arrow data types in self-describing formats
+                    Err(err) => {
+                        match ArrowDataType::deserialize(IntoDeserializer::<E>::into_deserializer(
+                            v,
+                        )) {
+                            Ok(res) => Ok(GenericOrArrowDataType::Arrow(res)),
+                            Err(_) => Err(E::custom(err.to_string())),
+                        }
+                    }
                 }
             }
 
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                map: A,
+            ) -> Result<Self::Value, A::Error> {
+                // variants of arrow data types in self-describing formats
+                let field =
+                    ArrowDataType::deserialize(serde::de::value::MapAccessDeserializer::new(map))?;
+                Ok(GenericOrArrowDataType::Arrow(field))
+            }
+
             fn visit_enum<A: serde::de::EnumAccess<'de>>(
                 self,
                 data: A,
@@ -1,15 +1,25 @@
 use half::f16;
 use serde::Serialize;
 
-use crate::internal::error::Result;
+use crate::internal::error::{fail, Result};
 
 use super::{
-    bool_builder::BoolBuilder, date64_builder::Date64Builder, decimal_builder::DecimalBuilder,
-    dictionary_utf8_builder::DictionaryUtf8Builder, float_builder::FloatBuilder,
-    int_builder::IntBuilder, list_builder::ListBuilder, map_builder::MapBuilder,
-    null_builder::NullBuilder, struct_builder::StructBuilder, union_builder::UnionBuilder,
-    unknown_variant_builder::UnknownVariantBuilder, utf8_builder::Utf8Builder,
-    utils::SimpleSerializer,
+    bool_builder::BoolBuilder,
+    date32_builder::Date32Builder,
+    date64_builder::Date64Builder,
+    decimal_builder::DecimalBuilder,
+    dictionary_utf8_builder::DictionaryUtf8Builder,
+    float_builder::FloatBuilder,
+    int_builder::IntBuilder,
+    list_builder::ListBuilder,
+    map_builder::MapBuilder,
+    null_builder::NullBuilder,
+    struct_builder::StructBuilder,
+    time64_builder::Time64Builder,
+    union_builder::UnionBuilder,
+    unknown_variant_builder::UnknownVariantBuilder,
+    utf8_builder::Utf8Builder,
+    utils::{Mut, SimpleSerializer},
 };
 
 // TODO: add outer sequence builder? (not limited by i64 limits)
@@ -28,7 +38,9 @@ pub enum ArrayBuilder {
     F16(FloatBuilder<f16>),
     F32(FloatBuilder<f32>),
     F64(FloatBuilder<f64>),
+    Date32(Date32Builder),
     Date64(Date64Builder),
+    Time64(Time64Builder),
     Decimal128(DecimalBuilder),
     List(ListBuilder<i32>),
     LargeList(ListBuilder<i64>),
@@ -57,7 +69,9 @@ macro_rules! dispatch {
             $wrapper::F16($name) => $expr,
             $wrapper::F32($name) => $expr,
             $wrapper::F64($name) => $expr,
+            $wrapper::Date32($name) => $expr,
             $wrapper::Date64($name) => $expr,
+            $wrapper::Time64($name) => $expr,
             $wrapper::Decimal128($name) => $expr,
             $wrapper::Utf8($name) => $expr,
             $wrapper::LargeUtf8($name) => $expr,
@@ -88,7 +102,9 @@ impl ArrayBuilder {
             Self::F16(_) => "F16",
             Self::F32(_) => "F32",
             Self::F64(_) => "F64",
+            Self::Date32(_) => "Date32",
             Self::Date64(_) => "Date64",
+            Self::Time64(_) => "Time64",
             Self::Decimal128(_) => "Decimal128",
             Self::Utf8(_) => "Utf8",
             Self::LargeUtf8(_) => "LargeUtf8",
@@ -107,6 +123,62 @@ impl ArrayBuilder {
     }
 }
 
+impl ArrayBuilder {
+    /// Append the contents of another builder with the same structure
+    pub fn append(&mut self, other: ArrayBuilder) -> Result<()> {
+        match (self, other) {
+            (Self::Null(this), Self::Null(other)) => this.append(other),
+            (Self::Bool(this), Self::Bool(other)) => this.append(other),
+            (Self::I8(this), Self::I8(other)) => this.append(other),
+            (Self::I16(this), Self::I16(other)) => this.append(other),
+            (Self::I32(this), Self::I32(other)) => this.append(other),
+            (Self::I64(this), Self::I64(other)) => this.append(other),
+            (Self::U8(this), Self::U8(other)) => this.append(other),
+            (Self::U16(this), Self::U16(other)) => this.append(other),
+            (Self::U32(this), Self::U32(other)) => this.append(other),
+            (Self::U64(this), Self::U64(other)) => this.append(other),
+            (Self::F16(this), Self::F16(other)) => this.append(other),
+            (Self::F32(this), Self::F32(other)) => this.append(other),
+            (Self::F64(this), Self::F64(other)) => this.append(other),
+            (Self::Date32(this), Self::Date32(other)) => this.append(other),
+            (Self::Date64(this), Self::Date64(other)) => this.append(other),
+            (Self::Time64(this), Self::Time64(other)) => this.append(other),
+            (Self::Decimal128(this), Self::Decimal128(other)) => this.append(other),
+            (Self::Utf8(this), Self::Utf8(other)) => this.append(other),
+            (Self::LargeUtf8(this), Self::LargeUtf8(other)) => this.append(other),
+            (Self::List(this), Self::List(other)) => this.append(other),
+            (Self::LargeList(this), Self::LargeList(other)) => this.append(other),
+            (Self::Struct(this), Self::Struct(other)) => this.append(other),
+            (Self::Map(this), Self::Map(other)) => this.append(other),
+            (Self::DictionaryUtf8(this), Self::DictionaryUtf8(other)) => this.append(other),
+            (Self::Union(this), Self::Union(other)) => this.append(other),
+            (Self::UnknownVariant(this), Self::UnknownVariant(other)) => this.append(other),
+            (this, other) => fail!(
+                "Cannot append builder {} to builder {}",
+                other.name(),
+                this.name()
+            ),
+        }
+    }
+}
+
+impl ArrayBuilder {
+    /// Serialize a value into this builder
+    ///
+    /// String builders with the [`JsonAsUtf8`][crate::internal::schema::Strategy::JsonAsUtf8]
+    /// strategy store the JSON representation of the value, null builders that
+    /// ignore values drop the value regardless of its structure. All other
+    /// builders are serialized into directly.
+    pub fn serialize_value<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<()> {
+        match self {
+            Self::Null(builder) if builder.ignore_values => builder.serialize_none(),
+            Self::Utf8(builder) if builder.is_json() => builder.serialize_json(value),
+            Self::LargeUtf8(builder) if builder.is_json() => builder.serialize_json(value),
+            _ => value.serialize(Mut(self)),
+        }
+    }
+}
+
 impl ArrayBuilder {
     /// Take the contained array builder, while leaving structure intact
     pub fn take(&mut self) -> ArrayBuilder {
@@ -124,7 +196,9 @@ impl ArrayBuilder {
             Self::F16(builder) => Self::F16(builder.take()),
             Self::F32(builder) => Self::F32(builder.take()),
             Self::F64(builder) => Self::F64(builder.take()),
+            Self::Date32(builder) => Self::Date32(builder.take()),
             Self::Date64(builder) => Self::Date64(builder.take()),
+            Self::Time64(builder) => Self::Time64(builder.take()),
             Self::Decimal128(builder) => Self::Decimal128(builder.take()),
             Self::Utf8(builder) => Self::Utf8(builder.take()),
             Self::LargeUtf8(builder) => Self::LargeUtf8(builder.take()),
@@ -137,6 +211,38 @@ impl ArrayBuilder {
             Self::UnknownVariant(builder) => Self::UnknownVariant(builder.take()),
         }
     }
+
+    /// Construct an empty builder with the same structure as this one
+    pub fn empty(&self) -> ArrayBuilder {
+        match self {
+            Self::Null(builder) => Self::Null(builder.empty()),
+            Self::Bool(builder) => Self::Bool(builder.empty()),
+            Self::I8(builder) => Self::I8(builder.empty()),
+            Self::I16(builder) => Self::I16(builder.empty()),
+            Self::I32(builder) => Self::I32(builder.empty()),
+            Self::I64(builder) => Self::I64(builder.empty()),
+            Self::U8(builder) => Self::U8(builder.empty()),
+            Self::U16(builder) => Self::U16(builder.empty()),
+            Self::U32(builder) => Self::U32(builder.empty()),
+            Self::U64(builder) => Self::U64(builder.empty()),
+            Self::F16(builder) => Self::F16(builder.empty()),
+            Self::F32(builder) => Self::F32(builder.empty()),
+            Self::F64(builder) => Self::F64(builder.empty()),
+            Self::Date32(builder) => Self::Date32(builder.empty()),
+            Self::Date64(builder) => Self::Date64(builder.empty()),
+            Self::Time64(builder) => Self::Time64(builder.empty()),
+            Self::Decimal128(builder) => Self::Decimal128(builder.empty()),
+            Self::Utf8(builder) => Self::Utf8(builder.empty()),
+            Self::LargeUtf8(builder) => Self::LargeUtf8(builder.empty()),
+            Self::List(builder) => Self::List(builder.empty()),
+            Self::LargeList(builder) => Self::LargeList(builder.empty()),
+            Self::Struct(builder) => Self::Struct(builder.empty()),
+            Self::Map(builder) => Self::Map(builder.empty()),
+            Self::DictionaryUtf8(builder) => Self::DictionaryUtf8(builder.empty()),
+            Self::Union(builder) => Self::Union(builder.empty()),
+            Self::UnknownVariant(builder) => Self::UnknownVariant(builder.empty()),
+        }
+    }
 }
 
 #[rustfmt::skip]
@@ -1,16 +1,23 @@
-use crate::{internal::common::MutableBitBuffer, Result};
+use crate::{
+    internal::{common::MutableBitBuffer, error::fail},
+    Result,
+};
 
-use super::utils::{push_validity, push_validity_default, SimpleSerializer};
+use super::utils::{
+    append_validity, parse_str, push_validity, push_validity_default, SimpleSerializer,
+};
 
 #[derive(Debug, Clone)]
 pub struct BoolBuilder {
+    pub parse_str: bool,
     pub validity: Option<MutableBitBuffer>,
     pub buffer: MutableBitBuffer,
 }
 
 impl BoolBuilder {
-    pub fn new(is_nullable: bool) -> Self {
+    pub fn new(parse_str: bool, is_nullable: bool) -> Self {
         Self {
+            parse_str,
             validity: is_nullable.then(MutableBitBuffer::default),
             buffer: MutableBitBuffer::default(),
         }
@@ -18,14 +25,29 @@ impl BoolBuilder {
 
     pub fn take(&mut self) -> Self {
         Self {
+            parse_str: self.parse_str,
             validity: self.validity.as_mut().map(std::mem::take),
             buffer: std::mem::take(&mut self.buffer),
         }
     }
 
+    pub fn empty(&self) -> Self {
+        Self {
+            parse_str: self.parse_str,
+            validity: self.validity.as_ref().map(|_| Default::default()),
+            buffer: Default::default(),
+        }
+    }
+
     pub fn is_nullable(&self) -> bool {
         self.validity.is_some()
     }
+
+    pub fn append(&mut self, other: Self) -> Result<()> {
+        append_validity(&mut self.validity, other.validity)?;
+        self.buffer.append(&other.buffer);
+        Ok(())
+    }
 }
 
 impl SimpleSerializer for BoolBuilder {
@@ -50,4 +72,11 @@ impl SimpleSerializer for BoolBuilder {
         self.buffer.push(v);
         Ok(())
     }
+
+    fn serialize_str(&mut self, v: &str) -> Result<()> {
+        if !self.parse_str {
+            fail!("serialize_str is not implemented for {}", self.name());
+        }
+        self.serialize_bool(parse_str(v, "bool")?)
+    }
 }
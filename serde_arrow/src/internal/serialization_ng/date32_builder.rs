@@ -0,0 +1,76 @@
+use crate::{
+    internal::{common::MutableBitBuffer, timestamp},
+    Result,
+};
+
+use super::utils::{append_validity, push_validity, push_validity_default, SimpleSerializer};
+
+#[derive(Debug, Clone)]
+pub struct Date32Builder {
+    pub validity: Option<MutableBitBuffer>,
+    pub buffer: Vec<i32>,
+}
+
+impl Date32Builder {
+    pub fn new(nullable: bool) -> Self {
+        Self {
+            validity: nullable.then(MutableBitBuffer::default),
+            buffer: Vec::new(),
+        }
+    }
+
+    pub fn take(&mut self) -> Self {
+        Self {
+            validity: self.validity.as_mut().map(std::mem::take),
+            buffer: std::mem::take(&mut self.buffer),
+        }
+    }
+
+    pub fn empty(&self) -> Self {
+        Self {
+            validity: self.validity.as_ref().map(|_| Default::default()),
+            buffer: Default::default(),
+        }
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        self.validity.is_some()
+    }
+
+    pub fn append(&mut self, other: Self) -> Result<()> {
+        append_validity(&mut self.validity, other.validity)?;
+        self.buffer.extend(other.buffer);
+        Ok(())
+    }
+}
+
+impl SimpleSerializer for Date32Builder {
+    fn name(&self) -> &str {
+        "Date32Builder"
+    }
+
+    fn serialize_default(&mut self) -> Result<()> {
+        push_validity_default(&mut self.validity);
+        self.buffer.push(0);
+        Ok(())
+    }
+
+    fn serialize_none(&mut self) -> Result<()> {
+        push_validity(&mut self.validity, false)?;
+        self.buffer.push(0);
+        Ok(())
+    }
+
+    fn serialize_str(&mut self, v: &str) -> Result<()> {
+        let days = timestamp::parse_date32(v)?;
+        push_validity(&mut self.validity, true)?;
+        self.buffer.push(days);
+        Ok(())
+    }
+
+    fn serialize_i32(&mut self, v: i32) -> Result<()> {
+        push_validity(&mut self.validity, true)?;
+        self.buffer.push(v);
+        Ok(())
+    }
+}
@@ -1,9 +1,9 @@
 use crate::{
-    internal::{common::MutableBitBuffer, schema::GenericField},
+    internal::{common::MutableBitBuffer, schema::GenericField, timestamp},
     Result,
 };
 
-use super::utils::{push_validity, push_validity_default, SimpleSerializer};
+use super::utils::{append_validity, push_validity, push_validity_default, SimpleSerializer};
 
 #[derive(Debug, Clone)]
 pub struct Date64Builder {
@@ -32,9 +32,24 @@ impl Date64Builder {
         }
     }
 
+    pub fn empty(&self) -> Self {
+        Self {
+            field: self.field.clone(),
+            utc: self.utc,
+            validity: self.validity.as_ref().map(|_| Default::default()),
+            buffer: Default::default(),
+        }
+    }
+
     pub fn is_nullable(&self) -> bool {
         self.validity.is_some()
     }
+
+    pub fn append(&mut self, other: Self) -> Result<()> {
+        append_validity(&mut self.validity, other.validity)?;
+        self.buffer.extend(other.buffer);
+        Ok(())
+    }
 }
 
 impl SimpleSerializer for Date64Builder {
@@ -59,8 +74,7 @@ impl SimpleSerializer for Date64Builder {
             use chrono::{DateTime, Utc};
             v.parse::<DateTime<Utc>>()?.timestamp_millis()
         } else {
-            use chrono::NaiveDateTime;
-            v.parse::<NaiveDateTime>()?.timestamp_millis()
+            timestamp::parse_naive_datetime(v)?.timestamp_millis()
         };
         push_validity(&mut self.validity, true)?;
         self.buffer.push(timestamp);
@@ -1,21 +1,24 @@
 use crate::{
     internal::{
         common::MutableBitBuffer,
+        conversions::NumericConversion,
         decimal::{self, DecimalParser},
+        error::fail,
     },
     Result,
 };
 
-use super::utils::{push_validity, push_validity_default, SimpleSerializer};
+use super::utils::{append_validity, push_validity, push_validity_default, SimpleSerializer};
 
 #[derive(Debug, Clone)]
 pub struct DecimalBuilder {
     pub precision: u8,
     pub scale: i8,
+    pub conversion: NumericConversion,
     pub validity: Option<MutableBitBuffer>,
     pub buffer: Vec<i128>,
-    pub f32_factor: f32,
     pub f64_factor: f64,
+    pub max_value: i128,
     pub parser: DecimalParser,
 }
 
@@ -24,10 +27,11 @@ impl DecimalBuilder {
         Self {
             precision,
             scale,
+            conversion: NumericConversion::default(),
             validity: nullable.then(MutableBitBuffer::default),
             buffer: Vec::new(),
-            f32_factor: (10.0_f32).powi(scale as i32),
             f64_factor: (10.0_f64).powi(scale as i32),
+            max_value: 10_i128.pow(u32::from(precision)) - 1,
             parser: DecimalParser::new(precision, scale, true),
         }
     }
@@ -36,10 +40,24 @@ impl DecimalBuilder {
         Self {
             precision: self.precision,
             scale: self.scale,
+            conversion: self.conversion,
             validity: self.validity.as_mut().map(std::mem::take),
             buffer: std::mem::take(&mut self.buffer),
-            f32_factor: self.f32_factor,
             f64_factor: self.f64_factor,
+            max_value: self.max_value,
+            parser: self.parser,
+        }
+    }
+
+    pub fn empty(&self) -> Self {
+        Self {
+            precision: self.precision,
+            scale: self.scale,
+            conversion: self.conversion,
+            validity: self.validity.as_ref().map(|_| Default::default()),
+            buffer: Default::default(),
+            f64_factor: self.f64_factor,
+            max_value: self.max_value,
             parser: self.parser,
         }
     }
@@ -47,6 +65,61 @@ impl DecimalBuilder {
     pub fn is_nullable(&self) -> bool {
         self.validity.is_some()
     }
+
+    pub fn append(&mut self, other: Self) -> Result<()> {
+        append_validity(&mut self.validity, other.validity)?;
+        self.buffer.extend(other.buffer);
+        Ok(())
+    }
+
+    /// Push a float value already multiplied by `10^scale`
+    ///
+    /// `exact` states whether the rounded value converts back to the original
+    /// float.
+    fn push_scaled_float(
+        &mut self,
+        value: impl std::fmt::Display,
+        scaled: f64,
+        exact: bool,
+    ) -> Result<()> {
+        let saturating = self.conversion == NumericConversion::Saturating;
+        let rounded = if scaled.is_nan() && saturating {
+            0
+        } else if scaled.is_nan() {
+            fail!(
+                "Cannot convert {value} to Decimal128({}, {}): value out of range",
+                self.precision,
+                self.scale,
+            );
+        } else {
+            // note: `as` saturates at the bounds of i128
+            scaled.round() as i128
+        };
+
+        let rounded = if (-self.max_value..=self.max_value).contains(&rounded) {
+            rounded
+        } else if saturating {
+            rounded.clamp(-self.max_value, self.max_value)
+        } else {
+            fail!(
+                "Cannot convert {value} to Decimal128({}, {}): value out of range",
+                self.precision,
+                self.scale,
+            );
+        };
+
+        if self.conversion == NumericConversion::Strict && !exact {
+            fail!(
+                "Cannot convert {value} to Decimal128({}, {}) without loss of precision",
+                self.precision,
+                self.scale,
+            );
+        }
+
+        push_validity(&mut self.validity, true)?;
+        self.buffer.push(rounded);
+        Ok(())
+    }
 }
 
 impl SimpleSerializer for DecimalBuilder {
@@ -67,15 +140,15 @@ impl SimpleSerializer for DecimalBuilder {
     }
 
     fn serialize_f32(&mut self, v: f32) -> Result<()> {
-        push_validity(&mut self.validity, true)?;
-        self.buffer.push((v * self.f32_factor) as i128);
-        Ok(())
+        let scaled = f64::from(v) * self.f64_factor;
+        let exact = (scaled.round() / self.f64_factor) as f32 == v;
+        self.push_scaled_float(v, scaled, exact)
     }
 
     fn serialize_f64(&mut self, v: f64) -> Result<()> {
-        push_validity(&mut self.validity, true)?;
-        self.buffer.push((v * self.f64_factor) as i128);
-        Ok(())
+        let scaled = v * self.f64_factor;
+        let exact = scaled.round() / self.f64_factor == v;
+        self.push_scaled_float(v, scaled, exact)
     }
 
     fn serialize_str(&mut self, v: &str) -> Result<()> {
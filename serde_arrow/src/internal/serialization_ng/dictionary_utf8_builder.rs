@@ -1,11 +1,15 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::Range};
 
 use serde::Serialize;
 
-use crate::internal::{error::Result, schema::GenericField};
+use crate::internal::{
+    error::{fail, Result},
+    schema::GenericField,
+};
 
 use super::{
     array_builder::ArrayBuilder,
+    int_builder::IntBuilder,
     utils::{Mut, SimpleSerializer},
 };
 
@@ -15,6 +19,12 @@ pub struct DictionaryUtf8Builder {
     pub indices: Box<ArrayBuilder>,
     pub values: Box<ArrayBuilder>,
     pub index: HashMap<String, usize>,
+    /// If `true`, keep the dictionary when taking the builder
+    pub persistent: bool,
+    /// The number of values known at the start of the current batch
+    pub batch_start: usize,
+    /// The range of values added in the last taken batch
+    pub last_delta: Range<usize>,
 }
 
 impl DictionaryUtf8Builder {
@@ -24,21 +34,118 @@ impl DictionaryUtf8Builder {
             indices: Box::new(indices),
             values: Box::new(values),
             index: HashMap::new(),
+            persistent: false,
+            batch_start: 0,
+            last_delta: 0..0,
         }
     }
 
     pub fn take(&mut self) -> Self {
+        self.last_delta = self.batch_start..self.index.len();
+
+        if self.persistent {
+            // the taken builder only requires the values, not the index, as
+            // it is only used to build arrays
+            self.batch_start = self.index.len();
+            Self {
+                field: self.field.clone(),
+                indices: Box::new(self.indices.take()),
+                values: self.values.clone(),
+                index: HashMap::new(),
+                persistent: true,
+                batch_start: 0,
+                last_delta: self.last_delta.clone(),
+            }
+        } else {
+            Self {
+                field: self.field.clone(),
+                indices: Box::new(self.indices.take()),
+                values: Box::new(self.values.take()),
+                index: std::mem::take(&mut self.index),
+                persistent: false,
+                batch_start: 0,
+                last_delta: self.last_delta.clone(),
+            }
+        }
+    }
+
+    pub fn empty(&self) -> Self {
         Self {
             field: self.field.clone(),
-            indices: Box::new(self.indices.take()),
-            values: Box::new(self.values.take()),
-            index: std::mem::take(&mut self.index),
+            indices: Box::new(self.indices.empty()),
+            values: Box::new(self.values.empty()),
+            index: HashMap::new(),
+            persistent: self.persistent,
+            batch_start: 0,
+            last_delta: 0..0,
         }
     }
 
     pub fn is_nullable(&self) -> bool {
         self.indices.is_nullable()
     }
+
+    /// Append the entries of another builder
+    ///
+    /// Values unknown to this builder are added to its dictionary and the
+    /// indices of `other` are remapped accordingly.
+    pub fn append(&mut self, other: Self) -> Result<()> {
+        let mut other_values = other.index.into_iter().collect::<Vec<_>>();
+        other_values.sort_by_key(|(_, other_idx)| *other_idx);
+
+        let mut mapping = vec![0; other_values.len()];
+        for (value, other_idx) in other_values {
+            mapping[other_idx] = match self.index.get(&value) {
+                Some(idx) => *idx,
+                None => {
+                    let idx = self.index.len();
+                    self.values.serialize_str(&value)?;
+                    self.index.insert(value, idx);
+                    idx
+                }
+            };
+        }
+
+        match *other.indices {
+            ArrayBuilder::I8(other) => remap_indices(&mut self.indices, other, &mapping),
+            ArrayBuilder::I16(other) => remap_indices(&mut self.indices, other, &mapping),
+            ArrayBuilder::I32(other) => remap_indices(&mut self.indices, other, &mapping),
+            ArrayBuilder::I64(other) => remap_indices(&mut self.indices, other, &mapping),
+            ArrayBuilder::U8(other) => remap_indices(&mut self.indices, other, &mapping),
+            ArrayBuilder::U16(other) => remap_indices(&mut self.indices, other, &mapping),
+            ArrayBuilder::U32(other) => remap_indices(&mut self.indices, other, &mapping),
+            ArrayBuilder::U64(other) => remap_indices(&mut self.indices, other, &mapping),
+            other => fail!("Cannot use {} as an index for a dictionary", other.name()),
+        }
+    }
+}
+
+fn remap_indices<I>(
+    indices: &mut ArrayBuilder,
+    other: IntBuilder<I>,
+    mapping: &[usize],
+) -> Result<()>
+where
+    I: Copy + TryInto<usize> + std::fmt::Debug,
+{
+    for (pos, other_idx) in other.buffer.into_iter().enumerate() {
+        let is_valid = other
+            .validity
+            .as_ref()
+            .map(|v| v.is_set(pos))
+            .unwrap_or(true);
+        let mapped = other_idx
+            .try_into()
+            .ok()
+            .and_then(|other_idx: usize| mapping.get(other_idx));
+
+        match (is_valid, mapped) {
+            (true, Some(idx)) => idx.serialize(Mut(&mut *indices))?,
+            (true, None) => fail!("Invalid dictionary index {other_idx:?} at position {pos}"),
+            (false, _) => indices.serialize_none()?,
+        }
+    }
+    Ok(())
 }
 
 impl SimpleSerializer for DictionaryUtf8Builder {
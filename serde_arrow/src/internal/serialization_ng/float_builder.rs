@@ -1,18 +1,29 @@
-use half::f16;
-
-use crate::{internal::common::MutableBitBuffer, Result};
-
-use super::utils::{push_validity, push_validity_default, Mut, SimpleSerializer};
+use crate::{
+    internal::{
+        common::MutableBitBuffer,
+        conversions::{ConvertNumber, NumericConversion},
+        error::fail,
+    },
+    Result,
+};
+
+use super::utils::{
+    append_validity, parse_str, push_validity, push_validity_default, SimpleSerializer,
+};
 
 #[derive(Debug, Clone, Default)]
 pub struct FloatBuilder<I> {
+    pub parse_str: bool,
+    pub conversion: NumericConversion,
     pub validity: Option<MutableBitBuffer>,
     pub buffer: Vec<I>,
 }
 
 impl<I> FloatBuilder<I> {
-    pub fn new(is_nullable: bool) -> Self {
+    pub fn new(parse_str: bool, is_nullable: bool) -> Self {
         Self {
+            parse_str,
+            conversion: NumericConversion::default(),
             validity: is_nullable.then(MutableBitBuffer::default),
             buffer: Default::default(),
         }
@@ -20,15 +31,32 @@ impl<I> FloatBuilder<I> {
 
     pub fn take(&mut self) -> Self {
         Self {
+            parse_str: self.parse_str,
+            conversion: self.conversion,
             validity: self.validity.as_mut().map(std::mem::take),
             buffer: std::mem::take(&mut self.buffer),
         }
     }
 
+    pub fn empty(&self) -> Self {
+        Self {
+            parse_str: self.parse_str,
+            conversion: self.conversion,
+            validity: self.validity.as_ref().map(|_| Default::default()),
+            buffer: Default::default(),
+        }
+    }
+
     pub fn is_nullable(&self) -> bool {
         self.validity.is_some()
     }
 
+    pub fn append(&mut self, other: Self) -> Result<()> {
+        append_validity(&mut self.validity, other.validity)?;
+        self.buffer.extend(other.buffer);
+        Ok(())
+    }
+
     fn serialize_value(&mut self, value: I) -> Result<()> {
         push_validity(&mut self.validity, true)?;
         self.buffer.push(value);
@@ -36,147 +64,85 @@ impl<I> FloatBuilder<I> {
     }
 }
 
-impl SimpleSerializer for FloatBuilder<f32> {
-    fn name(&self) -> &str {
-        "FloatBuilder<f32>"
-    }
-
-    fn serialize_default(&mut self) -> Result<()> {
-        self.buffer.push(0.0);
-        Ok(())
-    }
-
-    fn serialize_none(&mut self) -> Result<()> {
-        push_validity(&mut self.validity, false)?;
-        self.buffer.push(0.0);
-        Ok(())
-    }
-
-    fn serialize_some<V: serde::Serialize + ?Sized>(&mut self, value: &V) -> Result<()> {
-        value.serialize(Mut(self))
-    }
-
-    fn serialize_i8(&mut self, v: i8) -> Result<()> {
-        self.serialize_value(v as f32)
-    }
-
-    fn serialize_i16(&mut self, v: i16) -> Result<()> {
-        self.serialize_value(v as f32)
-    }
-
-    fn serialize_i32(&mut self, v: i32) -> Result<()> {
-        self.serialize_value(v as f32)
-    }
-
-    fn serialize_i64(&mut self, v: i64) -> Result<()> {
-        self.serialize_value(v as f32)
-    }
-
-    fn serialize_u8(&mut self, v: u8) -> Result<()> {
-        self.serialize_value(v as f32)
-    }
-
-    fn serialize_u16(&mut self, v: u16) -> Result<()> {
-        self.serialize_value(v as f32)
-    }
-
-    fn serialize_u32(&mut self, v: u32) -> Result<()> {
-        self.serialize_value(v as f32)
-    }
-
-    fn serialize_u64(&mut self, v: u64) -> Result<()> {
-        self.serialize_value(v as f32)
+impl<I: Default + ConvertNumber> FloatBuilder<I> {
+    fn serialize_integer(&mut self, v: i128) -> Result<()> {
+        self.serialize_value(I::from_integer(v, self.conversion)?)
     }
 
-    fn serialize_f32(&mut self, v: f32) -> Result<()> {
-        self.serialize_value(v)
-    }
-
-    fn serialize_f64(&mut self, v: f64) -> Result<()> {
-        self.serialize_value(v as f32)
+    fn serialize_float(&mut self, v: f64) -> Result<()> {
+        self.serialize_value(I::from_float(v, self.conversion)?)
     }
 }
 
-impl SimpleSerializer for FloatBuilder<f64> {
+impl<I> SimpleSerializer for FloatBuilder<I>
+where
+    I: Default + ConvertNumber + std::str::FromStr,
+    I::Err: std::error::Error + Send + Sync + 'static,
+{
     fn name(&self) -> &str {
-        "FloatBuilder<64>"
+        match I::NAME {
+            "f16" => "FloatBuilder<f16>",
+            "f32" => "FloatBuilder<f32>",
+            _ => "FloatBuilder<f64>",
+        }
     }
 
     fn serialize_default(&mut self) -> Result<()> {
         push_validity_default(&mut self.validity);
-        self.buffer.push(0.0);
+        self.buffer.push(I::default());
         Ok(())
     }
 
     fn serialize_none(&mut self) -> Result<()> {
         push_validity(&mut self.validity, false)?;
-        self.buffer.push(0.0);
+        self.buffer.push(I::default());
         Ok(())
     }
 
     fn serialize_i8(&mut self, v: i8) -> Result<()> {
-        self.serialize_value(v as f64)
+        self.serialize_integer(v.into())
     }
 
     fn serialize_i16(&mut self, v: i16) -> Result<()> {
-        self.serialize_value(v as f64)
+        self.serialize_integer(v.into())
     }
 
     fn serialize_i32(&mut self, v: i32) -> Result<()> {
-        self.serialize_value(v as f64)
+        self.serialize_integer(v.into())
     }
 
     fn serialize_i64(&mut self, v: i64) -> Result<()> {
-        self.serialize_value(v as f64)
+        self.serialize_integer(v.into())
     }
 
     fn serialize_u8(&mut self, v: u8) -> Result<()> {
-        self.serialize_value(v as f64)
+        self.serialize_integer(v.into())
     }
 
     fn serialize_u16(&mut self, v: u16) -> Result<()> {
-        self.serialize_value(v as f64)
+        self.serialize_integer(v.into())
     }
 
     fn serialize_u32(&mut self, v: u32) -> Result<()> {
-        self.serialize_value(v as f64)
+        self.serialize_integer(v.into())
     }
 
     fn serialize_u64(&mut self, v: u64) -> Result<()> {
-        self.serialize_value(v as f64)
+        self.serialize_integer(v.into())
     }
 
     fn serialize_f32(&mut self, v: f32) -> Result<()> {
-        self.serialize_value(v as f64)
+        self.serialize_float(v.into())
     }
 
     fn serialize_f64(&mut self, v: f64) -> Result<()> {
-        self.serialize_value(v)
+        self.serialize_float(v)
     }
-}
 
-impl SimpleSerializer for FloatBuilder<f16> {
-    fn name(&self) -> &str {
-        "FloatBuilder<f16>"
-    }
-
-    fn serialize_default(&mut self) -> Result<()> {
-        push_validity_default(&mut self.validity);
-        self.buffer.push(f16::ZERO);
-        Ok(())
-    }
-
-    fn serialize_none(&mut self) -> Result<()> {
-        push_validity(&mut self.validity, false)?;
-        self.buffer.push(f16::ZERO);
-        Ok(())
-    }
-
-    fn serialize_f32(&mut self, v: f32) -> Result<()> {
-        self.serialize_value(f16::from_f32(v))
-    }
-
-    fn serialize_f64(&mut self, v: f64) -> Result<()> {
-        self.serialize_value(f16::from_f64(v))
+    fn serialize_str(&mut self, v: &str) -> Result<()> {
+        if !self.parse_str {
+            fail!("serialize_str is not implemented for {}", self.name());
+        }
+        self.serialize_value(parse_str(v, I::NAME)?)
     }
 }
@@ -1,16 +1,29 @@
-use crate::{internal::common::MutableBitBuffer, Error, Result};
-
-use super::utils::{push_validity, push_validity_default, SimpleSerializer};
+use crate::{
+    internal::{
+        common::MutableBitBuffer,
+        conversions::{ConvertNumber, NumericConversion},
+        error::fail,
+    },
+    Result,
+};
+
+use super::utils::{
+    append_validity, parse_str, push_validity, push_validity_default, SimpleSerializer,
+};
 
 #[derive(Debug, Clone, Default)]
 pub struct IntBuilder<I> {
+    pub parse_str: bool,
+    pub conversion: NumericConversion,
     pub validity: Option<MutableBitBuffer>,
     pub buffer: Vec<I>,
 }
 
 impl<I> IntBuilder<I> {
-    pub fn new(is_nullable: bool) -> Self {
+    pub fn new(parse_str: bool, is_nullable: bool) -> Self {
         Self {
+            parse_str,
+            conversion: NumericConversion::default(),
             validity: is_nullable.then(MutableBitBuffer::default),
             buffer: Default::default(),
         }
@@ -18,36 +31,50 @@ impl<I> IntBuilder<I> {
 
     pub fn take(&mut self) -> Self {
         Self {
+            parse_str: self.parse_str,
+            conversion: self.conversion,
             validity: self.validity.as_mut().map(std::mem::take),
             buffer: std::mem::take(&mut self.buffer),
         }
     }
 
+    pub fn empty(&self) -> Self {
+        Self {
+            parse_str: self.parse_str,
+            conversion: self.conversion,
+            validity: self.validity.as_ref().map(|_| Default::default()),
+            buffer: Default::default(),
+        }
+    }
+
     pub fn is_nullable(&self) -> bool {
         self.validity.is_some()
     }
+
+    pub fn append(&mut self, other: Self) -> Result<()> {
+        append_validity(&mut self.validity, other.validity)?;
+        self.buffer.extend(other.buffer);
+        Ok(())
+    }
 }
 
-impl<I> SimpleSerializer for IntBuilder<I>
-where
-    I: Default
-        + TryFrom<i8>
-        + TryFrom<i16>
-        + TryFrom<i32>
-        + TryFrom<i64>
-        + TryFrom<u8>
-        + TryFrom<u16>
-        + TryFrom<u32>
-        + TryFrom<u64>,
-    Error: From<<I as TryFrom<i8>>::Error>,
-    Error: From<<I as TryFrom<i16>>::Error>,
-    Error: From<<I as TryFrom<i32>>::Error>,
-    Error: From<<I as TryFrom<i64>>::Error>,
-    Error: From<<I as TryFrom<u8>>::Error>,
-    Error: From<<I as TryFrom<u16>>::Error>,
-    Error: From<<I as TryFrom<u32>>::Error>,
-    Error: From<<I as TryFrom<u64>>::Error>,
-{
+impl<I: Default + ConvertNumber> IntBuilder<I> {
+    fn serialize_integer(&mut self, v: i128) -> Result<()> {
+        let value = I::from_integer(v, self.conversion)?;
+        push_validity(&mut self.validity, true)?;
+        self.buffer.push(value);
+        Ok(())
+    }
+
+    fn serialize_float(&mut self, v: f64) -> Result<()> {
+        let value = I::from_float(v, self.conversion)?;
+        push_validity(&mut self.validity, true)?;
+        self.buffer.push(value);
+        Ok(())
+    }
+}
+
+impl<I: Default + ConvertNumber> SimpleSerializer for IntBuilder<I> {
     fn name(&self) -> &str {
         "IntBuilder<()>"
     }
@@ -65,56 +92,58 @@ where
     }
 
     fn serialize_i8(&mut self, v: i8) -> Result<()> {
-        push_validity(&mut self.validity, true)?;
-        self.buffer.push(I::try_from(v)?);
-        Ok(())
+        self.serialize_integer(v.into())
     }
 
     fn serialize_i16(&mut self, v: i16) -> Result<()> {
-        push_validity(&mut self.validity, true)?;
-        self.buffer.push(I::try_from(v)?);
-        Ok(())
+        self.serialize_integer(v.into())
     }
 
     fn serialize_i32(&mut self, v: i32) -> Result<()> {
-        push_validity(&mut self.validity, true)?;
-        self.buffer.push(I::try_from(v)?);
-        Ok(())
+        self.serialize_integer(v.into())
     }
 
     fn serialize_i64(&mut self, v: i64) -> Result<()> {
-        push_validity(&mut self.validity, true)?;
-        self.buffer.push(I::try_from(v)?);
-        Ok(())
+        self.serialize_integer(v.into())
     }
 
     fn serialize_u8(&mut self, v: u8) -> Result<()> {
-        push_validity(&mut self.validity, true)?;
-        self.buffer.push(I::try_from(v)?);
-        Ok(())
+        self.serialize_integer(v.into())
     }
 
     fn serialize_u16(&mut self, v: u16) -> Result<()> {
-        push_validity(&mut self.validity, true)?;
-        self.buffer.push(I::try_from(v)?);
-        Ok(())
+        self.serialize_integer(v.into())
     }
 
     fn serialize_u32(&mut self, v: u32) -> Result<()> {
-        push_validity(&mut self.validity, true)?;
-        self.buffer.push(I::try_from(v)?);
-        Ok(())
+        self.serialize_integer(v.into())
     }
 
     fn serialize_u64(&mut self, v: u64) -> Result<()> {
-        push_validity(&mut self.validity, true)?;
-        self.buffer.push(I::try_from(v)?);
-        Ok(())
+        self.serialize_integer(v.into())
+    }
+
+    fn serialize_f32(&mut self, v: f32) -> Result<()> {
+        self.serialize_float(v.into())
+    }
+
+    fn serialize_f64(&mut self, v: f64) -> Result<()> {
+        self.serialize_float(v)
     }
 
     fn serialize_char(&mut self, v: char) -> Result<()> {
-        push_validity(&mut self.validity, true)?;
-        self.buffer.push(I::try_from(u32::from(v))?);
-        Ok(())
+        self.serialize_integer(u32::from(v).into())
+    }
+
+    fn serialize_str(&mut self, v: &str) -> Result<()> {
+        if !self.parse_str {
+            fail!("serialize_str is not implemented for {}", self.name());
+        }
+
+        if v.starts_with('-') {
+            self.serialize_integer(parse_str::<i64>(v, I::NAME)?.into())
+        } else {
+            self.serialize_integer(parse_str::<u64>(v, I::NAME)?.into())
+        }
     }
 }
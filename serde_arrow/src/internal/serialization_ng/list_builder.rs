@@ -10,7 +10,7 @@ use crate::{
 
 use super::{
     array_builder::ArrayBuilder,
-    utils::{push_validity, push_validity_default, Mut, SimpleSerializer},
+    utils::{append_validity, push_validity, push_validity_default, SimpleSerializer},
 };
 
 #[derive(Debug, Clone)]
@@ -41,9 +41,24 @@ impl<O: Offset> ListBuilder<O> {
         }
     }
 
+    pub fn empty(&self) -> Self {
+        Self {
+            field: self.field.clone(),
+            validity: self.validity.as_ref().map(|_| Default::default()),
+            offsets: Default::default(),
+            element: Box::new(self.element.empty()),
+        }
+    }
+
     pub fn is_nullable(&self) -> bool {
         self.validity.is_some()
     }
+
+    pub fn append(&mut self, other: Self) -> Result<()> {
+        append_validity(&mut self.validity, other.validity)?;
+        self.offsets.append(other.offsets)?;
+        self.element.append(*other.element)
+    }
 }
 
 impl<O: Offset> ListBuilder<O> {
@@ -53,7 +68,7 @@ impl<O: Offset> ListBuilder<O> {
 
     fn element<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<()> {
         self.offsets.inc_current_items()?;
-        value.serialize(Mut(self.element.as_mut()))
+        self.element.serialize_value(value)
     }
 
     fn end(&mut self) -> Result<()> {
@@ -10,7 +10,7 @@ use crate::{
 
 use super::{
     array_builder::ArrayBuilder,
-    utils::{push_validity, push_validity_default, SimpleSerializer},
+    utils::{append_validity, push_validity, push_validity_default, SimpleSerializer},
 };
 
 #[derive(Debug, Clone)]
@@ -40,9 +40,24 @@ impl MapBuilder {
         }
     }
 
+    pub fn empty(&self) -> Self {
+        Self {
+            entry_field: self.entry_field.clone(),
+            validity: self.validity.as_ref().map(|_| Default::default()),
+            offsets: Default::default(),
+            entry: Box::new(self.entry.empty()),
+        }
+    }
+
     pub fn is_nullable(&self) -> bool {
         self.validity.is_some()
     }
+
+    pub fn append(&mut self, other: Self) -> Result<()> {
+        append_validity(&mut self.validity, other.validity)?;
+        self.offsets.append(other.offsets)?;
+        self.entry.append(*other.entry)
+    }
 }
 
 impl SimpleSerializer for MapBuilder {
@@ -2,6 +2,7 @@
 
 pub mod array_builder;
 pub mod bool_builder;
+pub mod date32_builder;
 pub mod date64_builder;
 pub mod decimal_builder;
 pub mod dictionary_utf8_builder;
@@ -12,6 +13,8 @@ pub mod map_builder;
 pub mod null_builder;
 pub mod outer_sequence_builder;
 pub mod struct_builder;
+pub mod time64_builder;
+pub mod transcode;
 pub mod union_builder;
 pub mod unknown_variant_builder;
 pub mod utf8_builder;
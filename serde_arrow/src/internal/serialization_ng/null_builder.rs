@@ -1,26 +1,61 @@
-use crate::Result;
+use crate::{internal::error::fail, Result};
 
 use super::utils::SimpleSerializer;
 
 #[derive(Debug, Clone, Default)]
 pub struct NullBuilder {
     pub count: usize,
+    /// If `true`, primitive values are accepted and stored as nulls, as used
+    /// by the [`InconsistentTypes`][crate::internal::schema::Strategy::InconsistentTypes]
+    /// strategy
+    pub ignore_values: bool,
 }
 
 impl NullBuilder {
-    pub fn new() -> Self {
-        Self::default()
+    pub fn new(ignore_values: bool) -> Self {
+        Self {
+            count: 0,
+            ignore_values,
+        }
     }
 
     pub fn take(&mut self) -> Self {
         Self {
             count: std::mem::take(&mut self.count),
+            ignore_values: self.ignore_values,
         }
     }
 
+    pub fn empty(&self) -> Self {
+        Self::new(self.ignore_values)
+    }
+
+    fn serialize_ignored(&mut self, method: &str) -> Result<()> {
+        if !self.ignore_values {
+            fail!("{method} is not implemented for {}", self.name());
+        }
+        self.count += 1;
+        Ok(())
+    }
+
     pub fn is_nullable(&self) -> bool {
         true
     }
+
+    pub fn append(&mut self, other: Self) -> Result<()> {
+        self.count += other.count;
+        Ok(())
+    }
+}
+
+macro_rules! serialize_ignored {
+    ($($name:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $name(&mut self, _: $ty) -> Result<()> {
+                self.serialize_ignored(stringify!($name))
+            }
+        )*
+    };
 }
 
 impl SimpleSerializer for NullBuilder {
@@ -42,4 +77,20 @@ impl SimpleSerializer for NullBuilder {
         self.count += 1;
         Ok(())
     }
+
+    serialize_ignored!(
+        serialize_bool(bool),
+        serialize_char(char),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_str(&str),
+    );
 }
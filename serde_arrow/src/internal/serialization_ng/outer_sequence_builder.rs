@@ -1,7 +1,10 @@
-use serde::Serialize;
+use std::ops::Range;
+
+use serde::{Deserializer, Serialize};
 
 use crate::{
     internal::{
+        conversions::NumericConversion,
         error::fail,
         schema::{GenericDataType, GenericField, GenericTimeUnit},
     },
@@ -11,6 +14,7 @@ use crate::{
 
 use super::{
     bool_builder::BoolBuilder,
+    date32_builder::Date32Builder,
     date64_builder::Date64Builder,
     decimal_builder::DecimalBuilder,
     dictionary_utf8_builder::DictionaryUtf8Builder,
@@ -19,7 +23,9 @@ use super::{
     list_builder::ListBuilder,
     map_builder::MapBuilder,
     null_builder::NullBuilder,
-    struct_builder::StructBuilder,
+    struct_builder::{KeyLookupSerializer, StructBuilder},
+    time64_builder::Time64Builder,
+    transcode::Transcoder,
     union_builder::UnionBuilder,
     unknown_variant_builder::UnknownVariantBuilder,
     utf8_builder::Utf8Builder,
@@ -47,26 +53,31 @@ impl OuterSequenceBuilder {
         fn build_builder(field: &GenericField) -> Result<ArrayBuilder> {
             use {ArrayBuilder as A, GenericDataType as T};
 
+            let parse_str = matches!(&field.strategy, Some(Strategy::StrAsPrimitive));
+            let inconsistent_types = matches!(&field.strategy, Some(Strategy::InconsistentTypes));
+
             let builder = match &field.data_type {
                 T::Null => {
                     if matches!(&field.strategy, Some(Strategy::UnknownVariant)) {
                         A::UnknownVariant(UnknownVariantBuilder)
                     } else {
-                        A::Null(NullBuilder::new())
+                        A::Null(NullBuilder::new(inconsistent_types))
                     }
                 }
-                T::Bool => A::Bool(BoolBuilder::new(field.nullable)),
-                T::I8 => A::I8(IntBuilder::new(field.nullable)),
-                T::I16 => A::I16(IntBuilder::new(field.nullable)),
-                T::I32 => A::I32(IntBuilder::new(field.nullable)),
-                T::I64 => A::I64(IntBuilder::new(field.nullable)),
-                T::U8 => A::U8(IntBuilder::new(field.nullable)),
-                T::U16 => A::U16(IntBuilder::new(field.nullable)),
-                T::U32 => A::U32(IntBuilder::new(field.nullable)),
-                T::U64 => A::U64(IntBuilder::new(field.nullable)),
-                T::F16 => A::F16(FloatBuilder::new(field.nullable)),
-                T::F32 => A::F32(FloatBuilder::new(field.nullable)),
-                T::F64 => A::F64(FloatBuilder::new(field.nullable)),
+                T::Bool => A::Bool(BoolBuilder::new(parse_str, field.nullable)),
+                T::I8 => A::I8(IntBuilder::new(parse_str, field.nullable)),
+                T::I16 => A::I16(IntBuilder::new(parse_str, field.nullable)),
+                T::I32 => A::I32(IntBuilder::new(parse_str, field.nullable)),
+                T::I64 => A::I64(IntBuilder::new(parse_str, field.nullable)),
+                T::U8 => A::U8(IntBuilder::new(parse_str, field.nullable)),
+                T::U16 => A::U16(IntBuilder::new(parse_str, field.nullable)),
+                T::U32 => A::U32(IntBuilder::new(parse_str, field.nullable)),
+                T::U64 => A::U64(IntBuilder::new(parse_str, field.nullable)),
+                T::F16 => A::F16(FloatBuilder::new(parse_str, field.nullable)),
+                T::F32 => A::F32(FloatBuilder::new(parse_str, field.nullable)),
+                T::F64 => A::F64(FloatBuilder::new(parse_str, field.nullable)),
+                T::Date32 => A::Date32(Date32Builder::new(field.nullable)),
+                T::Time64(unit) => A::Time64(Time64Builder::new(*unit, field.nullable)),
                 T::Date64 => match field.strategy.as_ref() {
                     Some(Strategy::NaiveStrAsDate64) => {
                         A::Date64(Date64Builder::new(field.clone(), false, field.nullable))
@@ -93,8 +104,10 @@ impl OuterSequenceBuilder {
                 T::Decimal128(precision, scale) => {
                     A::Decimal128(DecimalBuilder::new(*precision, *scale, field.nullable))
                 }
-                T::Utf8 => A::Utf8(Utf8Builder::new(field.nullable)),
-                T::LargeUtf8 => A::LargeUtf8(Utf8Builder::new(field.nullable)),
+                T::Utf8 => A::Utf8(Utf8Builder::new(field.strategy.clone(), field.nullable)),
+                T::LargeUtf8 => {
+                    A::LargeUtf8(Utf8Builder::new(field.strategy.clone(), field.nullable))
+                }
                 T::List => {
                     let Some(child) = field.children.first() else {
                         fail!("cannot build a list without an element field");
@@ -183,6 +196,364 @@ impl OuterSequenceBuilder {
     pub fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
         self.element(value)
     }
+
+    /// Extend the builder with columns, i.e., a struct or map of sequences
+    ///
+    /// Each sequence is serialized into the field of the same name. All fields
+    /// must be given and all sequences must be of equal length.
+    pub fn extend_columns<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let mut columns = ColumnsSerializer {
+            builder: &mut self.0,
+            lengths: Vec::new(),
+            next: None,
+        };
+        value.serialize(Mut(&mut columns))?;
+        let columns = columns.lengths;
+
+        let mut lengths = vec![None; self.0.named_fields.len()];
+        for (idx, len) in columns {
+            if lengths[idx].is_some() {
+                fail!("Duplicate column {}", self.0.named_fields[idx].0);
+            }
+            lengths[idx] = Some(len);
+        }
+
+        let mut num_rows = None;
+        for ((name, _), len) in std::iter::zip(&self.0.named_fields, lengths) {
+            let Some(len) = len else {
+                fail!("Missing column {name}");
+            };
+            match num_rows {
+                None => num_rows = Some(len),
+                Some(num_rows) if num_rows != len => {
+                    fail!("Column {name} has {len} rows, expected {num_rows}")
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Extend the builder with the sequence of records of a deserializer
+    pub fn extend_from_deserializer<'de, D: Deserializer<'de>>(
+        &mut self,
+        deserializer: D,
+    ) -> Result<()> {
+        self.extend(&Transcoder::new(deserializer))
+    }
+
+    /// Push a single record of a deserializer into the builder
+    pub fn push_from_deserializer<'de, D: Deserializer<'de>>(
+        &mut self,
+        deserializer: D,
+    ) -> Result<()> {
+        self.push(&Transcoder::new(deserializer))
+    }
+
+    /// Append the records of another builder constructed from the same schema
+    pub fn append(&mut self, other: OuterSequenceBuilder) -> Result<()> {
+        self.0.append(other.0)
+    }
+
+    /// Serialize the items in chunks on the rayon thread pool and concatenate
+    /// the resulting builders in order
+    #[cfg(feature = "rayon")]
+    pub fn extend_parallel<T: Serialize + Sync>(&mut self, items: &[T]) -> Result<()> {
+        use rayon::prelude::*;
+
+        let num_threads = rayon::current_num_threads().max(1);
+        let chunk_size = items.len().div_ceil(num_threads).max(1);
+
+        let empty = self.empty();
+        let builders = items
+            .par_chunks(chunk_size)
+            .map(|chunk| {
+                let mut builder = empty.clone();
+                builder.extend(chunk)?;
+                Ok(builder)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for builder in builders {
+            self.append(builder)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "rayon")]
+    fn empty(&self) -> Self {
+        Self(self.0.empty())
+    }
+
+    /// Keep the dictionaries of dictionary encoded fields between batches
+    pub fn set_persistent_dictionaries(&mut self, value: bool) {
+        for (name, builder) in &mut self.0.named_fields {
+            visit_dictionaries(&format!("$.{name}"), builder, &mut |_, builder| {
+                builder.persistent = value;
+            });
+        }
+    }
+
+    /// Set the policy for numeric conversions of all numeric fields
+    pub fn set_numeric_conversion(&mut self, value: NumericConversion) {
+        for (name, builder) in &mut self.0.named_fields {
+            visit_builders(&format!("$.{name}"), builder, &mut |_, builder| {
+                use ArrayBuilder as A;
+                match builder {
+                    A::I8(builder) => builder.conversion = value,
+                    A::I16(builder) => builder.conversion = value,
+                    A::I32(builder) => builder.conversion = value,
+                    A::I64(builder) => builder.conversion = value,
+                    A::U8(builder) => builder.conversion = value,
+                    A::U16(builder) => builder.conversion = value,
+                    A::U32(builder) => builder.conversion = value,
+                    A::U64(builder) => builder.conversion = value,
+                    A::F16(builder) => builder.conversion = value,
+                    A::F32(builder) => builder.conversion = value,
+                    A::F64(builder) => builder.conversion = value,
+                    A::Decimal128(builder) => builder.conversion = value,
+                    _ => {}
+                }
+            });
+        }
+    }
+
+    /// The dictionary values added in the last batch for each dictionary
+    /// encoded field
+    pub fn dictionary_deltas(&self) -> Vec<DictionaryDelta> {
+        let mut deltas = Vec::new();
+        for (name, builder) in &self.0.named_fields {
+            collect_dictionary_deltas(&format!("$.{name}"), builder, &mut deltas);
+        }
+        deltas
+    }
+}
+
+/// Serialize a struct or map of sequences into the fields of a struct builder
+struct ColumnsSerializer<'a> {
+    builder: &'a mut StructBuilder,
+    /// The field index and the number of rows of each serialized column
+    lengths: Vec<(usize, usize)>,
+    next: Option<usize>,
+}
+
+impl<'a> ColumnsSerializer<'a> {
+    fn column<V: Serialize + ?Sized>(&mut self, idx: usize, value: &V) -> Result<()> {
+        let mut column = ColumnSerializer {
+            builder: &mut self.builder.named_fields[idx].1,
+            len: 0,
+        };
+        value.serialize(Mut(&mut column))?;
+        self.lengths.push((idx, column.len));
+        Ok(())
+    }
+}
+
+impl<'a> SimpleSerializer for ColumnsSerializer<'a> {
+    fn name(&self) -> &str {
+        "ColumnsSerializer"
+    }
+
+    fn serialize_struct_start(&mut self, _: &'static str, _: usize) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_struct_field<V: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &V,
+    ) -> Result<()> {
+        match self.builder.index.get(key) {
+            Some(&idx) => self.column(idx, value),
+            None => fail!("Unknown column {key}"),
+        }
+    }
+
+    fn serialize_struct_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_map_start(&mut self, _: Option<usize>) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_map_key<V: Serialize + ?Sized>(&mut self, key: &V) -> Result<()> {
+        let Some(idx) = KeyLookupSerializer::lookup(&self.builder.index, key)? else {
+            fail!("Unknown column");
+        };
+        self.next = Some(idx);
+        Ok(())
+    }
+
+    fn serialize_map_value<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<()> {
+        let Some(idx) = self.next.take() else {
+            fail!("Column values must be preceded by their name");
+        };
+        self.column(idx, value)
+    }
+
+    fn serialize_map_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Serialize the elements of a sequence into a single builder
+struct ColumnSerializer<'a> {
+    builder: &'a mut ArrayBuilder,
+    len: usize,
+}
+
+impl<'a> ColumnSerializer<'a> {
+    fn element<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<()> {
+        self.builder.serialize_value(value)?;
+        self.len += 1;
+        Ok(())
+    }
+}
+
+impl<'a> SimpleSerializer for ColumnSerializer<'a> {
+    fn name(&self) -> &str {
+        "ColumnSerializer"
+    }
+
+    fn serialize_seq_start(&mut self, _: Option<usize>) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_seq_element<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<()> {
+        self.element(value)
+    }
+
+    fn serialize_seq_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_tuple_start(&mut self, _: usize) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_tuple_element<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<()> {
+        self.element(value)
+    }
+
+    fn serialize_tuple_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The dictionary values added to a dictionary encoded field in a batch
+///
+/// If dictionaries are persisted, the dictionary of each batch contains all
+/// values of the previous batches and `new_values` are the values to send as a
+/// delta dictionary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictionaryDelta {
+    /// The path of the field as used in schema tracing, e.g.,
+    /// `"$.outer.inner"` or `"$.tags.item"` for the items of a list
+    pub path: String,
+    /// The positions of the new values in the dictionary
+    pub new_values: Range<usize>,
+}
+
+/// The nested builders with their paths
+///
+/// The paths follow the convention of schema tracing: list items are named
+/// `item`, map keys and values `key` and `value`.
+fn children<'a>(path: &str, builder: &'a ArrayBuilder) -> Vec<(String, &'a ArrayBuilder)> {
+    match builder {
+        ArrayBuilder::List(builder) => vec![(format!("{path}.item"), builder.element.as_ref())],
+        ArrayBuilder::LargeList(builder) => {
+            vec![(format!("{path}.item"), builder.element.as_ref())]
+        }
+        ArrayBuilder::Map(builder) => match builder.entry.as_ref() {
+            ArrayBuilder::Struct(entry) => std::iter::zip(["key", "value"], &entry.named_fields)
+                .map(|(name, (_, child))| (format!("{path}.{name}"), child))
+                .collect(),
+            _ => Vec::new(),
+        },
+        ArrayBuilder::Struct(builder) => builder
+            .named_fields
+            .iter()
+            .map(|(name, child)| (format!("{path}.{name}"), child))
+            .collect(),
+        ArrayBuilder::Union(builder) => std::iter::zip(&builder.field.children, &builder.fields)
+            .map(|(field, child)| (format!("{path}.{}", field.name), child))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The nested builders with their paths, mutably borrowed, see [`children`]
+fn children_mut<'a>(
+    path: &str,
+    builder: &'a mut ArrayBuilder,
+) -> Vec<(String, &'a mut ArrayBuilder)> {
+    match builder {
+        ArrayBuilder::List(builder) => vec![(format!("{path}.item"), builder.element.as_mut())],
+        ArrayBuilder::LargeList(builder) => {
+            vec![(format!("{path}.item"), builder.element.as_mut())]
+        }
+        ArrayBuilder::Map(builder) => match builder.entry.as_mut() {
+            ArrayBuilder::Struct(entry) => {
+                std::iter::zip(["key", "value"], &mut entry.named_fields)
+                    .map(|(name, (_, child))| (format!("{path}.{name}"), child))
+                    .collect()
+            }
+            _ => Vec::new(),
+        },
+        ArrayBuilder::Struct(builder) => builder
+            .named_fields
+            .iter_mut()
+            .map(|(name, child)| (format!("{path}.{name}"), child))
+            .collect(),
+        ArrayBuilder::Union(builder) => {
+            std::iter::zip(&builder.field.children, &mut builder.fields)
+                .map(|(field, child)| (format!("{path}.{}", field.name), child))
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Call `func` for the builder and all its nested builders
+fn visit_builders<F: FnMut(&str, &mut ArrayBuilder)>(
+    path: &str,
+    builder: &mut ArrayBuilder,
+    func: &mut F,
+) {
+    func(path, builder);
+    for (child_path, child) in children_mut(path, builder) {
+        visit_builders(&child_path, child, func);
+    }
+}
+
+fn visit_dictionaries<F: FnMut(&str, &mut DictionaryUtf8Builder)>(
+    path: &str,
+    builder: &mut ArrayBuilder,
+    func: &mut F,
+) {
+    visit_builders(path, builder, &mut |path, builder| {
+        if let ArrayBuilder::DictionaryUtf8(builder) = builder {
+            func(path, builder);
+        }
+    });
+}
+
+/// Collect the dictionary deltas of the builder and all its nested builders
+fn collect_dictionary_deltas(
+    path: &str,
+    builder: &ArrayBuilder,
+    deltas: &mut Vec<DictionaryDelta>,
+) {
+    if let ArrayBuilder::DictionaryUtf8(builder) = builder {
+        deltas.push(DictionaryDelta {
+            path: path.to_owned(),
+            new_values: builder.last_delta.clone(),
+        });
+    }
+    for (child_path, child) in children(path, builder) {
+        collect_dictionary_deltas(&child_path, child, deltas);
+    }
 }
 
 impl OuterSequenceBuilder {
@@ -9,7 +9,7 @@ use crate::{
 
 use super::{
     array_builder::ArrayBuilder,
-    utils::{push_validity, push_validity_default, Mut, SimpleSerializer},
+    utils::{append_validity, push_validity, push_validity_default, Mut, SimpleSerializer},
 };
 
 const UNKNOWN_KEY: usize = usize::MAX;
@@ -19,7 +19,7 @@ pub struct StructBuilder {
     pub fields: Vec<GenericField>,
     pub validity: Option<MutableBitBuffer>,
     pub named_fields: Vec<(String, ArrayBuilder)>,
-    pub cached_names: Vec<Option<(*const u8, usize)>>,
+    pub cached_names: Vec<Option<(usize, usize)>>,
     pub seen: Vec<bool>,
     pub next: usize,
     pub index: BTreeMap<String, usize>,
@@ -77,9 +77,43 @@ impl StructBuilder {
         }
     }
 
+    pub fn empty(&self) -> Self {
+        Self {
+            fields: self.fields.clone(),
+            validity: self.validity.as_ref().map(|_| Default::default()),
+            named_fields: self
+                .named_fields
+                .iter()
+                .map(|(name, builder)| (name.clone(), builder.empty()))
+                .collect(),
+            cached_names: vec![None; self.named_fields.len()],
+            seen: vec![false; self.named_fields.len()],
+            next: 0,
+            index: self.index.clone(),
+        }
+    }
+
     pub fn is_nullable(&self) -> bool {
         self.validity.is_some()
     }
+
+    pub fn append(&mut self, other: Self) -> Result<()> {
+        if self.named_fields.len() != other.named_fields.len() {
+            fail!("cannot append struct builders with different fields");
+        }
+        append_validity(&mut self.validity, other.validity)?;
+        for ((name, builder), (other_name, other_builder)) in
+            std::iter::zip(&mut self.named_fields, other.named_fields)
+        {
+            if *name != other_name {
+                fail!(
+                    "cannot append struct builders with different fields: {name} != {other_name}"
+                );
+            }
+            builder.append(other_builder)?;
+        }
+        Ok(())
+    }
 }
 
 impl StructBuilder {
@@ -115,7 +149,7 @@ impl StructBuilder {
             fail!("Duplicate field {key}", key = self.named_fields[idx].0);
         }
 
-        value.serialize(Mut(&mut self.named_fields[idx].1))?;
+        self.named_fields[idx].1.serialize_value(value)?;
         self.seen[idx] = true;
         self.next = idx + 1;
         Ok(())
@@ -155,7 +189,7 @@ impl SimpleSerializer for StructBuilder {
         key: &'static str,
         value: &T,
     ) -> Result<()> {
-        let fast_key = (key.as_ptr(), key.len());
+        let fast_key = (key.as_ptr() as usize, key.len());
         let idx = if self.cached_names.get(self.next) == Some(&Some(fast_key)) {
             self.next
         } else {
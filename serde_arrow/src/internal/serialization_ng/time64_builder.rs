@@ -0,0 +1,80 @@
+use crate::{
+    internal::{common::MutableBitBuffer, schema::GenericTimeUnit, timestamp},
+    Result,
+};
+
+use super::utils::{append_validity, push_validity, push_validity_default, SimpleSerializer};
+
+#[derive(Debug, Clone)]
+pub struct Time64Builder {
+    pub unit: GenericTimeUnit,
+    pub validity: Option<MutableBitBuffer>,
+    pub buffer: Vec<i64>,
+}
+
+impl Time64Builder {
+    pub fn new(unit: GenericTimeUnit, nullable: bool) -> Self {
+        Self {
+            unit,
+            validity: nullable.then(MutableBitBuffer::default),
+            buffer: Vec::new(),
+        }
+    }
+
+    pub fn take(&mut self) -> Self {
+        Self {
+            unit: self.unit,
+            validity: self.validity.as_mut().map(std::mem::take),
+            buffer: std::mem::take(&mut self.buffer),
+        }
+    }
+
+    pub fn empty(&self) -> Self {
+        Self {
+            unit: self.unit,
+            validity: self.validity.as_ref().map(|_| Default::default()),
+            buffer: Default::default(),
+        }
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        self.validity.is_some()
+    }
+
+    pub fn append(&mut self, other: Self) -> Result<()> {
+        append_validity(&mut self.validity, other.validity)?;
+        self.buffer.extend(other.buffer);
+        Ok(())
+    }
+}
+
+impl SimpleSerializer for Time64Builder {
+    fn name(&self) -> &str {
+        "Time64Builder"
+    }
+
+    fn serialize_default(&mut self) -> Result<()> {
+        push_validity_default(&mut self.validity);
+        self.buffer.push(0);
+        Ok(())
+    }
+
+    fn serialize_none(&mut self) -> Result<()> {
+        push_validity(&mut self.validity, false)?;
+        self.buffer.push(0);
+        Ok(())
+    }
+
+    fn serialize_str(&mut self, v: &str) -> Result<()> {
+        let value = timestamp::parse_time64(v, self.unit)?;
+        push_validity(&mut self.validity, true)?;
+        self.buffer.push(value);
+        Ok(())
+    }
+
+    fn serialize_i64(&mut self, v: i64) -> Result<()> {
+        push_validity(&mut self.validity, true)?;
+        self.buffer.push(v);
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+//! Serialize the data of a deserializer without an intermediate value
+//!
+//! The [`Transcoder`] wraps a self-describing deserializer (e.g., JSON) and
+//! implements `Serialize` by forwarding each value reported by the
+//! deserializer to the serializer.
+use std::cell::RefCell;
+
+use serde::{
+    de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor},
+    ser::{self, Serialize, SerializeMap, SerializeSeq, Serializer},
+};
+
+pub struct Transcoder<D>(RefCell<Option<D>>);
+
+impl<D> Transcoder<D> {
+    pub fn new(deserializer: D) -> Self {
+        Self(RefCell::new(Some(deserializer)))
+    }
+}
+
+impl<'de, D: Deserializer<'de>> Serialize for Transcoder<D> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Some(deserializer) = self.0.borrow_mut().take() else {
+            return Err(ser::Error::custom("Transcoder can only be serialized once"));
+        };
+        deserializer
+            .deserialize_any(TranscodeVisitor(serializer))
+            .map_err(ser::Error::custom)
+    }
+}
+
+struct TranscodeVisitor<S>(S);
+
+impl<'de, S: Serializer> Visitor<'de> for TranscodeVisitor<S> {
+    type Value = S::Ok;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "any value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<S::Ok, E> {
+        self.0.serialize_bool(v).map_err(de::Error::custom)
+    }
+
+    fn visit_i8<E: de::Error>(self, v: i8) -> Result<S::Ok, E> {
+        self.0.serialize_i8(v).map_err(de::Error::custom)
+    }
+
+    fn visit_i16<E: de::Error>(self, v: i16) -> Result<S::Ok, E> {
+        self.0.serialize_i16(v).map_err(de::Error::custom)
+    }
+
+    fn visit_i32<E: de::Error>(self, v: i32) -> Result<S::Ok, E> {
+        self.0.serialize_i32(v).map_err(de::Error::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<S::Ok, E> {
+        self.0.serialize_i64(v).map_err(de::Error::custom)
+    }
+
+    fn visit_u8<E: de::Error>(self, v: u8) -> Result<S::Ok, E> {
+        self.0.serialize_u8(v).map_err(de::Error::custom)
+    }
+
+    fn visit_u16<E: de::Error>(self, v: u16) -> Result<S::Ok, E> {
+        self.0.serialize_u16(v).map_err(de::Error::custom)
+    }
+
+    fn visit_u32<E: de::Error>(self, v: u32) -> Result<S::Ok, E> {
+        self.0.serialize_u32(v).map_err(de::Error::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<S::Ok, E> {
+        self.0.serialize_u64(v).map_err(de::Error::custom)
+    }
+
+    fn visit_f32<E: de::Error>(self, v: f32) -> Result<S::Ok, E> {
+        self.0.serialize_f32(v).map_err(de::Error::custom)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<S::Ok, E> {
+        self.0.serialize_f64(v).map_err(de::Error::custom)
+    }
+
+    fn visit_char<E: de::Error>(self, v: char) -> Result<S::Ok, E> {
+        self.0.serialize_char(v).map_err(de::Error::custom)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<S::Ok, E> {
+        self.0.serialize_str(v).map_err(de::Error::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<S::Ok, E> {
+        self.0.serialize_bytes(v).map_err(de::Error::custom)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<S::Ok, E> {
+        self.0.serialize_unit().map_err(de::Error::custom)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<S::Ok, E> {
+        self.0.serialize_none().map_err(de::Error::custom)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<S::Ok, D::Error> {
+        self.0
+            .serialize_some(&Transcoder::new(deserializer))
+            .map_err(de::Error::custom)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<S::Ok, D::Error> {
+        Transcoder::new(deserializer)
+            .serialize(self.0)
+            .map_err(de::Error::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<S::Ok, A::Error> {
+        let mut target = self
+            .0
+            .serialize_seq(seq.size_hint())
+            .map_err(de::Error::custom)?;
+        while let Some(()) = seq.next_element_seed(SeqElementSeed(&mut target))? {}
+        target.end().map_err(de::Error::custom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<S::Ok, A::Error> {
+        let mut target = self
+            .0
+            .serialize_map(map.size_hint())
+            .map_err(de::Error::custom)?;
+        while let Some(()) = map.next_key_seed(MapKeySeed(&mut target))? {
+            map.next_value_seed(MapValueSeed(&mut target))?;
+        }
+        target.end().map_err(de::Error::custom)
+    }
+}
+
+struct SeqElementSeed<'a, S>(&'a mut S);
+
+impl<'a, 'de, S: SerializeSeq> DeserializeSeed<'de> for SeqElementSeed<'a, S> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        self.0
+            .serialize_element(&Transcoder::new(deserializer))
+            .map_err(de::Error::custom)
+    }
+}
+
+struct MapKeySeed<'a, S>(&'a mut S);
+
+impl<'a, 'de, S: SerializeMap> DeserializeSeed<'de> for MapKeySeed<'a, S> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        self.0
+            .serialize_key(&Transcoder::new(deserializer))
+            .map_err(de::Error::custom)
+    }
+}
+
+struct MapValueSeed<'a, S>(&'a mut S);
+
+impl<'a, 'de, S: SerializeMap> DeserializeSeed<'de> for MapValueSeed<'a, S> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        self.0
+            .serialize_value(&Transcoder::new(deserializer))
+            .map_err(de::Error::custom)
+    }
+}
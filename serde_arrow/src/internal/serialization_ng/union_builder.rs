@@ -1,12 +1,12 @@
 use crate::{
-    internal::{error::fail, schema::GenericField},
+    internal::{
+        error::fail,
+        schema::{GenericDataType, GenericField, Strategy},
+    },
     Result,
 };
 
-use super::{
-    utils::{Mut, SimpleSerializer},
-    ArrayBuilder,
-};
+use super::{utils::SimpleSerializer, ArrayBuilder};
 
 #[derive(Debug, Clone)]
 pub struct UnionBuilder {
@@ -15,11 +15,17 @@ pub struct UnionBuilder {
     pub types: Vec<i8>,
     pub offsets: Vec<i32>,
     pub current_offset: Vec<i32>,
+    /// If `true`, values are not wrapped in enum variants, but the variant is
+    /// selected by the type of the value, as used by the
+    /// [`InconsistentTypes`][crate::internal::schema::Strategy::InconsistentTypes]
+    /// strategy
+    pub untagged: bool,
 }
 
 impl UnionBuilder {
     pub fn new(field: GenericField, fields: Vec<ArrayBuilder>) -> Result<Self> {
         Ok(Self {
+            untagged: matches!(field.strategy, Some(Strategy::InconsistentTypes)),
             field,
             current_offset: vec![0; fields.len()],
             types: Vec::new(),
@@ -35,11 +41,44 @@ impl UnionBuilder {
             types: std::mem::take(&mut self.types),
             offsets: std::mem::take(&mut self.offsets),
             current_offset: std::mem::replace(&mut self.current_offset, vec![0; self.fields.len()]),
+            untagged: self.untagged,
+        }
+    }
+
+    pub fn empty(&self) -> Self {
+        Self {
+            field: self.field.clone(),
+            fields: self.fields.iter().map(|field| field.empty()).collect(),
+            types: Default::default(),
+            offsets: Default::default(),
+            current_offset: vec![0; self.fields.len()],
+            untagged: self.untagged,
         }
     }
 
     pub fn is_nullable(&self) -> bool {
-        false
+        self.untagged
+            && self
+                .find_untagged_variant(&[GenericDataType::Null])
+                .is_some()
+    }
+
+    pub fn append(&mut self, other: Self) -> Result<()> {
+        if self.fields.len() != other.fields.len() {
+            fail!("cannot append union builders with different variants");
+        }
+        for (&type_id, &offset) in std::iter::zip(&other.types, &other.offsets) {
+            self.offsets
+                .push(self.current_offset[type_id as usize] + offset);
+        }
+        self.types.extend(other.types);
+        for (idx, (builder, other_builder)) in
+            std::iter::zip(&mut self.fields, other.fields).enumerate()
+        {
+            self.current_offset[idx] += other.current_offset[idx];
+            builder.append(other_builder)?;
+        }
+        Ok(())
     }
 }
 
@@ -56,6 +95,45 @@ impl UnionBuilder {
 
         Ok(variant_builder)
     }
+
+    /// Find the first variant with one of the given data types, earlier data
+    /// types take precedence
+    fn find_untagged_variant(&self, data_types: &[GenericDataType]) -> Option<usize> {
+        data_types.iter().find_map(|data_type| {
+            self.field
+                .children
+                .iter()
+                .position(|child| &child.data_type == data_type)
+        })
+    }
+
+    fn serialize_untagged(
+        &mut self,
+        method: &str,
+        data_types: &[GenericDataType],
+    ) -> Result<&mut ArrayBuilder> {
+        if !self.untagged {
+            fail!("{method} is not implemented for {}", self.name());
+        }
+        let Some(variant_index) = self.find_untagged_variant(data_types) else {
+            fail!(
+                "{method} is not supported for {name}: no variant of type {data_types:?}",
+                name = self.name(),
+            );
+        };
+        self.serialize_variant(u32::try_from(variant_index)?)
+    }
+}
+
+macro_rules! serialize_untagged {
+    ($($name:ident($ty:ty) => [$($data_type:ident),*]),* $(,)?) => {
+        $(
+            fn $name(&mut self, v: $ty) -> Result<()> {
+                self.serialize_untagged(stringify!($name), &[$(GenericDataType::$data_type),*])?
+                    .$name(v)
+            }
+        )*
+    };
 }
 
 impl SimpleSerializer for UnionBuilder {
@@ -63,6 +141,27 @@ impl SimpleSerializer for UnionBuilder {
         "UnionBuilder"
     }
 
+    fn serialize_none(&mut self) -> Result<()> {
+        self.serialize_untagged("serialize_none", &[GenericDataType::Null])?
+            .serialize_none()
+    }
+
+    // numbers fall back to the wider types used when numbers are coerced
+    serialize_untagged!(
+        serialize_bool(bool) => [Bool],
+        serialize_i8(i8) => [I8, I64, F64],
+        serialize_i16(i16) => [I16, I64, F64],
+        serialize_i32(i32) => [I32, I64, F64],
+        serialize_i64(i64) => [I64, F64],
+        serialize_u8(u8) => [U8, U64, I64, F64],
+        serialize_u16(u16) => [U16, U64, I64, F64],
+        serialize_u32(u32) => [U32, U64, I64, F64],
+        serialize_u64(u64) => [U64, I64, F64],
+        serialize_f32(f32) => [F32, F64],
+        serialize_f64(f64) => [F64],
+        serialize_str(&str) => [LargeUtf8, Utf8],
+    );
+
     fn serialize_unit_variant(
         &mut self,
         _: &'static str,
@@ -80,7 +179,7 @@ impl SimpleSerializer for UnionBuilder {
         value: &V,
     ) -> Result<()> {
         let variant_builder = self.serialize_variant(variant_index)?;
-        value.serialize(Mut(variant_builder))
+        variant_builder.serialize_value(value)
     }
 
     fn serialize_struct_variant_start<'this>(
@@ -12,9 +12,17 @@ impl UnknownVariantBuilder {
         UnknownVariantBuilder
     }
 
+    pub fn empty(&self) -> Self {
+        UnknownVariantBuilder
+    }
+
     pub fn is_nullable(&self) -> bool {
         false
     }
+
+    pub fn append(&mut self, _: Self) -> Result<()> {
+        Ok(())
+    }
 }
 
 impl SimpleSerializer for UnknownVariantBuilder {
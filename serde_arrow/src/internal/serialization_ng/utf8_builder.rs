@@ -1,20 +1,34 @@
+use serde::Serialize;
+
 use crate::{
-    internal::common::{MutableBitBuffer, MutableOffsetBuffer, Offset},
+    internal::{
+        common::{string_formats::matches_uuid, MutableBitBuffer, MutableOffsetBuffer, Offset},
+        error::fail,
+        json,
+        schema::Strategy,
+    },
     Result,
 };
 
-use super::utils::{push_validity, push_validity_default, SimpleSerializer};
+use super::utils::{append_validity, push_validity, push_validity_default, Mut, SimpleSerializer};
 
 #[derive(Debug, Clone)]
 pub struct Utf8Builder<O> {
+    /// The strategy of the field: with
+    /// [`InconsistentTypes`][Strategy::InconsistentTypes] booleans and numbers
+    /// are formatted as strings, with [`UuidStr`][Strategy::UuidStr] strings
+    /// are checked to be UUIDs, with [`JsonAsUtf8`][Strategy::JsonAsUtf8]
+    /// arbitrary values are stored as their JSON representation
+    pub strategy: Option<Strategy>,
     pub validity: Option<MutableBitBuffer>,
     pub offsets: MutableOffsetBuffer<O>,
     pub buffer: Vec<u8>,
 }
 
 impl<O: Offset> Utf8Builder<O> {
-    pub fn new(is_nullable: bool) -> Self {
+    pub fn new(strategy: Option<Strategy>, is_nullable: bool) -> Self {
         Self {
+            strategy,
             validity: is_nullable.then(MutableBitBuffer::default),
             offsets: MutableOffsetBuffer::default(),
             buffer: Vec::new(),
@@ -23,15 +37,86 @@ impl<O: Offset> Utf8Builder<O> {
 
     pub fn take(&mut self) -> Self {
         Self {
+            strategy: self.strategy.clone(),
             validity: self.validity.as_mut().map(std::mem::take),
             offsets: std::mem::take(&mut self.offsets),
             buffer: std::mem::take(&mut self.buffer),
         }
     }
 
+    pub fn empty(&self) -> Self {
+        Self {
+            strategy: self.strategy.clone(),
+            validity: self.validity.as_ref().map(|_| Default::default()),
+            offsets: Default::default(),
+            buffer: Default::default(),
+        }
+    }
+
     pub fn is_nullable(&self) -> bool {
         self.validity.is_some()
     }
+
+    pub fn append(&mut self, other: Self) -> Result<()> {
+        append_validity(&mut self.validity, other.validity)?;
+        self.offsets.append(other.offsets)?;
+        self.buffer.extend(other.buffer);
+        Ok(())
+    }
+
+    pub fn is_json(&self) -> bool {
+        matches!(self.strategy, Some(Strategy::JsonAsUtf8))
+    }
+
+    /// Store the JSON representation of the value
+    ///
+    /// For nullable fields, `null` is stored as a missing value.
+    pub fn serialize_json<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<()> {
+        let start = self.buffer.len();
+        if let Err(err) = json::write_json(&mut self.buffer, value) {
+            // do not keep partially written values
+            self.buffer.truncate(start);
+            return Err(err);
+        }
+        if self.is_nullable() && self.buffer[start..] == *b"null" {
+            self.buffer.truncate(start);
+            return self.serialize_none();
+        }
+        push_validity(&mut self.validity, true)?;
+        self.offsets.push(self.buffer.len() - start)?;
+        Ok(())
+    }
+
+    fn push_str(&mut self, v: &str) -> Result<()> {
+        push_validity(&mut self.validity, true)?;
+        self.offsets.push(v.len())?;
+        self.buffer.extend(v.as_bytes());
+        Ok(())
+    }
+
+    fn serialize_formatted<V: std::fmt::Display + Serialize>(
+        &mut self,
+        method: &str,
+        v: V,
+    ) -> Result<()> {
+        if self.is_json() {
+            return self.serialize_json(&v);
+        }
+        if !matches!(self.strategy, Some(Strategy::InconsistentTypes)) {
+            fail!("{method} is not implemented for {}", self.name());
+        }
+        self.push_str(&v.to_string())
+    }
+}
+
+macro_rules! serialize_formatted {
+    ($($name:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $name(&mut self, v: $ty) -> Result<()> {
+                self.serialize_formatted(stringify!($name), v)
+            }
+        )*
+    };
 }
 
 impl<O: Offset> SimpleSerializer for Utf8Builder<O> {
@@ -51,11 +136,35 @@ impl<O: Offset> SimpleSerializer for Utf8Builder<O> {
         Ok(())
     }
 
-    fn serialize_str(&mut self, v: &str) -> Result<()> {
-        push_validity(&mut self.validity, true)?;
-        self.offsets.push(v.len())?;
-        self.buffer.extend(v.as_bytes());
+    fn serialize_some<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<()> {
+        if self.is_json() {
+            return self.serialize_json(value);
+        }
+        value.serialize(Mut(self))
+    }
 
-        Ok(())
+    fn serialize_str(&mut self, v: &str) -> Result<()> {
+        match self.strategy {
+            Some(Strategy::JsonAsUtf8) => self.serialize_json(v),
+            Some(Strategy::UuidStr) if !matches_uuid(v) => {
+                fail!("Cannot serialize {v:?} into {}: not a UUID", self.name())
+            }
+            _ => self.push_str(v),
+        }
     }
+
+    serialize_formatted!(
+        serialize_bool(bool),
+        serialize_char(char),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+    );
 }
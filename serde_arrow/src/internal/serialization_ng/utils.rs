@@ -30,6 +30,33 @@ pub fn push_validity_default(buffer: &mut Option<MutableBitBuffer>) {
     }
 }
 
+pub fn append_validity(
+    buffer: &mut Option<MutableBitBuffer>,
+    other: Option<MutableBitBuffer>,
+) -> Result<()> {
+    match (buffer.as_mut(), other) {
+        (Some(buffer), Some(other)) => {
+            buffer.append(&other);
+            Ok(())
+        }
+        (None, None) => Ok(()),
+        _ => fail!("cannot append builders with different nullability"),
+    }
+}
+
+/// Parse a string into a value of type `T`, as used by the
+/// [`StrAsPrimitive`][crate::internal::schema::Strategy::StrAsPrimitive]
+/// strategy
+pub fn parse_str<T>(s: &str, target: &str) -> Result<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    s.parse::<T>().map_err(|err| {
+        Error::custom_from(format!("Cannot parse string {s:?} as {target}: {err}"), err)
+    })
+}
+
 /// A simplified serialization trait with default implementations raising an
 /// error
 ///
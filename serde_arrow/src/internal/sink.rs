@@ -101,6 +101,10 @@ pub trait EventSink {
     fn accept_u16(&mut self, val: u16) -> Result<()>;
     fn accept_u32(&mut self, val: u32) -> Result<()>;
     fn accept_u64(&mut self, val: u64) -> Result<()>;
+    /// Accept a char, per default it is passed on as its code point
+    fn accept_char(&mut self, val: char) -> Result<()> {
+        self.accept_u32(u32::from(val))
+    }
     fn accept_f32(&mut self, val: f32) -> Result<()>;
     fn accept_f64(&mut self, val: f64) -> Result<()>;
     fn accept(&mut self, event: Event<'_>) -> Result<()>;
@@ -179,7 +183,7 @@ impl<'a, S: EventSink> Serializer for EventSerializer<'a, S> {
     }
 
     fn serialize_char(self, val: char) -> Result<()> {
-        self.0.accept_u32(u32::from(val))
+        self.0.accept_char(val)
     }
 
     fn serialize_str(self, val: &str) -> Result<()> {
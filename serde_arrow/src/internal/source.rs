@@ -1,29 +1,24 @@
 use std::borrow::Cow;
 
 use serde::de::{
-    self, Deserialize, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor,
+    self, value::MapDeserializer, Deserialize, DeserializeSeed, EnumAccess, IntoDeserializer,
+    MapAccess, SeqAccess, VariantAccess, Visitor,
 };
 
 use crate::internal::{
     error::{error, fail, Error, Result},
     event::Event,
+    json,
 };
 
 /// Deserialize a type from an [EventSource]
 ///
 /// This function may be helpful when creating custom formats.
 ///
-pub fn deserialize_from_source<
-    'de,
-    'event,
-    T: Deserialize<'de>,
-    S: IntoEventSource<'event> + 'event,
->(
+pub fn deserialize_from_source<'de, T: Deserialize<'de>, S: IntoEventSource<'de>>(
     source: S,
 ) -> Result<T> {
-    let mut deserializer = Deserializer {
-        source: PeekableEventSource::new(source.into_event_source()),
-    };
+    let mut deserializer = Deserializer::new(source);
     let res = T::deserialize(&mut deserializer)?;
 
     if deserializer.source.next()?.is_some() {
@@ -35,21 +30,22 @@ pub fn deserialize_from_source<
 
 /// A source of [Events][Event] that can be used to deserialize rust objects
 ///
-/// **Note**: implementations are not expected to yield `Some` events for
-/// nullable values, as the nullability can be recovered from the underlying
-/// arrays. This relaxed relaxed requirements implies that schema tracing from
-/// the Array sources may be unreliable.
+/// **Note**: implementations are not required to yield `Some` events for
+/// nullable values. The deserializer accepts values with and without `Some`
+/// markers, also for self-describing types, such as `serde_json::Value`.
+/// However, schema tracing from sources without `Some` markers may be
+/// unreliable.
 ///
 pub trait EventSource<'a> {
     fn next(&mut self) -> Result<Option<Event<'a>>>;
 }
 
-pub struct PeekableEventSource<'a, S: EventSource<'a> + 'a> {
+pub struct PeekableEventSource<'a, S: EventSource<'a>> {
     source: S,
     peeked: Option<Option<Event<'a>>>,
 }
 
-impl<'a, S: EventSource<'a> + 'a> PeekableEventSource<'a, S> {
+impl<'a, S: EventSource<'a>> PeekableEventSource<'a, S> {
     pub fn new(source: S) -> Self {
         Self {
             source,
@@ -68,7 +64,7 @@ impl<'a, S: EventSource<'a> + 'a> PeekableEventSource<'a, S> {
     }
 }
 
-impl<'a, S: EventSource<'a> + 'a> EventSource<'a> for PeekableEventSource<'a, S> {
+impl<'a, S: EventSource<'a>> EventSource<'a> for PeekableEventSource<'a, S> {
     fn next(&mut self) -> Result<Option<Event<'a>>> {
         if let Some(peeked) = self.peeked.take() {
             Ok(peeked)
@@ -96,12 +92,180 @@ pub struct Deserializer<'event, S: EventSource<'event>> {
     source: PeekableEventSource<'event, S>,
 }
 
-impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
-    for &'a mut Deserializer<'event, S>
-{
+impl<'de, S: EventSource<'de>> Deserializer<'de, S> {
+    pub(crate) fn new<I: IntoEventSource<'de, EventSource = S>>(source: I) -> Self {
+        Self {
+            source: PeekableEventSource::new(source.into_event_source()),
+        }
+    }
+}
+
+/// The key used by `serde_json` to represent numbers with arbitrary precision
+const ARBITRARY_PRECISION_TOKEN: &str = "$serde_json::private::Number";
+
+impl<'de, S: EventSource<'de>> Deserializer<'de, S> {
+    /// Deserialize a decimal as a tuple of its unscaled value and its scale
+    fn deserialize_decimal_parts<V: Visitor<'de>>(&mut self, visitor: V) -> Result<V::Value> {
+        let Some(Event::Decimal128(val, scale)) = self.source.next()? else {
+            fail!("Expected a decimal");
+        };
+        let res = visitor.visit_seq(DecimalParts {
+            val,
+            scale,
+            next: 0,
+        })?;
+        Ok(res)
+    }
+
+    /// Deserialize a timestamp as a tuple of its components
+    ///
+    /// This representation is used by the `time` crate: `OffsetDateTime`
+    /// requests 9 components, `PrimitiveDateTime` 6.
+    fn deserialize_timestamp_parts<V: Visitor<'de>>(
+        &mut self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        let Some(Event::Timestamp(ts)) = self.source.next()? else {
+            fail!("Expected a timestamp");
+        };
+        let parts = match len {
+            9 => ts.to_parts(true)?,
+            6 => ts.to_parts(false)?,
+            _ => fail!("Cannot deserialize a timestamp as a tuple of length {len}"),
+        };
+        visitor.visit_seq(TimestampParts { parts, next: 0 })
+    }
+}
+
+impl<'de, S: EventSource<'de>> Deserializer<'de, S> {
+    /// Take the next event, if it encodes a value as JSON (see [`Event::Json`])
+    fn next_json(&mut self) -> Result<Option<Cow<'de, str>>> {
+        if !matches!(
+            self.source.peek()?,
+            Some(Event::Json(_) | Event::OwnedJson(_))
+        ) {
+            return Ok(None);
+        }
+        match self.source.next()? {
+            Some(Event::Json(s)) => Ok(Some(Cow::Borrowed(s))),
+            Some(Event::OwnedJson(s)) => Ok(Some(Cow::Owned(s))),
+            _ => fail!("Expected a JSON encoded value"),
+        }
+    }
+
+    /// Deserialize the next value, parsing values encoded as JSON
+    fn deserialize_value<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value> {
+        match self.next_json()? {
+            Some(s) => json::deserialize_json(&s, seed),
+            None => seed.deserialize(self),
+        }
+    }
+}
+
+impl<'de, S: EventSource<'de>> Deserializer<'de, S> {
+    /// Deserialize a variant without type information in the externally
+    /// tagged representation of serde, i.e., `{"name": value}`
+    ///
+    /// As the arrays do not distinguish between unit variants and variants
+    /// with null values, variants with null values are deserialized as the
+    /// bare variant name.
+    fn deserialize_any_variant<V: Visitor<'de>>(&mut self, visitor: V) -> Result<V::Value> {
+        let name = match required(self.source.next()?)? {
+            Event::Variant(name, _) => Cow::Borrowed(name),
+            Event::OwnedVariant(name, _) => Cow::Owned(name),
+            ev => fail!("Expected a variant, got {ev}"),
+        };
+
+        if let Some(Event::Null) = self.source.peek()? {
+            self.source.next()?;
+            return match name {
+                Cow::Borrowed(name) => visitor.visit_borrowed_str(name),
+                Cow::Owned(name) => visitor.visit_string(name),
+            };
+        }
+
+        visitor.visit_map(VariantAsMap {
+            deserializer: self,
+            name: Some(name),
+        })
+    }
+}
+
+/// Access a variant as a map with a single entry
+struct VariantAsMap<'a, 'de, S: EventSource<'de>> {
+    deserializer: &'a mut Deserializer<'de, S>,
+    name: Option<Cow<'de, str>>,
+}
+
+impl<'a, 'de, S: EventSource<'de>> MapAccess<'de> for VariantAsMap<'a, 'de, S> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.name.take() {
+            Some(Cow::Borrowed(name)) => seed
+                .deserialize(de::value::BorrowedStrDeserializer::<Error>::new(name))
+                .map(Some),
+            Some(Cow::Owned(name)) => seed
+                .deserialize(IntoDeserializer::<Error>::into_deserializer(name))
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(&mut *self.deserializer)
+    }
+}
+
+/// Access the components of a timestamp as a sequence
+struct TimestampParts {
+    parts: Vec<i64>,
+    next: usize,
+}
+
+impl<'de> SeqAccess<'de> for TimestampParts {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        let Some(&part) = self.parts.get(self.next) else {
+            return Ok(None);
+        };
+        self.next += 1;
+        let res = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(part))?;
+        Ok(Some(res))
+    }
+}
+
+/// Access the unscaled value and the scale of a decimal as a sequence
+struct DecimalParts {
+    val: i128,
+    scale: i8,
+    next: usize,
+}
+
+impl<'de> SeqAccess<'de> for DecimalParts {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        let res = match self.next {
+            0 => seed.deserialize(IntoDeserializer::<Error>::into_deserializer(self.val))?,
+            1 => seed.deserialize(IntoDeserializer::<Error>::into_deserializer(self.scale))?,
+            _ => return Ok(None),
+        };
+        self.next += 1;
+        Ok(Some(res))
+    }
+}
+
+impl<'de, 'a, S: EventSource<'de>> de::Deserializer<'de> for &'a mut Deserializer<'de, S> {
     type Error = Error;
 
     fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if let Some(s) = self.next_json()? {
+            return json::deserialize_json_any(&s, visitor);
+        }
+
         match self.source.peek()? {
             Some(Event::Null) => self.deserialize_unit(visitor),
             Some(Event::Bool(_)) => self.deserialize_bool(visitor),
@@ -117,12 +281,16 @@ impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
             Some(Event::F64(_)) => self.deserialize_f64(visitor),
             Some(Event::Str(_)) => self.deserialize_str(visitor),
             Some(Event::OwnedStr(_)) => self.deserialize_string(visitor),
+            Some(Event::Decimal128(_, _)) => self.deserialize_string(visitor),
+            Some(Event::Timestamp(ts)) if ts.prefer_str => self.deserialize_string(visitor),
+            Some(Event::Timestamp(_)) => self.deserialize_i64(visitor),
             Some(Event::StartStruct) => self.deserialize_struct("", &[], visitor),
             Some(Event::StartMap) => self.deserialize_map(visitor),
             Some(Event::StartSequence) => self.deserialize_seq(visitor),
             Some(Event::StartTuple) => self.deserialize_tuple(0, visitor),
+            Some(Event::Some) => self.deserialize_option(visitor),
             Some(Event::Variant(_, _) | Event::OwnedVariant(_, _)) => {
-                self.deserialize_enum("", &[], visitor)
+                self.deserialize_any_variant(visitor)
             }
             Some(
                 ev @ (Event::EndMap
@@ -131,7 +299,8 @@ impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
                 | Event::EndTuple
                 | Event::Item
                 | Event::Default
-                | Event::Some),
+                | Event::Json(_)
+                | Event::OwnedJson(_)),
             ) => fail!("Invalid event in deserialize_any: Some({ev})"),
             None => fail!("Invalid event in deserialize_any: None"),
         }
@@ -173,6 +342,14 @@ impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
         visitor.visit_u64(required(self.source.next()?)?.try_into()?)
     }
 
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i128(required(self.source.next()?)?.try_into()?)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u128(required(self.source.next()?)?.try_into()?)
+    }
+
     fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
         visitor.visit_f32(required(self.source.next()?)?.try_into()?)
     }
@@ -195,8 +372,10 @@ impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
 
     fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
         match required(self.source.next()?)? {
-            Event::Str(val) => visitor.visit_str(val),
+            Event::Str(val) => visitor.visit_borrowed_str(val),
             Event::OwnedStr(val) => visitor.visit_str(&val),
+            ev @ Event::Decimal128(_, _) => visitor.visit_string(ev.try_into()?),
+            Event::Timestamp(ts) => visitor.visit_string(ts.format()?),
             ev => fail!("Invalid event {}, expected str", ev),
         }
     }
@@ -205,16 +384,28 @@ impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
         match required(self.source.next()?)? {
             Event::Str(val) => visitor.visit_string(val.to_owned()),
             Event::OwnedStr(val) => visitor.visit_string(val),
+            ev @ Event::Decimal128(_, _) => visitor.visit_string(ev.try_into()?),
+            Event::Timestamp(ts) => visitor.visit_string(ts.format()?),
             ev => fail!("Invalid event {}, expected string", ev),
         }
     }
 
-    fn deserialize_bytes<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
-        fail!("deserialize_bytes: Bytes are not supported at the moment")
+    // NOTE: binary arrays are not yet supported, strings are returned as their
+    // UTF-8 bytes (borrowed if possible)
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match required(self.source.next()?)? {
+            Event::Str(val) => visitor.visit_borrowed_bytes(val.as_bytes()),
+            Event::OwnedStr(val) => visitor.visit_bytes(val.as_bytes()),
+            ev => fail!("Invalid event {}, expected bytes", ev),
+        }
     }
 
-    fn deserialize_byte_buf<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
-        fail!("deserialize_byte_buf: Bytes are not supported at the moment")
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match required(self.source.next()?)? {
+            Event::Str(val) => visitor.visit_byte_buf(val.as_bytes().to_vec()),
+            Event::OwnedStr(val) => visitor.visit_byte_buf(val.into_bytes()),
+            ev => fail!("Invalid event {}, expected bytes", ev),
+        }
     }
 
     fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
@@ -226,6 +417,9 @@ impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
             if let Some(Event::Some) = self.source.peek()? {
                 self.source.next()?;
             }
+            if let Some(s) = self.next_json()? {
+                return json::deserialize_json_option(&s, visitor);
+            }
 
             visitor.visit_some(self)
         }
@@ -267,7 +461,12 @@ impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
         Ok(res)
     }
 
-    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        match self.source.peek()? {
+            Some(Event::Decimal128(_, _)) => return self.deserialize_decimal_parts(visitor),
+            Some(Event::Timestamp(_)) => return self.deserialize_timestamp_parts(len, visitor),
+            _ => {}
+        }
         if !matches!(self.source.next()?, Some(Event::StartTuple)) {
             fail!("Expected start of tuple");
         }
@@ -283,9 +482,14 @@ impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
     fn deserialize_tuple_struct<V: Visitor<'de>>(
         self,
         _name: &'static str,
-        _len: usize,
+        len: usize,
         visitor: V,
     ) -> Result<V::Value> {
+        match self.source.peek()? {
+            Some(Event::Decimal128(_, _)) => return self.deserialize_decimal_parts(visitor),
+            Some(Event::Timestamp(_)) => return self.deserialize_timestamp_parts(len, visitor),
+            _ => {}
+        }
         if !matches!(self.source.next()?, Some(Event::StartTuple)) {
             fail!("Expected start of tuple");
         }
@@ -301,6 +505,17 @@ impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
     fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
         match self.source.next()? {
             Some(Event::StartMap) | Some(Event::StartStruct) => {}
+            Some(ev @ Event::Decimal128(_, _)) => {
+                // the representation used by serde_json's arbitrary_precision feature
+                let number: String = ev.try_into()?;
+                let mut map = MapDeserializer::<_, Error>::new(std::iter::once((
+                    ARBITRARY_PRECISION_TOKEN,
+                    number,
+                )));
+                let res = visitor.visit_map(&mut map)?;
+                map.end()?;
+                return Ok(res);
+            }
             Some(ev) => fail!("Expected StartMap, got Some({ev})"),
             None => fail!("Expected StartMap, got None"),
         }
@@ -356,25 +571,28 @@ impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
     }
 }
 
-impl<'de, 'a, 'event, S: EventSource<'event>> SeqAccess<'de> for &'a mut Deserializer<'event, S> {
+impl<'de, 'a, S: EventSource<'de>> SeqAccess<'de> for &'a mut Deserializer<'de, S> {
     type Error = Error;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
     where
         T: DeserializeSeed<'de>,
     {
-        if matches!(self.source.peek()?, Some(Event::EndSequence)) {
+        if matches!(
+            self.source.peek()?,
+            Some(Event::EndSequence | Event::EndTuple)
+        ) {
             return Ok(None);
         }
         // ignore event markers to be forwards compatible
         if matches!(self.source.peek()?, Some(Event::Item)) {
             self.source.next()?;
         }
-        seed.deserialize(&mut **self).map(Some)
+        self.deserialize_value(seed).map(Some)
     }
 }
 
-impl<'de, 'a, 'event, S: EventSource<'event>> MapAccess<'de> for &'a mut Deserializer<'event, S> {
+impl<'de, 'a, S: EventSource<'de>> MapAccess<'de> for &'a mut Deserializer<'de, S> {
     type Error = Error;
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
@@ -391,18 +609,105 @@ impl<'de, 'a, 'event, S: EventSource<'event>> MapAccess<'de> for &'a mut Deseria
             _ => {}
         }
 
-        seed.deserialize(&mut **self).map(Some)
+        seed.deserialize(MapKeyDeserializer(&mut **self)).map(Some)
     }
 
     fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
     where
         V: DeserializeSeed<'de>,
     {
-        seed.deserialize(&mut **self)
+        self.deserialize_value(seed)
+    }
+}
+
+/// Deserialize map keys, formatting primitive keys if strings are requested
+///
+/// This way maps with non-string keys can be deserialized into types that only
+/// support string keys, e.g., `serde_json::Value`. All other requests are
+/// forwarded to the underlying deserializer.
+struct MapKeyDeserializer<'a, 'de, S: EventSource<'de>>(&'a mut Deserializer<'de, S>);
+
+impl<'a, 'de, S: EventSource<'de>> MapKeyDeserializer<'a, 'de, S> {
+    fn formatted_key(&mut self) -> Result<Option<String>> {
+        let key = match self.0.source.peek()? {
+            Some(Event::Bool(val)) => val.to_string(),
+            Some(Event::I8(val)) => val.to_string(),
+            Some(Event::I16(val)) => val.to_string(),
+            Some(Event::I32(val)) => val.to_string(),
+            Some(Event::I64(val)) => val.to_string(),
+            Some(Event::U8(val)) => val.to_string(),
+            Some(Event::U16(val)) => val.to_string(),
+            Some(Event::U32(val)) => val.to_string(),
+            Some(Event::U64(val)) => val.to_string(),
+            Some(Event::F32(val)) => val.to_string(),
+            Some(Event::F64(val)) => val.to_string(),
+            _ => return Ok(None),
+        };
+        self.0.source.next()?;
+        Ok(Some(key))
     }
 }
 
-impl<'de, 'a, 'event, S: EventSource<'event>> EnumAccess<'de> for &'a mut Deserializer<'event, S> {
+macro_rules! forward_map_key {
+    ($lifetime:lifetime, $name:ident $(, $arg:ident: $ty:ty)*) => {
+        fn $name<V: Visitor<$lifetime>>(self $(, $arg: $ty)*, visitor: V) -> Result<V::Value> {
+            de::Deserializer::$name(self.0 $(, $arg)*, visitor)
+        }
+    };
+}
+
+impl<'a, 'de, S: EventSource<'de>> de::Deserializer<'de> for MapKeyDeserializer<'a, 'de, S> {
+    type Error = Error;
+
+    fn deserialize_str<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value> {
+        match self.formatted_key()? {
+            Some(key) => visitor.visit_string(key),
+            None => de::Deserializer::deserialize_str(self.0, visitor),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value> {
+        match self.formatted_key()? {
+            Some(key) => visitor.visit_string(key),
+            None => de::Deserializer::deserialize_string(self.0, visitor),
+        }
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    forward_map_key!('de, deserialize_any);
+    forward_map_key!('de, deserialize_bool);
+    forward_map_key!('de, deserialize_i8);
+    forward_map_key!('de, deserialize_i16);
+    forward_map_key!('de, deserialize_i32);
+    forward_map_key!('de, deserialize_i64);
+    forward_map_key!('de, deserialize_i128);
+    forward_map_key!('de, deserialize_u8);
+    forward_map_key!('de, deserialize_u16);
+    forward_map_key!('de, deserialize_u32);
+    forward_map_key!('de, deserialize_u64);
+    forward_map_key!('de, deserialize_u128);
+    forward_map_key!('de, deserialize_f32);
+    forward_map_key!('de, deserialize_f64);
+    forward_map_key!('de, deserialize_char);
+    forward_map_key!('de, deserialize_bytes);
+    forward_map_key!('de, deserialize_byte_buf);
+    forward_map_key!('de, deserialize_option);
+    forward_map_key!('de, deserialize_unit);
+    forward_map_key!('de, deserialize_unit_struct, name: &'static str);
+    forward_map_key!('de, deserialize_newtype_struct, name: &'static str);
+    forward_map_key!('de, deserialize_seq);
+    forward_map_key!('de, deserialize_tuple, len: usize);
+    forward_map_key!('de, deserialize_tuple_struct, name: &'static str, len: usize);
+    forward_map_key!('de, deserialize_map);
+    forward_map_key!('de, deserialize_struct, name: &'static str, fields: &'static [&'static str]);
+    forward_map_key!('de, deserialize_enum, name: &'static str, variants: &'static [&'static str]);
+    forward_map_key!('de, deserialize_ignored_any);
+}
+
+impl<'de, 'a, S: EventSource<'de>> EnumAccess<'de> for &'a mut Deserializer<'de, S> {
     type Error = Error;
     type Variant = Self;
 
@@ -486,16 +791,14 @@ impl<'de, 'a, 'event, S: EventSource<'event>> EnumAccess<'de> for &'a mut Deseri
     }
 }
 
-impl<'de, 'a, 'event, S: EventSource<'event>> VariantAccess<'de>
-    for &'a mut Deserializer<'event, S>
-{
+impl<'de, 'a, S: EventSource<'de>> VariantAccess<'de> for &'a mut Deserializer<'de, S> {
     type Error = Error;
 
     fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
     where
         T: DeserializeSeed<'de>,
     {
-        seed.deserialize(&mut *self)
+        self.deserialize_value(seed)
     }
 
     fn struct_variant<V>(
@@ -0,0 +1,228 @@
+//! Timestamp support
+//!
+//! Timestamps are stored as 64 bit integers counting the units since the unix
+//! epoch. Depending on the requested type, they are converted into strings,
+//! into integers in the unit of the field, or into their date and time
+//! components.
+//!
+//! Dates (`Date32`) are stored as the number of days since the unix epoch,
+//! times (`Time64`) as the units since midnight. Both are converted from and
+//! into strings.
+
+use crate::internal::{
+    error::{fail, Result},
+    schema::GenericTimeUnit,
+};
+
+/// A timestamp as read from a `Date64` or `Timestamp` array
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timestamp {
+    /// The number of units since the unix epoch
+    pub value: i64,
+    pub unit: GenericTimeUnit,
+    /// Whether the timestamp refers to an instant (with timezone) or is naive
+    pub utc: bool,
+    /// Whether self-describing types receive the formatted string, instead of
+    /// the value
+    pub prefer_str: bool,
+}
+
+impl Timestamp {
+    /// Format the timestamp
+    ///
+    /// Timestamps with timezone are formatted according to RFC 3339 in UTC
+    /// (e.g., `2024-01-01T12:00:00Z`), naive timestamps without an offset
+    /// (e.g., `2024-01-01T12:00:00`).
+    pub fn format(&self) -> Result<String> {
+        let val = self.to_naive()?;
+
+        // NOTE: chrono documents that Debug, not Display, can be parsed
+        if self.utc {
+            Ok(format!("{:?}", val.and_utc()))
+        } else {
+            Ok(format!("{:?}", val))
+        }
+    }
+
+    /// Split the timestamp into its components
+    ///
+    /// The components follow the serde representation of the `time` crate:
+    /// `(year, ordinal, hour, minute, second, nanosecond)` for
+    /// `PrimitiveDateTime` and, with `with_offset`, the offset
+    /// `(hours, minutes, seconds)` appended for `OffsetDateTime`. The offset
+    /// is always zero, as timestamps are stored in UTC.
+    pub fn to_parts(self, with_offset: bool) -> Result<Vec<i64>> {
+        use chrono::{Datelike, Timelike};
+
+        let val = self.to_naive()?;
+        let mut parts = vec![
+            i64::from(val.year()),
+            i64::from(val.ordinal()),
+            i64::from(val.hour()),
+            i64::from(val.minute()),
+            i64::from(val.second()),
+            i64::from(val.nanosecond()),
+        ];
+        if with_offset {
+            parts.extend([0, 0, 0]);
+        }
+        Ok(parts)
+    }
+
+    fn to_naive(self) -> Result<chrono::NaiveDateTime> {
+        use chrono::NaiveDateTime;
+
+        let per_second = units_per_second(self.unit);
+        let seconds = self.value.div_euclid(per_second);
+        let nanoseconds = self.value.rem_euclid(per_second) * (1_000_000_000 / per_second);
+
+        let Some(val) = NaiveDateTime::from_timestamp_opt(seconds, nanoseconds as u32) else {
+            fail!("Unsupported timestamp value: {}", self.value);
+        };
+        Ok(val)
+    }
+}
+
+/// Parse a naive datetime, e.g., `2024-01-31T12:00:00`
+///
+/// In addition to the format of chrono, a space may separate the date and the
+/// time (`2024-01-31 12:00:00`) and dates without time (`2024-01-31`) are
+/// interpreted as midnight.
+pub fn parse_naive_datetime(s: &str) -> Result<chrono::NaiveDateTime> {
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    let err = match s.parse::<NaiveDateTime>() {
+        Ok(val) => return Ok(val),
+        Err(err) => err,
+    };
+    if let Ok(val) = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f") {
+        return Ok(val);
+    }
+    if let Some(val) = s
+        .parse::<NaiveDate>()
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+    {
+        return Ok(val);
+    }
+    Err(err.into())
+}
+
+/// Parse a date (e.g., `2024-01-31`) into the days since the unix epoch
+pub fn parse_date32(s: &str) -> Result<i32> {
+    use chrono::NaiveDate;
+
+    let date = s.parse::<NaiveDate>()?;
+    let days = date.signed_duration_since(NaiveDate::default()).num_days();
+    Ok(i32::try_from(days)?)
+}
+
+/// Format the days since the unix epoch as a date, e.g., `2024-01-31`
+pub fn format_date32(days: i32) -> Result<String> {
+    use chrono::{Duration, NaiveDate};
+
+    let Some(date) = NaiveDate::default().checked_add_signed(Duration::days(days.into())) else {
+        fail!("Unsupported date value: {days}");
+    };
+    Ok(date.to_string())
+}
+
+/// Parse a time (e.g., `12:30:00.5`) into the units since midnight
+pub fn parse_time64(s: &str, unit: GenericTimeUnit) -> Result<i64> {
+    use chrono::{NaiveTime, Timelike};
+
+    let time = s.parse::<NaiveTime>()?;
+    let seconds = i64::from(time.num_seconds_from_midnight());
+    let nanoseconds = i64::from(time.nanosecond());
+
+    let per_second = units_per_second(unit);
+    Ok(seconds * per_second + nanoseconds / (1_000_000_000 / per_second))
+}
+
+/// Format the units since midnight as a time, e.g., `12:30:00.500`
+pub fn format_time64(value: i64, unit: GenericTimeUnit) -> Result<String> {
+    use chrono::NaiveTime;
+
+    let per_second = units_per_second(unit);
+    let seconds = value.div_euclid(per_second);
+    let nanoseconds = value.rem_euclid(per_second) * (1_000_000_000 / per_second);
+
+    let time = u32::try_from(seconds).ok().and_then(|seconds| {
+        NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanoseconds as u32)
+    });
+    let Some(time) = time else {
+        fail!("Unsupported time value: {value}");
+    };
+    Ok(time.to_string())
+}
+
+fn units_per_second(unit: GenericTimeUnit) -> i64 {
+    match unit {
+        GenericTimeUnit::Second => 1,
+        GenericTimeUnit::Millisecond => 1_000,
+        GenericTimeUnit::Microsecond => 1_000_000,
+        GenericTimeUnit::Nanosecond => 1_000_000_000,
+    }
+}
+
+#[test]
+fn test_format() {
+    let ts = Timestamp {
+        value: -1_500,
+        unit: GenericTimeUnit::Millisecond,
+        utc: false,
+        prefer_str: false,
+    };
+    assert_eq!(ts.format().unwrap(), "1969-12-31T23:59:58.500");
+
+    let ts = Timestamp {
+        value: 1_700_000_000_123_456,
+        unit: GenericTimeUnit::Microsecond,
+        utc: true,
+        prefer_str: false,
+    };
+    assert_eq!(ts.format().unwrap(), "2023-11-14T22:13:20.123456Z");
+    assert_eq!(
+        ts.to_parts(true).unwrap(),
+        vec![2023, 318, 22, 13, 20, 123_456_000, 0, 0, 0]
+    );
+    assert_eq!(
+        ts.to_parts(false).unwrap(),
+        vec![2023, 318, 22, 13, 20, 123_456_000]
+    );
+}
+
+#[test]
+fn test_dates_and_times() {
+    assert_eq!(parse_date32("1970-01-01").unwrap(), 0);
+    assert_eq!(parse_date32("1969-12-31").unwrap(), -1);
+    assert_eq!(parse_date32("2024-01-31").unwrap(), 19753);
+    assert_eq!(format_date32(19753).unwrap(), "2024-01-31");
+    assert_eq!(format_date32(-1).unwrap(), "1969-12-31");
+
+    let unit = GenericTimeUnit::Nanosecond;
+    assert_eq!(parse_time64("00:00:01", unit).unwrap(), 1_000_000_000);
+    assert_eq!(
+        parse_time64("12:30:00.5", unit).unwrap(),
+        45_000_500_000_000
+    );
+    assert_eq!(
+        format_time64(45_000_500_000_000, unit).unwrap(),
+        "12:30:00.500"
+    );
+
+    let unit = GenericTimeUnit::Microsecond;
+    assert_eq!(parse_time64("00:00:01.000002", unit).unwrap(), 1_000_002);
+    assert_eq!(format_time64(1_000_002, unit).unwrap(), "00:00:01.000002");
+    assert!(format_time64(-1, unit).is_err());
+
+    assert_eq!(
+        parse_naive_datetime("2024-01-31 12:00:00.5").unwrap(),
+        parse_naive_datetime("2024-01-31T12:00:00.500").unwrap(),
+    );
+    assert_eq!(
+        parse_naive_datetime("2024-01-31").unwrap(),
+        parse_naive_datetime("2024-01-31T00:00:00").unwrap(),
+    );
+    assert!(parse_naive_datetime("2024-01-31X").is_err());
+}
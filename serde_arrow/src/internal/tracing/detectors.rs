@@ -0,0 +1,91 @@
+//! Detect the content of strings in `from_samples`
+use crate::internal::{
+    common::string_formats::{
+        matches_date, matches_decimal, matches_float, matches_integer, matches_naive_datetime,
+        matches_offset_datetime, matches_time, matches_utc_datetime, matches_uuid,
+    },
+    schema::{GenericDataType, GenericTimeUnit, Strategy},
+};
+
+/// Detectors for the content of string fields in `from_samples`, see
+/// [`TracingOptions::string_detectors`][crate::schema::TracingOptions::string_detectors]
+///
+/// Each detector is tied to a data type and strategy, that allows to
+/// serialize the strings into arrays of this type. If all values of a string
+/// field are missing or detected, the field is traced with the data type of
+/// the detector. If the values are detected as different types, that cannot be
+/// coerced (see below), the field is traced as `LargeUtf8`.
+///
+/// Coercions:
+///
+/// - `Integer` and `Float` detect as `F64`
+/// - `Integer` and `Decimal` detect as `Decimal128` with enough precision
+///   for 64 bit integers
+/// - `Float` and `Decimal` detect as `F64`
+/// - `Date` and `NaiveDateTime` detect as `Date64`
+/// - `UtcDateTime` and `OffsetDateTime` detect as `Date64`
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StringDetector {
+    /// Datetimes without timezone, e.g., `2024-01-31T12:00:00` or
+    /// `2024-01-31 12:00:00.5`, as `Date64` with strategy
+    /// [`NaiveStrAsDate64`][crate::schema::Strategy::NaiveStrAsDate64]
+    NaiveDateTime,
+    /// Datetimes in UTC, e.g., `2024-01-31T12:00:00Z` or
+    /// `2024-01-31T12:00:00+00:00`, as `Date64` with strategy
+    /// [`UtcStrAsDate64`][crate::schema::Strategy::UtcStrAsDate64]
+    UtcDateTime,
+    /// Datetimes with an arbitrary offset (RFC 3339), e.g.,
+    /// `2024-01-31T12:00:00+02:00`, as `Date64` with strategy
+    /// [`UtcStrAsDate64`][crate::schema::Strategy::UtcStrAsDate64]. The
+    /// datetimes are converted to UTC, the offset is not stored.
+    OffsetDateTime,
+    /// Dates, e.g., `2024-01-31`, as `Date32`
+    Date,
+    /// Times, e.g., `12:30:00` or `12:30:00.123`, as `Time64(Nanosecond)`
+    Time,
+    /// Integers, e.g., `"42"`, as `I64` with strategy
+    /// [`StrAsPrimitive`][crate::schema::Strategy::StrAsPrimitive]
+    Integer,
+    /// Floats, e.g., `"3.5"` or `"1e3"`, as `F64` with strategy
+    /// [`StrAsPrimitive`][crate::schema::Strategy::StrAsPrimitive]
+    Float,
+    /// Decimal numbers without exponent, e.g., `"12.50"`, as `Decimal128`
+    /// with strategy [`Decimal128AsStr`][crate::schema::Strategy::Decimal128AsStr].
+    /// Precision and scale are inferred from the values.
+    Decimal,
+    /// UUIDs in their hyphenated form, e.g.,
+    /// `67e55044-10b1-426f-9247-bb680e5fe0c8`, as `LargeUtf8` with strategy
+    /// [`UuidStr`][crate::schema::Strategy::UuidStr]
+    Uuid,
+}
+
+impl StringDetector {
+    /// Detect the data type and strategy of the given string
+    pub fn detect(self, s: &str) -> Option<(GenericDataType, Option<Strategy>)> {
+        use {GenericDataType as T, Strategy as S};
+
+        match self {
+            Self::NaiveDateTime if matches_naive_datetime(s) => {
+                Some((T::Date64, Some(S::NaiveStrAsDate64)))
+            }
+            Self::UtcDateTime if matches_utc_datetime(s) => {
+                Some((T::Date64, Some(S::UtcStrAsDate64)))
+            }
+            Self::OffsetDateTime if matches_offset_datetime(s) => {
+                Some((T::Date64, Some(S::UtcStrAsDate64)))
+            }
+            Self::Date if matches_date(s) => Some((T::Date32, None)),
+            Self::Time if matches_time(s) => Some((T::Time64(GenericTimeUnit::Nanosecond), None)),
+            Self::Integer if matches_integer(s) => Some((T::I64, Some(S::StrAsPrimitive))),
+            Self::Float if matches_float(s) => Some((T::F64, Some(S::StrAsPrimitive))),
+            Self::Decimal => {
+                let (precision, scale) = matches_decimal(s)?;
+                Some((T::Decimal128(precision, scale), Some(S::Decimal128AsStr)))
+            }
+            Self::Uuid if matches_uuid(s) => Some((T::LargeUtf8, Some(S::UuidStr))),
+            _ => None,
+        }
+    }
+}
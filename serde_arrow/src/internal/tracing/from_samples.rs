@@ -7,6 +7,7 @@ use std::collections::HashMap;
 use serde::Serialize;
 
 use crate::internal::{
+    common::string_formats::MAX_DECIMAL_PRECISION,
     error::{fail, Result},
     event::Event,
     schema::{GenericDataType, Strategy},
@@ -17,11 +18,15 @@ use crate::internal::{
         StructMode, StructTracer, StructTracerState, Tracer, TupleTracer, TupleTracerState,
         UnionTracer, UnionTracerState,
     },
-    tracing::TracingOptions,
+    tracing::{
+        detectors::StringDetector, statistics::StatisticsCollector, ConflictingTypes,
+        TracingOptions,
+    },
 };
 
 impl Tracer {
     pub fn trace_samples<T: Serialize + ?Sized>(&mut self, samples: &T) -> Result<()> {
+        self.get_options().check_depth_limit()?;
         self.reset()?;
         let mut tracer = StripOuterSequenceSink::new(&mut *self);
         serialize_into_sink(&mut tracer, samples)
@@ -80,6 +85,9 @@ impl<E: EventSink> EventSink for StripOuterSequenceSink<E> {
             OwnedStr(val) => self.accept_str(&val),
             Variant(name, idx) => self.accept_variant(name, idx),
             OwnedVariant(name, idx) => self.accept_variant(&name, idx),
+            ev @ (Decimal128(_, _) | Timestamp(_) | Json(_) | OwnedJson(_)) => {
+                fail!("Cannot trace event {ev}")
+            }
         }
     }
 
@@ -170,10 +178,29 @@ impl EventSink for Tracer {
     macros::forward_specialized_to_generic!();
 
     fn accept(&mut self, event: Event<'_>) -> Result<()> {
+        // the nulls observed before the type is known
+        let unknown_nulls = match self {
+            Self::Unknown(tracer) => tracer.statistics.nulls,
+            _ => 0,
+        };
+
         match self {
+            // values stored as JSON or nested values beyond the depth limit
+            // are not traced, the parents track the nesting
+            Self::Unknown(tracer)
+                if tracer.options.is_json_path(&tracer.path)
+                    || (tracer.options.is_cut_off(&tracer.path)
+                        && (tracer.deferred || starts_nested_value(&event))) =>
+            {
+                tracer.accept_untraced(&event)
+            }
             // NOTE: unknown tracer is the only tracer that change the internal type
             Self::Unknown(tracer) => match event {
-                Event::Some | Event::Null => tracer.nullable = true,
+                Event::Some => tracer.nullable = true,
+                Event::Null => {
+                    tracer.nullable = true;
+                    tracer.statistics.observe_null();
+                }
                 Event::Bool(_)
                 | Event::I8(_)
                 | Event::I16(_)
@@ -269,6 +296,10 @@ impl EventSink for Tracer {
             Self::Union(tracer) => tracer.accept(event)?,
             Self::Map(tracer) => tracer.accept(event)?,
         }
+
+        if unknown_nulls != 0 && !self.is_unknown() {
+            self.get_statistics_mut().nulls += unknown_nulls;
+        }
         Ok(())
     }
 
@@ -288,6 +319,7 @@ impl StructTracer {
             nullable,
             state: StructTracerState::WaitForKey,
             seen_samples: 0,
+            statistics: StatisticsCollector::default(),
         }
     }
 }
@@ -300,13 +332,24 @@ impl EventSink for StructTracer {
         type E<'a> = Event<'a>;
 
         self.state = match (self.state, event) {
-            (WaitForKey, E::StartStruct | E::StartMap) => InKey,
-            (WaitForKey, E::Null | E::Some) => {
+            (WaitForKey, E::StartStruct | E::StartMap) => {
+                self.statistics.observe_start();
+                InKey
+            }
+            (WaitForKey, E::Null) => {
+                self.nullable = true;
+                self.statistics.observe_null();
+                WaitForKey
+            }
+            (WaitForKey, E::Some) => {
                 self.nullable = true;
                 WaitForKey
             }
             (WaitForKey, ev) => fail!("Invalid event {ev} for struct tracer in state Start"),
-            (InKey, E::Item) => InKey,
+            (InKey, E::Item) => {
+                self.statistics.observe_item();
+                InKey
+            }
             (InKey, E::Str(key)) => {
                 if let Some(&field_idx) = self.index.get(key) {
                     let Some(field) = self.fields.get_mut(field_idx) else {
@@ -329,6 +372,7 @@ impl EventSink for StructTracer {
                     if self.seen_samples != 0 {
                         println!("{key}");
                         field.tracer.mark_nullable();
+                        field.tracer.get_statistics_mut().nulls += self.seen_samples;
                     }
 
                     let field_idx = self.fields.len();
@@ -342,8 +386,12 @@ impl EventSink for StructTracer {
                     // field. was not seen in this sample
                     if field.last_seen_in_sample != self.seen_samples {
                         field.tracer.mark_nullable();
+                        field.tracer.get_statistics_mut().nulls += 1;
                     }
                 }
+                if matches!(self.mode, StructMode::Map) {
+                    self.statistics.observe_end();
+                }
                 self.seen_samples += 1;
 
                 WaitForKey
@@ -392,8 +440,16 @@ impl EventSink for TupleTracer {
         type E<'a> = Event<'a>;
 
         self.state = match (self.state, event) {
-            (WaitForStart, Event::StartTuple) => WaitForItem(0),
-            (WaitForStart, E::Null | E::Some) => {
+            (WaitForStart, Event::StartTuple) => {
+                self.statistics.observe_start();
+                WaitForItem(0)
+            }
+            (WaitForStart, E::Null) => {
+                self.nullable = true;
+                self.statistics.observe_null();
+                WaitForStart
+            }
+            (WaitForStart, E::Some) => {
                 self.nullable = true;
                 WaitForStart
             }
@@ -452,13 +508,27 @@ impl EventSink for ListTracer {
         use {Event as E, ListTracerState as S};
 
         self.state = match (self.state, event) {
-            (S::WaitForStart, E::Null | E::Some) => {
+            (S::WaitForStart, E::Null) => {
                 self.nullable = true;
+                self.statistics.observe_null();
                 S::WaitForStart
             }
-            (S::WaitForStart, E::StartSequence) => S::WaitForItem,
-            (S::WaitForItem, E::EndSequence) => S::WaitForStart,
-            (S::WaitForItem, E::Item) => S::InItem(0),
+            (S::WaitForStart, E::Some) => {
+                self.nullable = true;
+                S::WaitForStart
+            }
+            (S::WaitForStart, E::StartSequence) => {
+                self.statistics.observe_start();
+                S::WaitForItem
+            }
+            (S::WaitForItem, E::EndSequence) => {
+                self.statistics.observe_end();
+                S::WaitForStart
+            }
+            (S::WaitForItem, E::Item) => {
+                self.statistics.observe_item();
+                S::InItem(0)
+            }
             (S::InItem(depth), ev) if ev.is_start() => {
                 self.item_tracer.accept(ev)?;
                 S::InItem(depth + 1)
@@ -508,11 +578,17 @@ impl EventSink for UnionTracer {
         self.state = match self.state {
             S::WaitForVariant => match event {
                 E::Variant(variant, idx) => {
+                    if self.options.collect_statistics {
+                        self.statistics.observe_variant(variant);
+                    }
                     self.ensure_variant(variant, idx)?;
                     S::InVariant(idx, 0)
                 }
                 E::Some => fail!("Nullable unions are not supported"),
                 E::OwnedVariant(variant, idx) => {
+                    if self.options.collect_statistics {
+                        self.statistics.observe_variant(&variant);
+                    }
                     self.ensure_variant(variant, idx)?;
                     S::InVariant(idx, 0)
                 }
@@ -566,20 +642,32 @@ impl EventSink for MapTracer {
 
         self.state = match self.state {
             S::WaitForKey => match event {
-                Event::StartMap => S::InKey(0),
-                Event::Null | Event::Some => {
+                Event::StartMap => {
+                    self.statistics.observe_start();
+                    S::InKey(0)
+                }
+                Event::Null => {
+                    self.nullable = true;
+                    self.statistics.observe_null();
+                    S::WaitForKey
+                }
+                Event::Some => {
                     self.nullable = true;
                     S::WaitForKey
                 }
                 ev => fail!("Unexpected event {ev} in state Start of MapTracer"),
             },
             S::InKey(depth) => match event {
-                Event::Item if depth == 0 => S::InKey(depth),
+                Event::Item if depth == 0 => {
+                    self.statistics.observe_item();
+                    S::InKey(depth)
+                }
                 ev if ev.is_end() => match depth {
                     0 => {
                         if !matches!(ev, E::EndMap) {
                             fail!("Unexpected event {ev} in State Key at depth 0 in MapTracer")
                         }
+                        self.statistics.observe_end();
                         S::WaitForKey
                     }
                     1 => {
@@ -656,11 +744,19 @@ impl EventSink for PrimitiveTracer {
         use GenericDataType::*;
         use Strategy as S;
 
-        let (ev_type, ev_strategy) = match event {
+        if self.options.count_distinct_strings() {
+            match &event {
+                Event::Str(s) => self.strings.observe(s),
+                Event::OwnedStr(s) => self.strings.observe(s),
+                _ => {}
+            }
+        }
+
+        let (ev_type, ev_strategy) = match &event {
             Event::Some | Event::Null => (Null, None),
             Event::Bool(_) => (Bool, None),
             Event::Str(s) => self.get_string_type_and_strategy(s),
-            Event::OwnedStr(s) => self.get_string_type_and_strategy(&s),
+            Event::OwnedStr(s) => self.get_string_type_and_strategy(s),
             Event::U8(_) => (U8, None),
             Event::U16(_) => (U16, None),
             Event::U32(_) => (U32, None),
@@ -674,6 +770,27 @@ impl EventSink for PrimitiveTracer {
             ev => fail!("Cannot handle event {ev} in primitive tracer"),
         };
 
+        if matches!(event, Event::Null) {
+            self.statistics.observe_null();
+        } else if self.options.collect_statistics {
+            self.statistics
+                .observe_primitive(&event, &ev_type, ev_strategy.as_ref());
+        }
+
+        if matches!(self.strategy, Some(S::InconsistentTypes)) {
+            if matches!(ev_type, Null) {
+                self.nullable = true;
+            } else {
+                self.push_conflicting_type(ev_type, ev_strategy);
+            }
+            self.seen_samples += 1;
+            return Ok(());
+        }
+
+        let allow_conflicts = self.options.conflicting_types != ConflictingTypes::Fail;
+        let previous = (self.item_type.clone(), self.strategy.clone());
+        let current = (ev_type.clone(), ev_strategy.clone());
+
         // coercion rules as a table of (this_ty, this_strategy), (ev_ty, ev_strategy)
         (self.item_type, self.strategy) = match (
             (&self.item_type, self.strategy.as_ref()),
@@ -722,6 +839,71 @@ impl EventSink for PrimitiveTracer {
                     (LargeUtf8, None)
                 }
             }
+            ((I64, Some(S::StrAsPrimitive)), (I64, Some(S::StrAsPrimitive))) => {
+                (I64, Some(S::StrAsPrimitive))
+            }
+            ((I64 | F64, Some(S::StrAsPrimitive)), (I64 | F64, Some(S::StrAsPrimitive))) => {
+                (F64, Some(S::StrAsPrimitive))
+            }
+            // numbers that are not encoded as strings are still accepted
+            (
+                (I64, Some(S::StrAsPrimitive)),
+                (I8 | I16 | I32 | I64 | U8 | U16 | U32 | U64, None),
+            )
+            | (
+                (I8 | I16 | I32 | I64 | U8 | U16 | U32 | U64, None),
+                (I64, Some(S::StrAsPrimitive)),
+            ) => (I64, Some(S::StrAsPrimitive)),
+            (
+                (I64 | F64, Some(S::StrAsPrimitive)),
+                (I8 | I16 | I32 | I64 | U8 | U16 | U32 | U64 | F32 | F64, None),
+            )
+            | (
+                (I8 | I16 | I32 | I64 | U8 | U16 | U32 | U64 | F32 | F64, None),
+                (I64 | F64, Some(S::StrAsPrimitive)),
+            ) => (F64, Some(S::StrAsPrimitive)),
+            ((I64 | F64, Some(S::StrAsPrimitive)), (LargeUtf8, None)) => (LargeUtf8, None),
+            ((LargeUtf8, None), (I64 | F64, Some(S::StrAsPrimitive))) => (LargeUtf8, None),
+            ((Date32, None), (Date32, None)) => (Date32, None),
+            ((Time64(unit), None), (Time64(ev_unit), None)) if unit == &ev_unit => {
+                (Time64(ev_unit), None)
+            }
+            // dates are midnight of the given day
+            ((Date32, None), (Date64, Some(S::NaiveStrAsDate64)))
+            | ((Date64, Some(S::NaiveStrAsDate64)), (Date32, None)) => {
+                (Date64, Some(S::NaiveStrAsDate64))
+            }
+            ((LargeUtf8, Some(S::UuidStr)), (LargeUtf8, Some(S::UuidStr))) => {
+                (LargeUtf8, Some(S::UuidStr))
+            }
+            (
+                (Decimal128(precision, scale), Some(S::Decimal128AsStr)),
+                (Decimal128(ev_precision, ev_scale), Some(S::Decimal128AsStr)),
+            ) => merge_decimals((*precision, *scale), (ev_precision, ev_scale)),
+            (
+                (I64, Some(S::StrAsPrimitive)),
+                (Decimal128(precision, scale), Some(S::Decimal128AsStr)),
+            ) => merge_decimals(I64_DECIMAL, (precision, scale)),
+            (
+                (Decimal128(precision, scale), Some(S::Decimal128AsStr)),
+                (I64, Some(S::StrAsPrimitive)),
+            ) => merge_decimals((*precision, *scale), I64_DECIMAL),
+            ((F64, Some(S::StrAsPrimitive)), (Decimal128(_, _), Some(S::Decimal128AsStr)))
+            | ((Decimal128(_, _), Some(S::Decimal128AsStr)), (F64, Some(S::StrAsPrimitive))) => {
+                (F64, Some(S::StrAsPrimitive))
+            }
+            // detected strings of incompatible types, coerce to string
+            ((LargeUtf8, None), (ev, strategy))
+                if self.seen_samples == 0 && is_detected_string(&ev, strategy.as_ref()) =>
+            {
+                (ev, strategy)
+            }
+            ((ty, strategy), (ev, ev_strategy))
+                if is_detected_string(ty, strategy)
+                    && is_detected_string(&ev, ev_strategy.as_ref()) =>
+            {
+                (LargeUtf8, None)
+            }
             ((ty, None), (ev, None)) if self.options.coerce_numbers => match (ty, ev) {
                 // unsigned x unsigned -> u64
                 (U8 | U16 | U32 | U64, U8 | U16 | U32 | U64) => (U64, None),
@@ -737,13 +919,20 @@ impl EventSink for PrimitiveTracer {
                 (I8 | I16 | I32 | I64 | U8 | U16 | U32 | U64, F32 | F64) => (F64, None),
                 // float x int -> f64
                 (F32 | F64, I8 | I16 | I32 | I64 | U8 | U16 | U32 | U64) => (F64, None),
+                _ if allow_conflicts => (Union, Some(S::InconsistentTypes)),
                 (ty, ev) => fail!("Cannot accept event {ev} for tracer of primitive type {ty}"),
             },
+            _ if allow_conflicts => (Union, Some(S::InconsistentTypes)),
             ((this_ty, this_strategy), (ev_ty, ev_strategy)) => {
                 fail!("Cannot accept event {ev_ty} with strategy {ev_strategy:?} for tracer of primitive type {this_ty} with strategy {this_strategy:?}")
             }
         };
 
+        if matches!(self.strategy, Some(S::InconsistentTypes)) {
+            self.push_conflicting_type(previous.0, previous.1);
+            self.push_conflicting_type(current.0, current.1);
+        }
+
         self.seen_samples += 1;
         Ok(())
     }
@@ -753,141 +942,92 @@ impl EventSink for PrimitiveTracer {
     }
 }
 
-impl PrimitiveTracer {
-    fn get_string_type_and_strategy(&self, s: &str) -> (GenericDataType, Option<Strategy>) {
-        if self.options.guess_dates && matches_naive_datetime(s) {
-            (GenericDataType::Date64, Some(Strategy::NaiveStrAsDate64))
-        } else if self.options.guess_dates && matches_utc_datetime(s) {
-            (GenericDataType::Date64, Some(Strategy::UtcStrAsDate64))
-        } else {
-            (GenericDataType::LargeUtf8, None)
-        }
+/// The precision and scale of a decimal that can hold all `i64` values
+const I64_DECIMAL: (u8, i8) = (19, 0);
+
+/// Find the decimal type that can hold the values of both decimal types
+///
+/// If the required precision exceeds the maximum precision, the values are
+/// kept as strings.
+fn merge_decimals(
+    (precision, scale): (u8, i8),
+    (ev_precision, ev_scale): (u8, i8),
+) -> (GenericDataType, Option<Strategy>) {
+    let integer_digits = std::cmp::max(
+        i16::from(precision) - i16::from(scale),
+        i16::from(ev_precision) - i16::from(ev_scale),
+    );
+    let scale = std::cmp::max(scale, ev_scale);
+    let precision = integer_digits + i16::from(scale);
+
+    if precision > i16::from(MAX_DECIMAL_PRECISION) {
+        (GenericDataType::LargeUtf8, None)
+    } else {
+        (
+            GenericDataType::Decimal128(precision as u8, scale),
+            Some(Strategy::Decimal128AsStr),
+        )
     }
 }
 
-mod parsing {
-    pub const DIGIT: &[char] = &['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+/// Check whether the event starts a value that requires further nesting
+/// (structs, lists, maps, tuples and enums)
+fn starts_nested_value(event: &Event<'_>) -> bool {
+    event.is_start() || matches!(event, Event::Variant(_, _) | Event::OwnedVariant(_, _))
+}
 
-    pub fn match_optional_sign(s: &str) -> Result<&str, &str> {
-        Ok(s.strip_prefix(['+', '-']).unwrap_or(s))
-    }
+/// Check whether values of this type and strategy are traced from strings
+fn is_detected_string(data_type: &GenericDataType, strategy: Option<&Strategy>) -> bool {
+    use {GenericDataType as T, Strategy as S};
+
+    matches!(
+        (data_type, strategy),
+        (T::LargeUtf8, None | Some(S::UuidStr))
+            | (T::Date64, Some(S::NaiveStrAsDate64 | S::UtcStrAsDate64))
+            | (T::I64 | T::F64, Some(S::StrAsPrimitive))
+            | (T::Decimal128(_, _), Some(S::Decimal128AsStr))
+            | (T::Date32 | T::Time64(_), None)
+    )
+}
 
-    pub fn match_one_or_more_digits(s: &str) -> Result<&str, &str> {
-        let mut s = s.strip_prefix(DIGIT).ok_or(s)?;
-        while let Some(new_s) = s.strip_prefix(DIGIT) {
-            s = new_s;
+impl PrimitiveTracer {
+    /// Record a type observed in a field with conflicting types
+    ///
+    /// Values stored as strings (e.g., guessed dates) are recorded as strings.
+    fn push_conflicting_type(&mut self, data_type: GenericDataType, strategy: Option<Strategy>) {
+        if matches!(data_type, GenericDataType::Null) {
+            self.nullable = true;
+            return;
         }
-        Ok(s)
-    }
-
-    pub fn match_one_or_two_digits(s: &str) -> Result<&str, &str> {
-        let s = s.strip_prefix(DIGIT).ok_or(s)?;
-        Ok(s.strip_prefix(DIGIT).unwrap_or(s))
-    }
-
-    pub fn match_char(s: &str, c: char) -> Result<&str, &str> {
-        s.strip_prefix(c).ok_or(s)
-    }
-
-    pub fn matches_naive_datetime_with_sep<'a>(
-        s: &'a str,
-        sep: &'_ [char],
-    ) -> Result<&'a str, &'a str> {
-        let s = s.trim();
-        let s = match_optional_sign(s)?;
-        let s = match_one_or_more_digits(s)?;
-        let s = match_char(s, '-')?;
-        let s = match_one_or_two_digits(s)?;
-        let s = match_char(s, '-')?;
-        let s = match_one_or_two_digits(s)?;
-        let s = s.strip_prefix(sep).ok_or(s)?;
-        let s = match_one_or_two_digits(s)?;
-        let s = match_char(s, ':')?;
-        let s = match_one_or_two_digits(s)?;
-        let s = match_char(s, ':')?;
-        let s = match_one_or_two_digits(s)?;
-
-        if let Some(s) = s.strip_prefix('.') {
-            match_one_or_more_digits(s)
+        let data_type = if is_detected_string(&data_type, strategy.as_ref()) {
+            GenericDataType::LargeUtf8
         } else {
-            Ok(s)
+            data_type
+        };
+        if !self.conflicting_types.contains(&data_type) {
+            self.conflicting_types.push(data_type);
         }
     }
 
-    pub fn matches_naive_datetime(s: &str) -> Result<&str, &str> {
-        matches_naive_datetime_with_sep(s, &['T'])
-    }
-
-    pub fn matches_utc_datetime(s: &str) -> Result<&str, &str> {
-        let s = matches_naive_datetime_with_sep(s, &['T', ' '])?;
+    fn get_string_type_and_strategy(&self, s: &str) -> (GenericDataType, Option<Strategy>) {
+        use StringDetector as D;
 
-        if let Some(s) = s.strip_prefix('Z') {
-            Ok(s)
-        } else if let Some(s) = s.strip_prefix("+0000") {
-            Ok(s)
-        } else if let Some(s) = s.strip_prefix("+00:00") {
-            Ok(s)
+        let date_detectors: &[StringDetector] = if self.options.guess_dates {
+            &[D::NaiveDateTime, D::UtcDateTime]
         } else {
-            Err(s)
-        }
-    }
-}
-
-pub fn matches_naive_datetime(s: &str) -> bool {
-    parsing::matches_naive_datetime(s)
-        .map(|s| s.is_empty())
-        .unwrap_or_default()
-}
-
-pub fn matches_utc_datetime(s: &str) -> bool {
-    parsing::matches_utc_datetime(s)
-        .map(|s| s.is_empty())
-        .unwrap_or_default()
-}
-
-#[cfg(test)]
-mod test_matches_naive_datetime {
-    macro_rules! test {
-        ($( ( $name:ident, $s:expr, $expected:expr ), )*) => {
-            $(
-                #[test]
-                fn $name() {
-                    if $expected {
-                        assert_eq!(super::parsing::matches_naive_datetime($s), Ok(""));
-                    }
-                    assert_eq!(super::matches_naive_datetime($s), $expected);
-                }
-            )*
+            &[]
         };
-    }
-
-    test!(
-        (example_chrono_docs_1, "2015-09-18T23:56:04", true),
-        (example_chrono_docs_2, "+12345-6-7T7:59:60.5", true),
-        (surrounding_space, "   2015-09-18T23:56:04   ", true),
-    );
-}
-
-#[cfg(test)]
-mod test_matches_utc_datetime {
-    macro_rules! test {
-        ($( ( $name:ident, $s:expr, $expected:expr ), )*) => {
-            $(
-                #[test]
-                fn $name() {
-                    if $expected {
-                        assert_eq!(super::parsing::matches_utc_datetime($s), Ok(""));
-                    }
-                    assert_eq!(super::matches_utc_datetime($s), $expected);
-                }
-            )*
+        let number_detectors: &[StringDetector] = if self.options.guess_numbers {
+            &[D::Integer, D::Float]
+        } else {
+            &[]
         };
-    }
 
-    test!(
-        (example_chrono_docs_1, "2012-12-12T12:12:12Z", true),
-        (example_chrono_docs_2, "2012-12-12 12:12:12Z", true),
-        (example_chrono_docs_3, "2012-12-12 12:12:12+0000", true),
-        (example_chrono_docs_4, "2012-12-12 12:12:12+00:00", true),
-    );
+        date_detectors
+            .iter()
+            .chain(number_detectors)
+            .chain(&self.options.string_detectors)
+            .find_map(|detector| detector.detect(s))
+            .unwrap_or((GenericDataType::LargeUtf8, None))
+    }
 }
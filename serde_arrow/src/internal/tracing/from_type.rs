@@ -2,29 +2,39 @@
 #[cfg(test)]
 mod test_error_messages;
 
+use std::marker::PhantomData;
+
 use serde::{
     de::{DeserializeSeed, Visitor},
-    Deserialize, Deserializer,
+    Deserialize, Deserializer, Serialize,
 };
 
 use crate::internal::{
     error::{fail, Error, Result},
+    event::Event,
+    sink::{macros, serialize_into_sink, EventSink},
+    source::Deserializer as EventDeserializer,
     tracing::tracer::{StructField, Tracer},
 };
 
 impl Tracer {
     pub fn trace_type<'de, T: Deserialize<'de>>(&mut self) -> Result<()> {
+        self.get_options().check_depth_limit()?;
         self.reset()?;
 
         let mut budget = self.get_options().from_type_budget;
         while !self.is_complete() {
             if budget == 0 {
+                let mut incomplete = Vec::new();
+                self.collect_incomplete_paths(&mut incomplete);
                 fail!(
                     concat!(
                         "Could not determine schema from the type after {budget} iterations. ",
+                        "The values at {incomplete} were not traced. ",
                         "Consider increasing the budget option or using `from_samples`.",
                     ),
                     budget = self.get_options().from_type_budget,
+                    incomplete = incomplete.join(", "),
                 );
             }
             T::deserialize(TraceAny(&mut *self))?;
@@ -41,12 +51,24 @@ struct TraceAny<'a>(&'a mut Tracer);
 impl<'de, 'a> serde::de::Deserializer<'de> for TraceAny<'a> {
     type Error = Error;
 
-    fn deserialize_any<V: serde::de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+    fn deserialize_any<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let options = self.0.get_options();
+        let is_json = options.is_json_path(self.0.get_path());
+        if options.defer_any || options.any_fallback.is_some() || is_json || is_cut_off(self.0) {
+            if let Tracer::Unknown(tracer) = self.0 {
+                tracer.deferred = true;
+                return visitor.visit_unit();
+            }
+        }
+
         fail!(concat!(
             "Non self describing types cannot be traced with `from_type`. ",
             "Consider using `from_samples`. ",
+            "Alternatively, use `from_type_and_samples` or set `any_fallback` in `TracingOptions`. ",
             "One example is `serde_json::Value`. ",
-            "the schema depends on the JSON content and cannot be determined from the type alone."
+            "the schema depends on the JSON content and cannot be determined from the type alone. ",
+            "Another example are structs with flattened fields (`#[serde(flatten)]`): ",
+            "serde buffers the flattened fields without exposing their types.",
         ));
     }
 
@@ -112,7 +134,7 @@ impl<'de, 'a> serde::de::Deserializer<'de> for TraceAny<'a> {
 
     fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
         self.0.ensure_utf8()?;
-        visitor.visit_str("")
+        visitor.visit_borrowed_str("")
     }
 
     fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
@@ -156,6 +178,9 @@ impl<'de, 'a> serde::de::Deserializer<'de> for TraceAny<'a> {
     }
 
     fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if is_cut_off(self.0) {
+            return TraceCutOff::new(self.0).deserialize_seq(visitor);
+        }
         self.0.ensure_list()?;
         let Tracer::List(tracer) = self.0 else {
             unreachable!()
@@ -164,6 +189,9 @@ impl<'de, 'a> serde::de::Deserializer<'de> for TraceAny<'a> {
     }
 
     fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        if is_cut_off(self.0) {
+            return TraceCutOff::new(self.0).deserialize_tuple(len, visitor);
+        }
         self.0.ensure_tuple(len)?;
 
         let Tracer::Tuple(tracer) = self.0 else {
@@ -186,11 +214,25 @@ impl<'de, 'a> serde::de::Deserializer<'de> for TraceAny<'a> {
     }
 
     fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if is_cut_off(self.0) {
+            return TraceCutOff::new(self.0).deserialize_map(visitor);
+        }
         if self.0.get_options().map_as_struct {
+            if let Some(events) = sample_struct_events(self.0) {
+                // the struct is replaced by the traced samples, see `graft_samples`
+                if let Tracer::Unknown(tracer) = self.0 {
+                    tracer.deferred = true;
+                }
+                let events: &[Event<'de>] = &events;
+                return (&mut EventDeserializer::new(events)).deserialize_map(visitor);
+            }
+
             fail!(concat!(
                 "Cannot trace maps as structs with `from_type`. ",
-                "The struct fields cannot be known from the type alone.",
-                "Consider using `from_samples`. ",
+                "The struct fields cannot be known from the type alone. ",
+                "This includes structs with flattened fields (`#[serde(flatten)]`), ",
+                "as serde deserializes them as maps. ",
+                "Consider using `from_samples` or `from_type_and_samples`. ",
             ));
         }
 
@@ -207,10 +249,13 @@ impl<'de, 'a> serde::de::Deserializer<'de> for TraceAny<'a> {
 
     fn deserialize_struct<V: Visitor<'de>>(
         self,
-        _name: &'static str,
+        name: &'static str,
         fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value> {
+        if is_cut_off(self.0) {
+            return TraceCutOff::new(self.0).deserialize_struct(name, fields, visitor);
+        }
         self.0.ensure_struct(fields)?;
         let Tracer::Struct(tracer) = self.0 else {
             unreachable!()
@@ -225,10 +270,13 @@ impl<'de, 'a> serde::de::Deserializer<'de> for TraceAny<'a> {
 
     fn deserialize_enum<V: Visitor<'de>>(
         self,
-        _name: &'static str,
+        name: &'static str,
         variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value> {
+        if is_cut_off(self.0) {
+            return TraceCutOff::new(self.0).deserialize_enum(name, variants, visitor);
+        }
         self.0.ensure_union(variants)?;
 
         let Tracer::Union(tracer) = self.0 else {
@@ -361,11 +409,11 @@ impl<'de, 'a> serde::de::VariantAccess<'de> for TraceAny<'a> {
     type Error = Error;
 
     fn unit_variant(self) -> Result<()> {
-        <()>::deserialize(self)
+        PhantomData::<()>.deserialize(TraceAny(self.0))
     }
 
     fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
-        seed.deserialize(self)
+        seed.deserialize(TraceAny(self.0))
     }
 
     fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
@@ -458,3 +506,418 @@ impl<'de, 'a> serde::de::Deserializer<'de> for IdentifierDeserializer<'a> {
     unimplemented!('de, deserialize_enum, _: &'static str, _: &'static [&'static str]);
     unimplemented!('de, deserialize_ignored_any);
 }
+
+fn is_cut_off(tracer: &Tracer) -> bool {
+    tracer.get_options().is_cut_off(tracer.get_path())
+}
+
+/// Record the events of the samples for `from_type_and_samples`
+///
+/// Chars are recorded as strings, as serde buffers the values of flattened
+/// fields and only accepts strings for chars when deserializing them.
+pub fn record_sample_events<T: Serialize + ?Sized>(samples: &T) -> Result<Vec<Event<'static>>> {
+    let mut recorder = SampleEventRecorder(Vec::new());
+    serialize_into_sink(&mut recorder, samples)?;
+    Ok(recorder.0)
+}
+
+struct SampleEventRecorder(Vec<Event<'static>>);
+
+impl EventSink for SampleEventRecorder {
+    macros::forward_specialized_to_generic!();
+
+    fn accept_char(&mut self, val: char) -> Result<()> {
+        self.0.push(Event::OwnedStr(val.to_string()));
+        Ok(())
+    }
+
+    fn accept(&mut self, event: Event<'_>) -> Result<()> {
+        self.0.push(event.to_static());
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The events of the first sample of a struct at the path of the tracer
+///
+/// Structs with flattened fields are deserialized as maps, whose keys cannot
+/// be known from the type. In `from_type_and_samples` the struct is
+/// constructed from the sample instead and its schema is traced from the
+/// samples.
+fn sample_struct_events(tracer: &Tracer) -> Option<Vec<Event<'static>>> {
+    if !tracer.is_unknown() {
+        return None;
+    }
+    let options = tracer.get_options();
+    let events = options.sample_events.as_ref()?;
+
+    let mut finder = SampleFinder {
+        events,
+        target: tracer.get_path(),
+        map_as_struct: options.map_as_struct,
+        found: None,
+    };
+    let Some(Event::StartSequence | Event::StartTuple) = events.first() else {
+        return None;
+    };
+    let mut pos = 1;
+    while finder.found.is_none() && matches!(events.get(pos), Some(Event::Item)) {
+        pos = finder.skip_value(pos + 1, "$")?;
+    }
+
+    let (start, end) = finder.found?;
+    Some(events[start..end].to_vec())
+}
+
+/// Find the first struct or map at the target path in a stream of events
+struct SampleFinder<'a> {
+    events: &'a [Event<'static>],
+    target: &'a str,
+    map_as_struct: bool,
+    found: Option<(usize, usize)>,
+}
+
+impl<'a> SampleFinder<'a> {
+    /// Skip the value starting at `pos` and return the position after it
+    fn skip_value(&mut self, pos: usize, path: &str) -> Option<usize> {
+        let event = self.events.get(pos)?;
+        let end = match event {
+            Event::Some => return self.skip_value(pos + 1, path),
+            Event::Variant(name, _) => self.skip_value(pos + 1, &format!("{path}.{name}"))?,
+            Event::OwnedVariant(name, _) => self.skip_value(pos + 1, &format!("{path}.{name}"))?,
+            Event::StartSequence | Event::StartTuple => {
+                let mut pos = pos + 1;
+                let mut idx = 0;
+                loop {
+                    match self.events.get(pos)? {
+                        Event::EndSequence | Event::EndTuple => break pos + 1,
+                        Event::Item if matches!(event, Event::StartSequence) => {
+                            pos = self.skip_value(pos + 1, &format!("{path}.item"))?;
+                        }
+                        Event::Item => {
+                            pos = self.skip_value(pos + 1, &format!("{path}.{idx}"))?;
+                            idx += 1;
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+            Event::StartStruct | Event::StartMap => {
+                let as_struct = matches!(event, Event::StartStruct) || self.map_as_struct;
+                let mut pos = pos + 1;
+                loop {
+                    match self.events.get(pos)? {
+                        Event::EndStruct | Event::EndMap => break pos + 1,
+                        Event::Item => pos += 1,
+                        Event::Str(key) if as_struct => {
+                            pos = self.skip_value(pos + 1, &format!("{path}.{key}"))?;
+                        }
+                        Event::OwnedStr(key) if as_struct => {
+                            pos = self.skip_value(pos + 1, &format!("{path}.{key}"))?;
+                        }
+                        _ if as_struct => return None,
+                        _ => {
+                            pos = self.skip_value(pos, &format!("{path}.key"))?;
+                            pos = self.skip_value(pos, &format!("{path}.value"))?;
+                        }
+                    }
+                }
+            }
+            _ => pos + 1,
+        };
+
+        if self.found.is_none()
+            && path == self.target
+            && matches!(event, Event::StartStruct | Event::StartMap)
+        {
+            self.found = Some((pos, end));
+        }
+        Some(end)
+    }
+}
+
+/// Construct a nested value beyond the depth limit without tracing it
+///
+/// Options are constructed as `None`, sequences and maps as empty and enums as
+/// their first variant. The outer value is marked as not traced.
+struct TraceCutOff {
+    depth: usize,
+    max_depth: usize,
+}
+
+impl TraceCutOff {
+    fn new(tracer: &mut Tracer) -> Self {
+        if let Tracer::Unknown(tracer) = &mut *tracer {
+            tracer.deferred = true;
+        }
+        Self {
+            depth: 0,
+            max_depth: tracer.get_options().max_depth,
+        }
+    }
+}
+
+fn nested_cut_off(depth: usize, max_depth: usize) -> Result<TraceCutOff> {
+    if depth >= max_depth {
+        fail!(concat!(
+            "Cannot construct a value of the recursive type beyond the depth limit. ",
+            "The recursion must pass through an `Option`, a sequence, a map ",
+            "or an enum with a non-recursive first variant.",
+        ));
+    }
+    Ok(TraceCutOff {
+        depth: depth + 1,
+        max_depth,
+    })
+}
+
+impl<'de> serde::de::Deserializer<'de> for TraceCutOff {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_bool(Default::default())
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i8(Default::default())
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i16(Default::default())
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i32(Default::default())
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i64(Default::default())
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u8(Default::default())
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u16(Default::default())
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u32(Default::default())
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u64(Default::default())
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f32(Default::default())
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f64(Default::default())
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_char(Default::default())
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_borrowed_str("")
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_string(Default::default())
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_borrowed_bytes(&[])
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_byte_buf(Default::default())
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_none()
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_tuple(0, visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(TraceCutOffTuple {
+            len,
+            depth: self.depth,
+            max_depth: self.max_depth,
+        })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_struct("", &[], visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_map(TraceCutOffStruct {
+            pos: 0,
+            names: fields,
+            depth: self.depth,
+            max_depth: self.max_depth,
+        })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let Some(variant) = variants.first() else {
+            fail!("Cannot construct an enum without variants");
+        };
+        visitor.visit_enum(TraceCutOffEnum {
+            variant,
+            nested: nested_cut_off(self.depth, self.max_depth)?,
+        })
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+}
+
+struct TraceCutOffTuple {
+    len: usize,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl<'de> serde::de::SeqAccess<'de> for TraceCutOffTuple {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        let item = seed.deserialize(nested_cut_off(self.depth, self.max_depth)?)?;
+        Ok(Some(item))
+    }
+}
+
+struct TraceCutOffStruct {
+    pos: usize,
+    names: &'static [&'static str],
+    depth: usize,
+    max_depth: usize,
+}
+
+impl<'de> serde::de::MapAccess<'de> for TraceCutOffStruct {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.pos >= self.names.len() {
+            return Ok(None);
+        }
+        let key = seed.deserialize(IdentifierDeserializer {
+            idx: self.pos,
+            name: self.names[self.pos],
+        })?;
+        Ok(Some(key))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let value = seed.deserialize(nested_cut_off(self.depth, self.max_depth)?)?;
+        self.pos += 1;
+
+        Ok(value)
+    }
+}
+
+struct TraceCutOffEnum {
+    variant: &'static str,
+    nested: TraceCutOff,
+}
+
+impl<'de> serde::de::EnumAccess<'de> for TraceCutOffEnum {
+    type Error = Error;
+    type Variant = TraceCutOff;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant)> {
+        let variant = seed.deserialize(IdentifierDeserializer {
+            idx: 0,
+            name: self.variant,
+        })?;
+        Ok((variant, self.nested))
+    }
+}
+
+impl<'de> serde::de::VariantAccess<'de> for TraceCutOff {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_struct("", fields, visitor)
+    }
+}
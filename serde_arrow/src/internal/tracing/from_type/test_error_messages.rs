@@ -39,7 +39,44 @@ fn map_as_struct() {
     let err = err.to_string();
 
     assert!(err.contains("Cannot trace maps as structs with `from_type`"));
-    assert!(err.contains("Consider using `from_samples`."))
+    assert!(err.contains("Consider using `from_samples` or `from_type_and_samples`."))
+}
+
+#[test]
+fn flattened_struct() {
+    #[derive(serde::Deserialize)]
+    struct Outer {
+        #[allow(unused)]
+        a: i64,
+        #[allow(unused)]
+        #[serde(flatten)]
+        inner: Inner,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Inner {
+        #[allow(unused)]
+        b: f32,
+    }
+
+    let res = SerdeArrowSchema::from_type::<Outer>(TracingOptions::default());
+    let Err(err) = res else {
+        panic!("Expected error, got: {res:?}");
+    };
+    let err = err.to_string();
+
+    assert!(err.contains("Cannot trace maps as structs with `from_type`"));
+    assert!(err.contains("flattened fields (`#[serde(flatten)]`)"));
+    assert!(err.contains("Consider using `from_samples` or `from_type_and_samples`."));
+
+    let res = SerdeArrowSchema::from_type::<Outer>(TracingOptions::default().map_as_struct(false));
+    let Err(err) = res else {
+        panic!("Expected error, got: {res:?}");
+    };
+    let err = err.to_string();
+
+    assert!(err.contains("Non self describing types cannot be traced with `from_type`."));
+    assert!(err.contains("flattened fields (`#[serde(flatten)]`)"));
 }
 
 #[test]
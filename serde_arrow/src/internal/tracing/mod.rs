@@ -1,9 +1,104 @@
+pub mod detectors;
 pub mod from_samples;
 pub mod from_type;
+pub mod overrides;
+pub mod statistics;
 pub mod tracer;
 
+use std::{collections::BTreeMap, sync::Arc};
+
+use serde::Serialize;
+
+use crate::internal::{
+    error::{fail, Result},
+    event::Event,
+    json,
+    schema::{GenericDataType, GenericField, Strategy},
+};
+
 pub use tracer::Tracer;
 
+use detectors::StringDetector;
+use overrides::FieldOverride;
+
+/// How to handle fields with values of conflicting types in `from_samples`
+///
+/// Conflicts arise for primitive fields with values that cannot be coerced
+/// into a common type, e.g., strings and numbers. Fields with a fallback are
+/// marked with [`Strategy::InconsistentTypes`][crate::schema::Strategy].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ConflictingTypes {
+    /// Fail with an error (the default)
+    #[default]
+    Fail,
+    /// Store all values as strings in a `LargeUtf8` field, booleans and
+    /// numbers are formatted
+    Stringify,
+    /// Store the values in a dense union with one variant per observed type.
+    /// The variants are named after their data type (e.g., `"I64"`), nulls are
+    /// stored in a `Null` variant.
+    Union,
+    /// Drop the values: the field is traced as a `Null` field and its values
+    /// are ignored during serialization. Neither tracing nor serialization
+    /// report an error or warning, the data of the field is silently lost.
+    /// The strategy marks the field as dropped in the schema. The paths of
+    /// dropped fields are reported by
+    /// [`TracingStatistics::dropped_fields`][crate::schema::TracingStatistics::dropped_fields]
+    /// when tracing with
+    /// [`from_samples_with_statistics`][crate::schema::SerdeArrowSchema::from_samples_with_statistics].
+    Drop,
+}
+
+/// How to handle values nested deeper than
+/// [`TracingOptions::max_depth`]
+///
+/// Recursive types, e.g., trees, are cut off at the maximum depth. Nested
+/// values (structs, lists, maps and enums) at this depth are stored as a whole
+/// and not traced further. Primitive values keep their type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum DepthLimit {
+    /// Fail with an error (the default)
+    #[default]
+    Fail,
+    /// Store the values as JSON strings, see
+    /// [`Strategy::JsonAsUtf8`][crate::schema::Strategy::JsonAsUtf8]. Requires
+    /// the `json` feature, tracing fails without it
+    JsonAsUtf8,
+    /// Drop the values: the fields are traced as `Null` fields and their
+    /// values are ignored during serialization, as for
+    /// [`ConflictingTypes::Drop`]
+    Drop,
+}
+
+/// When to dictionary encode string fields in `from_samples`, see
+/// [`TracingOptions::string_dictionary_threshold`]
+///
+/// The criteria are evaluated on the string values observed for each field.
+/// Fields without observed string values are not dictionary encoded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum DictionaryThreshold {
+    /// Encode fields with at most the given number of distinct values
+    MaxDistinct(usize),
+    /// Encode fields where the number of distinct values divided by the number
+    /// of values is at most the given ratio
+    MaxRatio(f64),
+}
+
+impl DictionaryThreshold {
+    fn is_satisfied(&self, values: usize, distinct: usize) -> bool {
+        if values == 0 {
+            return false;
+        }
+        match *self {
+            Self::MaxDistinct(max_distinct) => distinct <= max_distinct,
+            Self::MaxRatio(max_ratio) => (distinct as f64) / (values as f64) <= max_ratio,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TracingMode {
     Unknown,
@@ -25,16 +120,24 @@ pub enum TracingMode {
 /// The defaults are:
 ///
 /// ```rust
-/// # use serde_arrow::schema::TracingOptions;
+/// # use serde_arrow::schema::{ConflictingTypes, DepthLimit, TracingOptions};
 /// assert_eq!(
 ///     TracingOptions::default(),
 ///     TracingOptions::new()
 ///         .allow_null_fields(false)
 ///         .map_as_struct(true)
 ///         .string_dictionary_encoding(false)
+///         .string_dictionary_threshold(None)
+///         .large_utf8(true)
+///         .large_list(true)
 ///         .coerce_numbers(false)
 ///         .guess_dates(false)
-///         .from_type_budget(100),
+///         .guess_numbers(false)
+///         .string_detectors(vec![])
+///         .conflicting_types(ConflictingTypes::Fail)
+///         .from_type_budget(100)
+///         .max_depth(20)
+///         .depth_limit(DepthLimit::Fail),
 /// );
 /// ```
 #[derive(Debug, Clone, PartialEq)]
@@ -58,6 +161,34 @@ pub struct TracingOptions {
     /// default polars package.
     pub string_dictionary_encoding: bool,
 
+    /// If given, dictionary encode only the string fields whose observed values
+    /// satisfy the threshold, see [`DictionaryThreshold`]. The default is
+    /// `None`.
+    ///
+    /// If set, [`string_dictionary_encoding`](#structfield.string_dictionary_encoding)
+    /// is ignored. The index type is the narrowest unsigned integer type that
+    /// can address all observed distinct values (`UInt8`, `UInt16` or
+    /// `UInt32`). Serializing data with more distinct values than observed
+    /// may therefore fail with an overflow error.
+    ///
+    /// As values are only observed by `from_samples`, no field is dictionary
+    /// encoded by `from_type`.
+    pub string_dictionary_threshold: Option<DictionaryThreshold>,
+
+    /// If `true`, trace strings as `LargeUtf8` with 64 bit offsets (the
+    /// default). If `false`, trace strings as `Utf8` with 32 bit offsets.
+    ///
+    /// Serializing more than `i32::MAX` bytes of string data into a `Utf8`
+    /// array fails with an overflow error.
+    pub large_utf8: bool,
+
+    /// If `true`, trace sequences as `LargeList` with 64 bit offsets (the
+    /// default). If `false`, trace sequences as `List` with 32 bit offsets.
+    ///
+    /// Serializing more than `i32::MAX` items into a `List` array fails with
+    /// an overflow error.
+    pub large_list: bool,
+
     /// If `true`, coerce different numeric types.
     ///
     /// This option may be helpful when dealing with data formats that do not
@@ -75,7 +206,10 @@ pub struct TracingOptions {
     /// If `true`, try to auto detect datetimes in string columns
     ///
     /// Currently the naive datetime (`YYYY-MM-DDThh:mm:ss`) and UTC datetimes
-    /// (`YYYY-MM-DDThh:mm:ssZ`) are understood.
+    /// (`YYYY-MM-DDThh:mm:ssZ`) are understood, both also with a space instead
+    /// of the `T` separator (`YYYY-MM-DD hh:mm:ss`). This option is equivalent to
+    /// the [`NaiveDateTime`][StringDetector::NaiveDateTime] and
+    /// [`UtcDateTime`][StringDetector::UtcDateTime] string detectors.
     ///
     /// For string fields where all values are either missing or conform to one
     /// of the format the data type is set as `Date64` with strategy
@@ -83,15 +217,69 @@ pub struct TracingOptions {
     /// [`UtcStrAsDate64`][crate::schema::Strategy::UtcStrAsDate64].
     pub guess_dates: bool,
 
+    /// If `true`, try to auto detect numbers in string columns
+    ///
+    /// For string fields where all values are either missing or integers
+    /// (e.g., `"42"`, `"-1"`), the data type is set as `I64`. If some values
+    /// are floats (e.g., `"3.5"`, `"1e3"`), the data type is set as `F64`. In
+    /// both cases the strategy is set to
+    /// [`StrAsPrimitive`][crate::schema::Strategy::StrAsPrimitive]. This
+    /// option is equivalent to the [`Integer`][StringDetector::Integer] and
+    /// [`Float`][StringDetector::Float] string detectors.
+    pub guess_numbers: bool,
+
+    /// Detectors for the content of string columns, see [`StringDetector`]
+    ///
+    /// The detectors are tried in order, the first matching detector
+    /// determines the type of a value. The detectors enabled by
+    /// [`guess_dates`](#structfield.guess_dates) and
+    /// [`guess_numbers`](#structfield.guess_numbers) are tried first.
+    pub string_detectors: Vec<StringDetector>,
+
+    /// How to handle primitive fields with values of conflicting types, see
+    /// [`ConflictingTypes`]. The default is to fail.
+    pub conflicting_types: ConflictingTypes,
+
     /// How many tracing iterations to perform in `from_type`.
     ///
     /// The default value may be too conservative for deeply nested types or
     /// enums with many variants.
     pub from_type_budget: usize,
 
+    /// The maximum nesting depth of the traced fields. The default is `20`.
+    ///
+    /// The fields of the records have depth 1, their children depth 2, and so
+    /// on. Values at this depth are handled according to
+    /// [`depth_limit`](#structfield.depth_limit).
+    pub max_depth: usize,
+
+    /// How to handle values at the maximum depth, see [`DepthLimit`]. The
+    /// default is to fail for nested values.
+    pub depth_limit: DepthLimit,
+
+    /// Overrides of the traced fields by path, see
+    /// [`override_field`][TracingOptions::override_field]
+    pub(crate) overrides: BTreeMap<String, FieldOverride>,
+
+    /// The field used for non self describing values in `from_type`, see
+    /// [`any_fallback`][TracingOptions::any_fallback]
+    pub(crate) any_fallback: Option<FieldOverride>,
+
     /// Internal field to improve error messages for the different tracing
     /// functions
     pub(crate) tracing_mode: TracingMode,
+
+    /// Internal field to request the collection of statistics
+    pub(crate) collect_statistics: bool,
+
+    /// Internal field to defer non self describing values in `from_type` to
+    /// samples
+    pub(crate) defer_any: bool,
+
+    /// Internal field with the events of the samples in
+    /// `from_type_and_samples`, used to construct structs with flattened
+    /// fields
+    pub(crate) sample_events: Option<Arc<Vec<Event<'static>>>>,
 }
 
 impl Default for TracingOptions {
@@ -100,10 +288,23 @@ impl Default for TracingOptions {
             allow_null_fields: false,
             map_as_struct: true,
             string_dictionary_encoding: false,
+            string_dictionary_threshold: None,
+            large_utf8: true,
+            large_list: true,
             coerce_numbers: false,
             guess_dates: false,
+            guess_numbers: false,
+            string_detectors: Vec::new(),
+            conflicting_types: ConflictingTypes::Fail,
             from_type_budget: 100,
+            max_depth: 20,
+            depth_limit: DepthLimit::Fail,
+            overrides: BTreeMap::new(),
+            any_fallback: None,
             tracing_mode: TracingMode::Unknown,
+            collect_statistics: false,
+            defer_any: false,
+            sample_events: None,
         }
     }
 }
@@ -131,6 +332,30 @@ impl TracingOptions {
         self
     }
 
+    /// Set [`string_dictionary_threshold`](#structfield.string_dictionary_threshold)
+    ///
+    /// ```rust
+    /// # use serde_arrow::schema::{DictionaryThreshold, TracingOptions};
+    /// let options = TracingOptions::default()
+    ///     .string_dictionary_threshold(Some(DictionaryThreshold::MaxRatio(0.1)));
+    /// ```
+    pub fn string_dictionary_threshold(mut self, value: Option<DictionaryThreshold>) -> Self {
+        self.string_dictionary_threshold = value;
+        self
+    }
+
+    /// Set [`large_utf8`](#structfield.large_utf8)
+    pub fn large_utf8(mut self, value: bool) -> Self {
+        self.large_utf8 = value;
+        self
+    }
+
+    /// Set [`large_list`](#structfield.large_list)
+    pub fn large_list(mut self, value: bool) -> Self {
+        self.large_list = value;
+        self
+    }
+
     /// Set [`coerce_numbers`](#structfield.coerce_numbers)
     pub fn coerce_numbers(mut self, value: bool) -> Self {
         self.coerce_numbers = value;
@@ -143,14 +368,254 @@ impl TracingOptions {
         self
     }
 
+    /// Set [`guess_numbers`](#structfield.guess_numbers)
+    pub fn guess_numbers(mut self, value: bool) -> Self {
+        self.guess_numbers = value;
+        self
+    }
+
+    /// Set [`string_detectors`](#structfield.string_detectors)
+    ///
+    /// ```rust
+    /// # use serde_arrow::schema::{StringDetector, TracingOptions};
+    /// let options = TracingOptions::default().string_detectors(vec![
+    ///     StringDetector::Date,
+    ///     StringDetector::OffsetDateTime,
+    ///     StringDetector::Decimal,
+    /// ]);
+    /// ```
+    pub fn string_detectors(mut self, value: Vec<StringDetector>) -> Self {
+        self.string_detectors = value;
+        self
+    }
+
+    /// Set [`conflicting_types`](#structfield.conflicting_types)
+    pub fn conflicting_types(mut self, value: ConflictingTypes) -> Self {
+        self.conflicting_types = value;
+        self
+    }
+
     /// Set [`from_type_budget`](#structfield.from_type_budget)
     pub fn from_type_budget(mut self, value: usize) -> Self {
         self.from_type_budget = value;
         self
     }
 
+    /// Set [`max_depth`](#structfield.max_depth)
+    pub fn max_depth(mut self, value: usize) -> Self {
+        self.max_depth = value;
+        self
+    }
+
+    /// Set [`depth_limit`](#structfield.depth_limit)
+    pub fn depth_limit(mut self, value: DepthLimit) -> Self {
+        self.depth_limit = value;
+        self
+    }
+
+    /// Override the field traced at the given path
+    ///
+    /// Paths start with `$` for the record, followed by the names of the
+    /// nested fields separated by dots, e.g., `$.user.id`. The items of lists
+    /// are addressed as `item`, the keys and values of maps as `key` and
+    /// `value`, e.g., `$.tags.item`.
+    ///
+    /// The override is given as a field description without name (see
+    /// [`SchemaLike::from_value`][crate::schema::SchemaLike::from_value]) with
+    /// the optional keys `data_type`, `strategy`, `nullable` and `children`.
+    /// Keys that are not given are kept from the traced field. If the data
+    /// type is changed, the strategy is replaced as well. Children may be
+    /// omitted, if they can be derived from the traced field (e.g., for
+    /// string fields that are overridden as `Dictionary`).
+    ///
+    /// With the strategy `JsonAsUtf8` (`{"strategy": "JsonAsUtf8"}`), the
+    /// values at the path are stored as JSON strings. Their structure is not
+    /// traced and may vary between samples. Values of non self describing
+    /// types, such as `serde_json::Value`, can also be traced this way with
+    /// `from_type`.
+    ///
+    /// The override is validated against the traced field when the schema is
+    /// built: nullable fields cannot be made non-nullable, paths that were not
+    /// traced result in an error.
+    ///
+    /// ```rust
+    /// # fn main() -> serde_arrow::Result<()> {
+    /// # use serde_json::json;
+    /// use serde::Deserialize;
+    /// use serde_arrow::schema::{SchemaLike, SerdeArrowSchema, TracingOptions};
+    ///
+    /// ##[derive(Deserialize)]
+    /// struct Record {
+    ///     price: f64,
+    ///     user: User,
+    /// }
+    ///
+    /// ##[derive(Deserialize)]
+    /// struct User {
+    ///     id: String,
+    /// }
+    ///
+    /// let options = TracingOptions::default()
+    ///     .override_field("$.price", json!({"data_type": "Decimal128(18, 4)"}))?
+    ///     .override_field("$.user.id", json!({"data_type": "Dictionary"}))?;
+    ///
+    /// let schema = SerdeArrowSchema::from_type::<Record>(options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn override_field<P: Into<String>, V: Serialize>(
+        mut self,
+        path: P,
+        value: V,
+    ) -> Result<Self> {
+        let path = path.into();
+        if path != "$" && !path.starts_with("$.") {
+            fail!("Invalid override path {path:?}, paths must start with \"$\"");
+        }
+
+        self.overrides.insert(path, to_field_override(value)?);
+        Ok(self)
+    }
+
+    /// Set the field used for non self describing values in `from_type`
+    ///
+    /// Non self describing types, such as `serde_json::Value`, cannot be
+    /// traced from the type alone. Per default, `from_type` fails for these
+    /// types and
+    /// [`from_type_and_samples`][crate::schema::SchemaLike::from_type_and_samples]
+    /// traces them from the given samples. With a fallback, these values are
+    /// described by the fallback instead, if no samples are available.
+    ///
+    /// The fallback is given as in
+    /// [`override_field`][TracingOptions::override_field] and applied to a
+    /// string field (`LargeUtf8` or `Utf8`, see
+    /// [`large_utf8`](#structfield.large_utf8)). Plain string fields only
+    /// accept string values. To store arbitrary values as JSON strings, use
+    /// `{"strategy": "JsonAsUtf8"}`, which requires the `json` feature.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "json")]
+    /// # fn main() -> serde_arrow::Result<()> {
+    /// # use serde_json::json;
+    /// use serde::Deserialize;
+    /// use serde_arrow::schema::{SchemaLike, SerdeArrowSchema, TracingOptions};
+    ///
+    /// ##[derive(Deserialize)]
+    /// struct Record {
+    ///     id: u64,
+    ///     label: serde_json::Value,
+    /// }
+    ///
+    /// let options = TracingOptions::default()
+    ///     .any_fallback(json!({"strategy": "JsonAsUtf8"}))?;
+    /// let schema = SerdeArrowSchema::from_type::<Record>(options)?;
+    ///
+    /// assert_eq!(
+    ///     schema,
+    ///     SerdeArrowSchema::from_value(&json!([
+    ///         {"name": "id", "data_type": "U64"},
+    ///         {"name": "label", "data_type": "LargeUtf8", "strategy": "JsonAsUtf8"},
+    ///     ]))?,
+    /// );
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "json"))]
+    /// # fn main() {}
+    /// ```
+    pub fn any_fallback<V: Serialize>(mut self, value: V) -> Result<Self> {
+        self.any_fallback = Some(to_field_override(value)?);
+        Ok(self)
+    }
+
     pub(crate) fn tracing_mode(mut self, value: TracingMode) -> Self {
         self.tracing_mode = value;
         self
     }
+
+    pub(crate) fn defer_any(mut self, value: bool) -> Self {
+        self.defer_any = value;
+        self
+    }
+
+    pub(crate) fn sample_events(mut self, value: Option<Arc<Vec<Event<'static>>>>) -> Self {
+        self.sample_events = value;
+        self
+    }
+
+    pub(crate) fn collect_statistics(mut self, value: bool) -> Self {
+        self.collect_statistics = value;
+        self
+    }
+
+    /// The data type used for traced strings
+    pub(crate) fn string_type(&self) -> GenericDataType {
+        if self.large_utf8 {
+            GenericDataType::LargeUtf8
+        } else {
+            GenericDataType::Utf8
+        }
+    }
+
+    /// The data type used for traced sequences
+    pub(crate) fn list_type(&self) -> &'static GenericDataType {
+        if self.large_list {
+            &GenericDataType::LargeList
+        } else {
+            &GenericDataType::List
+        }
+    }
+
+    /// Whether the values at the path are stored as JSON strings and not
+    /// traced further
+    pub(crate) fn is_json_path(&self, path: &str) -> bool {
+        matches!(
+            self.overrides.get(path),
+            Some(FieldOverride {
+                strategy: Some(Strategy::JsonAsUtf8),
+                ..
+            })
+        )
+    }
+
+    /// Whether the values at the path are beyond the depth limit and not
+    /// traced further
+    pub(crate) fn is_cut_off(&self, path: &str) -> bool {
+        !matches!(self.depth_limit, DepthLimit::Fail) && path_depth(path) >= self.max_depth
+    }
+
+    /// Fail, if the depth limit cannot be applied with the enabled features
+    pub(crate) fn check_depth_limit(&self) -> Result<()> {
+        if matches!(self.depth_limit, DepthLimit::JsonAsUtf8) {
+            json::ensure_available()?;
+        }
+        Ok(())
+    }
+
+    /// The field used for values beyond the depth limit
+    pub(crate) fn cut_off_field(&self, name: &str, nullable: bool) -> GenericField {
+        match self.depth_limit {
+            DepthLimit::JsonAsUtf8 => GenericField::new(name, self.string_type(), nullable)
+                .with_strategy(Strategy::JsonAsUtf8),
+            DepthLimit::Drop => GenericField::new(name, GenericDataType::Null, true)
+                .with_strategy(Strategy::InconsistentTypes),
+            DepthLimit::Fail => unreachable!("values are not cut off with DepthLimit::Fail"),
+        }
+    }
+
+    /// Whether the tracers need to track the distinct string values
+    pub(crate) fn count_distinct_strings(&self) -> bool {
+        self.collect_statistics || self.string_dictionary_threshold.is_some()
+    }
+}
+
+/// The nesting depth of a path, the fields of the records have depth 1
+pub(crate) fn path_depth(path: &str) -> usize {
+    path.chars().filter(|c| *c == '.').count()
+}
+
+fn to_field_override<V: Serialize>(value: V) -> Result<FieldOverride> {
+    // simple version of serde-transcode
+    let mut events = Vec::<crate::internal::event::Event>::new();
+    crate::internal::sink::serialize_into_sink(&mut events, &value)?;
+    crate::internal::source::deserialize_from_source(&events)
 }
@@ -0,0 +1,81 @@
+//! Overrides of traced fields, see [`TracingOptions::override_field`]
+//!
+//! [`TracingOptions::override_field`]: crate::schema::TracingOptions::override_field
+use serde::{Deserialize, Serialize};
+
+use crate::internal::{
+    error::{fail, Result},
+    schema::{GenericDataType, GenericField, Strategy},
+};
+
+/// Replace parts of the field traced at a given path
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldOverride {
+    #[serde(default)]
+    pub data_type: Option<GenericDataType>,
+    #[serde(default)]
+    pub strategy: Option<Strategy>,
+    #[serde(default)]
+    pub nullable: Option<bool>,
+    #[serde(default)]
+    pub children: Option<Vec<GenericField>>,
+}
+
+impl FieldOverride {
+    /// Apply the override to the traced field and validate the result
+    pub fn apply(&self, path: &str, mut field: GenericField) -> Result<GenericField> {
+        if let Some(nullable) = self.nullable {
+            if field.nullable && !nullable {
+                fail!("Cannot override the nullable field at {path} as non-nullable");
+            }
+            field.nullable = nullable;
+        }
+
+        if let Some(children) = &self.children {
+            let Some(data_type) = &self.data_type else {
+                fail!("The override of {path} sets children without a data type");
+            };
+            field.data_type = data_type.clone();
+            field.children = children.clone();
+            field.strategy = self.strategy.clone();
+        } else if let Some(data_type) = &self.data_type {
+            field.children = override_children(path, &field, data_type)?;
+            field.data_type = data_type.clone();
+            field.strategy = self.strategy.clone();
+        } else if let Some(strategy) = &self.strategy {
+            field.strategy = Some(strategy.clone());
+        }
+
+        if let Err(err) = field.validate() {
+            fail!("Invalid override of {path}: {msg}", msg = err.message());
+        }
+        Ok(field)
+    }
+}
+
+/// Determine the children of the overridden field, if no children are given
+fn override_children(
+    path: &str,
+    traced: &GenericField,
+    data_type: &GenericDataType,
+) -> Result<Vec<GenericField>> {
+    use GenericDataType as T;
+
+    match (&traced.data_type, data_type) {
+        (T::Utf8 | T::LargeUtf8, T::Dictionary) => Ok(vec![
+            GenericField::new("key", T::U32, traced.nullable),
+            GenericField::new("value", traced.data_type.clone(), false),
+        ]),
+        (T::List | T::LargeList, T::List | T::LargeList) => Ok(traced.children.clone()),
+        (traced_type, data_type) if traced_type == data_type => Ok(traced.children.clone()),
+        (_, T::Struct | T::List | T::LargeList | T::Map | T::Union | T::Dictionary) => fail!(
+            "The override of {path} requires children for the data type {data_type}, traced {traced_type}",
+            traced_type = traced.data_type,
+        ),
+        _ if traced.children.is_empty() => Ok(Vec::new()),
+        (traced_type, data_type) => fail!(
+            "Cannot override the data type {traced_type} of {path} with {data_type} without children"
+        ),
+    }
+}
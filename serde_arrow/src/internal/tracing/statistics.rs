@@ -0,0 +1,329 @@
+//! Statistics collected while tracing samples
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::DefaultHasher, BTreeMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
+use crate::internal::{
+    event::Event,
+    schema::{GenericDataType, GenericTimeUnit, Strategy},
+    timestamp,
+};
+
+/// Statistics of the values observed by `from_samples`, keyed by field path
+///
+/// The paths follow the conventions of
+/// [`TracingOptions::override_field`][crate::schema::TracingOptions::override_field],
+/// e.g., `$.user.id`. The records themselves are reported under `$`. See
+/// [`SerdeArrowSchema::from_samples_with_statistics`][crate::schema::SerdeArrowSchema::from_samples_with_statistics]
+/// for an example.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TracingStatistics {
+    pub(crate) fields: BTreeMap<String, FieldStatistics>,
+    pub(crate) dropped_fields: Vec<String>,
+}
+
+impl TracingStatistics {
+    /// Get the statistics of the field at the given path
+    pub fn get(&self, path: &str) -> Option<&FieldStatistics> {
+        self.fields.get(path)
+    }
+
+    /// Iterate over the paths and statistics of all fields
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &FieldStatistics)> {
+        self.fields
+            .iter()
+            .map(|(path, stats)| (path.as_str(), stats))
+    }
+
+    /// The paths of the fields dropped from the schema, as their values have
+    /// conflicting types (see
+    /// [`ConflictingTypes::Drop`][crate::schema::ConflictingTypes::Drop])
+    pub fn dropped_fields(&self) -> &[String] {
+        &self.dropped_fields
+    }
+}
+
+/// Statistics of a single field
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct FieldStatistics {
+    /// The number of non-null values
+    pub values: usize,
+    /// The number of null values, including the samples without the field
+    pub nulls: usize,
+    /// The smallest number or temporal value, see [`StatisticsValue`]
+    pub min: Option<StatisticsValue>,
+    /// The largest number or temporal value, see [`StatisticsValue`]
+    pub max: Option<StatisticsValue>,
+    /// The minimum and maximum length of strings in bytes
+    pub string_length_range: Option<(usize, usize)>,
+    /// The minimum and maximum number of items of lists or entries of maps
+    pub length_range: Option<(usize, usize)>,
+    /// An estimate of the number of distinct primitive values
+    ///
+    /// The values are compared by their 64 bit hashes, so only the hashes and
+    /// not the values themselves are kept. The memory required still grows
+    /// with the number of distinct values. Hash collisions may lead to slight
+    /// underestimates.
+    pub distinct_values: usize,
+    /// How often each variant of an enum was observed
+    pub variant_counts: BTreeMap<String, usize>,
+    /// The number of string values
+    pub string_values: usize,
+    /// The number of distinct string values, with the same caveats as
+    /// [`distinct_values`](#structfield.distinct_values)
+    pub distinct_strings: usize,
+}
+
+/// A bound of the values observed for a field
+///
+/// Numbers detected in strings (e.g., with
+/// [`guess_numbers`][crate::schema::TracingOptions::guess_numbers]) are
+/// reported as numbers. Dates, datetimes and times detected in strings are
+/// compared by their parsed value and reported as given in the samples.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum StatisticsValue {
+    /// An integer
+    Int(i128),
+    /// A floating point number
+    Float(f64),
+    /// A date, datetime or time
+    Temporal(String),
+}
+
+/// Collect the statistics of a single tracer
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatisticsCollector {
+    pub values: usize,
+    pub nulls: usize,
+    pub min: Option<Bound>,
+    pub max: Option<Bound>,
+    pub string_length_range: Option<(usize, usize)>,
+    pub length_range: Option<(usize, usize)>,
+    /// The length of the current list or map
+    pub current_length: usize,
+    pub hashes: HashSet<u64>,
+    pub variant_counts: BTreeMap<String, usize>,
+}
+
+/// A bound together with its sort key
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bound {
+    value: StatisticsValue,
+    /// The nanoseconds since the epoch (or midnight) for temporal values
+    temporal_key: i128,
+}
+
+impl Bound {
+    fn compare(&self, other: &Bound) -> Option<Ordering> {
+        use StatisticsValue as V;
+
+        match (&self.value, &other.value) {
+            (V::Int(a), V::Int(b)) => Some(a.cmp(b)),
+            (V::Int(a), V::Float(b)) => (*a as f64).partial_cmp(b),
+            (V::Float(a), V::Int(b)) => a.partial_cmp(&(*b as f64)),
+            (V::Float(a), V::Float(b)) => a.partial_cmp(b),
+            (V::Temporal(_), V::Temporal(_)) => Some(self.temporal_key.cmp(&other.temporal_key)),
+            _ => None,
+        }
+    }
+}
+
+impl StatisticsCollector {
+    pub fn to_statistics(&self, strings: Option<&StringCounter>) -> FieldStatistics {
+        FieldStatistics {
+            values: self.values,
+            nulls: self.nulls,
+            min: self.min.as_ref().map(|bound| bound.value.clone()),
+            max: self.max.as_ref().map(|bound| bound.value.clone()),
+            string_length_range: self.string_length_range,
+            length_range: self.length_range,
+            distinct_values: self.hashes.len(),
+            variant_counts: self.variant_counts.clone(),
+            string_values: strings.map(|strings| strings.values).unwrap_or_default(),
+            distinct_strings: strings.map(StringCounter::distinct).unwrap_or_default(),
+        }
+    }
+
+    pub fn observe_null(&mut self) {
+        self.nulls += 1;
+    }
+
+    pub fn observe_start(&mut self) {
+        self.values += 1;
+        self.current_length = 0;
+    }
+
+    pub fn observe_item(&mut self) {
+        self.current_length += 1;
+    }
+
+    pub fn observe_end(&mut self) {
+        self.length_range = Some(extend_range(self.length_range, self.current_length));
+    }
+
+    pub fn observe_variant(&mut self, variant: &str) {
+        self.values += 1;
+        *self.variant_counts.entry(variant.to_owned()).or_default() += 1;
+    }
+
+    /// Observe a primitive value with the type (and strategy) it was traced as
+    pub fn observe_primitive(
+        &mut self,
+        event: &Event<'_>,
+        data_type: &GenericDataType,
+        strategy: Option<&Strategy>,
+    ) {
+        use {Event as E, StatisticsValue as V};
+
+        let mut hasher = DefaultHasher::new();
+        let value = match event {
+            E::Null | E::Some => return,
+            E::Bool(v) => {
+                v.hash(&mut hasher);
+                None
+            }
+            E::I8(v) => hash_int(&mut hasher, *v),
+            E::I16(v) => hash_int(&mut hasher, *v),
+            E::I32(v) => hash_int(&mut hasher, *v),
+            E::I64(v) => hash_int(&mut hasher, *v),
+            E::U8(v) => hash_int(&mut hasher, *v),
+            E::U16(v) => hash_int(&mut hasher, *v),
+            E::U32(v) => hash_int(&mut hasher, *v),
+            E::U64(v) => hash_int(&mut hasher, *v),
+            E::F32(v) => hash_float(&mut hasher, f64::from(*v)),
+            E::F64(v) => hash_float(&mut hasher, *v),
+            E::Str(s) => self.observe_str(&mut hasher, s, data_type, strategy),
+            E::OwnedStr(s) => self.observe_str(&mut hasher, s, data_type, strategy),
+            _ => return,
+        };
+
+        self.values += 1;
+        self.hashes.insert(hasher.finish());
+
+        let Some(bound) = value else {
+            return;
+        };
+        if matches!(bound.value, V::Float(v) if v.is_nan()) {
+            return;
+        }
+        let replace_min = match &self.min {
+            Some(min) => bound.compare(min) == Some(Ordering::Less),
+            None => true,
+        };
+        if replace_min {
+            self.min = Some(bound.clone());
+        }
+
+        let replace_max = match &self.max {
+            Some(max) => bound.compare(max) == Some(Ordering::Greater),
+            None => true,
+        };
+        if replace_max {
+            self.max = Some(bound);
+        }
+    }
+
+    fn observe_str(
+        &mut self,
+        hasher: &mut DefaultHasher,
+        s: &str,
+        data_type: &GenericDataType,
+        strategy: Option<&Strategy>,
+    ) -> Option<Bound> {
+        use {GenericDataType as T, StatisticsValue as V, Strategy as S};
+
+        s.hash(hasher);
+        self.string_length_range = Some(extend_range(self.string_length_range, s.len()));
+
+        let number = |value| {
+            Some(Bound {
+                value,
+                temporal_key: 0,
+            })
+        };
+        let temporal = |temporal_key| {
+            Some(Bound {
+                value: V::Temporal(s.to_owned()),
+                temporal_key,
+            })
+        };
+
+        match (data_type, strategy) {
+            (T::I64, Some(S::StrAsPrimitive)) => number(V::Int(s.parse().ok()?)),
+            (T::F64, Some(S::StrAsPrimitive)) | (T::Decimal128(_, _), Some(S::Decimal128AsStr)) => {
+                number(V::Float(s.parse().ok()?))
+            }
+            (T::Date64, Some(S::NaiveStrAsDate64)) => {
+                let val = timestamp::parse_naive_datetime(s).ok()?.and_utc();
+                temporal(nanoseconds(val.timestamp(), val.timestamp_subsec_nanos()))
+            }
+            (T::Date64, Some(S::UtcStrAsDate64)) => {
+                let val = s.parse::<chrono::DateTime<chrono::Utc>>().ok()?;
+                temporal(nanoseconds(val.timestamp(), val.timestamp_subsec_nanos()))
+            }
+            (T::Date32, None) => {
+                let days = timestamp::parse_date32(s).ok()?;
+                temporal(nanoseconds(i64::from(days) * 86_400, 0))
+            }
+            (T::Time64(_), None) => temporal(
+                timestamp::parse_time64(s, GenericTimeUnit::Nanosecond)
+                    .ok()?
+                    .into(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+fn hash_int<I: Into<i128>>(hasher: &mut DefaultHasher, v: I) -> Option<Bound> {
+    let v = v.into();
+    v.hash(hasher);
+    Some(Bound {
+        value: StatisticsValue::Int(v),
+        temporal_key: 0,
+    })
+}
+
+fn hash_float(hasher: &mut DefaultHasher, v: f64) -> Option<Bound> {
+    v.to_bits().hash(hasher);
+    Some(Bound {
+        value: StatisticsValue::Float(v),
+        temporal_key: 0,
+    })
+}
+
+fn nanoseconds(seconds: i64, nanoseconds: u32) -> i128 {
+    i128::from(seconds) * 1_000_000_000 + i128::from(nanoseconds)
+}
+
+fn extend_range(range: Option<(usize, usize)>, value: usize) -> (usize, usize) {
+    match range {
+        Some((min, max)) => (min.min(value), max.max(value)),
+        None => (value, value),
+    }
+}
+
+/// Track the number of distinct strings observed by a tracer
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StringCounter {
+    pub values: usize,
+    pub hashes: HashSet<u64>,
+}
+
+impl StringCounter {
+    pub fn observe(&mut self, s: &str) {
+        let mut hasher = DefaultHasher::new();
+        s.hash(&mut hasher);
+
+        self.values += 1;
+        self.hashes.insert(hasher.finish());
+    }
+
+    pub fn distinct(&self) -> usize {
+        self.hashes.len()
+    }
+}
@@ -1,18 +1,18 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::internal::{
     error::{fail, Result},
+    event::Event,
     schema::{GenericDataType, GenericField, SerdeArrowSchema, Strategy},
-    tracing::TracingOptions,
+    tracing::{
+        path_depth,
+        statistics::{StatisticsCollector, StringCounter, TracingStatistics},
+        ConflictingTypes, TracingOptions,
+    },
 };
 
 use super::TracingMode;
 
-// TODO: allow to customize
-const MAX_TYPE_DEPTH: usize = 20;
-const RECURSIVE_TYPE_WARNING: &str =
-    "too deeply nested type detected. Recursive types are not supported in schema tracing";
-
 macro_rules! defined_tracer {
     ($($variant:ident($impl:ident)),* $(,)? ) => {
         #[derive(Debug, PartialEq, Clone)]
@@ -47,6 +47,17 @@ impl Tracer {
 
     /// Convert the traced schema into a schema object
     pub fn to_schema(&self) -> Result<SerdeArrowSchema> {
+        let overrides = &self.get_options().overrides;
+        if !overrides.is_empty() {
+            let mut paths = HashSet::new();
+            self.collect_paths(&mut paths);
+            for path in overrides.keys() {
+                if !paths.contains(path) {
+                    fail!("Cannot override the field at {path}: the path was not traced");
+                }
+            }
+        }
+
         let root = self.to_field("root")?;
 
         if root.nullable {
@@ -86,6 +97,15 @@ impl Tracer {
         matches!(self, Tracer::Unknown(_))
     }
 
+    /// Whether the tracer is beyond the depth limit and holds only nested
+    /// values, that were not traced, or missing values
+    pub fn is_cut_off(&self) -> bool {
+        match self {
+            Tracer::Unknown(tracer) => tracer.options.is_cut_off(&tracer.path),
+            _ => false,
+        }
+    }
+
     pub fn is_complete(&self) -> bool {
         dispatch_tracer!(self, tracer => tracer.is_complete())
     }
@@ -102,8 +122,144 @@ impl Tracer {
         dispatch_tracer!(self, tracer => tracer.nullable)
     }
 
+    /// Build the field, applying the override configured for its path
     pub fn to_field(&self, name: &str) -> Result<GenericField> {
-        dispatch_tracer!(self, tracer => tracer.to_field(name))
+        let options = self.get_options();
+        let field = if options.is_json_path(self.get_path()) {
+            // the structure of values stored as JSON is not kept
+            GenericField::new(name, options.string_type(), self.get_nullable())
+        } else if self.is_cut_off() {
+            options.cut_off_field(name, self.get_nullable())
+        } else {
+            dispatch_tracer!(self, tracer => tracer.to_field(name))?
+        };
+        match options.overrides.get(self.get_path()) {
+            Some(field_override) => field_override.apply(self.get_path(), field),
+            None => Ok(field),
+        }
+    }
+
+    /// The direct children of this tracer
+    pub fn children(&self) -> Vec<&Tracer> {
+        match self {
+            Tracer::Unknown(_) | Tracer::Primitive(_) => vec![],
+            Tracer::List(tracer) => vec![tracer.item_tracer.as_ref()],
+            Tracer::Map(tracer) => vec![tracer.key_tracer.as_ref(), tracer.value_tracer.as_ref()],
+            Tracer::Struct(tracer) => tracer.fields.iter().map(|field| &field.tracer).collect(),
+            Tracer::Tuple(tracer) => tracer.field_tracers.iter().collect(),
+            Tracer::Union(tracer) => tracer
+                .variants
+                .iter()
+                .flatten()
+                .map(|variant| &variant.tracer)
+                .collect(),
+        }
+    }
+
+    /// The direct children of this tracer, mutably borrowed
+    pub fn children_mut(&mut self) -> Vec<&mut Tracer> {
+        match self {
+            Tracer::Unknown(_) | Tracer::Primitive(_) => vec![],
+            Tracer::List(tracer) => vec![tracer.item_tracer.as_mut()],
+            Tracer::Map(tracer) => vec![tracer.key_tracer.as_mut(), tracer.value_tracer.as_mut()],
+            Tracer::Struct(tracer) => tracer
+                .fields
+                .iter_mut()
+                .map(|field| &mut field.tracer)
+                .collect(),
+            Tracer::Tuple(tracer) => tracer.field_tracers.iter_mut().collect(),
+            Tracer::Union(tracer) => tracer
+                .variants
+                .iter_mut()
+                .flatten()
+                .map(|variant| &mut variant.tracer)
+                .collect(),
+        }
+    }
+
+    /// Collect the paths of this tracer and all its children
+    pub fn collect_paths(&self, paths: &mut HashSet<String>) {
+        paths.insert(self.get_path().to_owned());
+        for child in self.children() {
+            child.collect_paths(paths);
+        }
+    }
+
+    /// Collect the paths of the values that were not yet traced
+    pub fn collect_incomplete_paths(&self, paths: &mut Vec<String>) {
+        if let Tracer::Unknown(tracer) = self {
+            if !tracer.deferred {
+                paths.push(tracer.path.clone());
+            }
+        }
+        for child in self.children() {
+            child.collect_incomplete_paths(paths);
+        }
+    }
+
+    /// Collect the statistics of this tracer and all its children
+    pub fn collect_statistics(&self, statistics: &mut TracingStatistics) {
+        let strings = match self {
+            Tracer::Primitive(tracer) => Some(&tracer.strings),
+            _ => None,
+        };
+        let stats = self.get_statistics().to_statistics(strings);
+        statistics.fields.insert(self.get_path().to_owned(), stats);
+
+        if let Tracer::Primitive(tracer) = self {
+            if tracer.is_dropped() {
+                statistics.dropped_fields.push(tracer.path.clone());
+            }
+        }
+
+        for child in self.children() {
+            child.collect_statistics(statistics);
+        }
+    }
+
+    /// Find the tracer with the given path among this tracer and its children
+    pub fn find(&self, path: &str) -> Option<&Tracer> {
+        if self.get_path() == path {
+            return Some(self);
+        }
+        if !path.starts_with(self.get_path()) {
+            return None;
+        }
+        self.children()
+            .into_iter()
+            .find_map(|child| child.find(path))
+    }
+
+    /// Replace the deferred non self describing values with the tracers of
+    /// the samples at the same path
+    pub fn graft_samples(&mut self, samples: &Tracer) {
+        if let Tracer::Unknown(tracer) = self {
+            if !tracer.deferred {
+                return;
+            }
+            let Some(sample_tracer) = samples.find(&tracer.path) else {
+                return;
+            };
+            let nullable = tracer.nullable || sample_tracer.get_nullable();
+            if !sample_tracer.is_unknown() {
+                *self = sample_tracer.clone();
+            }
+            if nullable {
+                self.mark_nullable();
+            }
+            return;
+        }
+        for child in self.children_mut() {
+            child.graft_samples(samples);
+        }
+    }
+
+    pub fn get_statistics(&self) -> &StatisticsCollector {
+        dispatch_tracer!(self, tracer => &tracer.statistics)
+    }
+
+    pub fn get_statistics_mut(&mut self) -> &mut StatisticsCollector {
+        dispatch_tracer!(self, tracer => &mut tracer.statistics)
     }
 
     pub fn get_options(&self) -> &TracingOptions {
@@ -119,7 +275,7 @@ impl Tracer {
     }
 
     pub fn get_depth(&self) -> usize {
-        self.get_path().chars().filter(|c| *c == '.').count()
+        path_depth(self.get_path())
     }
 }
 
@@ -130,8 +286,18 @@ impl Tracer {
     }
 
     pub fn enforce_depth_limit(&self) -> Result<()> {
-        if self.get_depth() >= MAX_TYPE_DEPTH {
-            fail!("{RECURSIVE_TYPE_WARNING}");
+        let max_depth = self.get_options().max_depth;
+        if self.get_depth() >= max_depth {
+            fail!(
+                concat!(
+                    "too deeply nested type detected at {path} (max_depth = {max_depth}). ",
+                    "Recursive types are not supported in schema tracing. ",
+                    "Consider setting `depth_limit` in `TracingOptions` to cut off ",
+                    "deeper values or increasing `max_depth`.",
+                ),
+                path = self.get_path(),
+                max_depth = max_depth,
+            );
         }
         Ok(())
     }
@@ -170,6 +336,7 @@ impl Tracer {
                     mode: StructMode::Struct,
                     state: StructTracerState::WaitForKey,
                     seen_samples: 0,
+                    statistics: StatisticsCollector::default(),
                 };
                 *this = Self::Struct(tracer);
                 Ok(())
@@ -203,6 +370,7 @@ impl Tracer {
                         .collect(),
                     nullable: this.get_nullable(),
                     state: TupleTracerState::WaitForStart,
+                    statistics: StatisticsCollector::default(),
                 };
                 *this = Self::Tuple(tracer);
                 Ok(())
@@ -240,6 +408,7 @@ impl Tracer {
                         })
                         .collect(),
                     nullable: this.get_nullable(),
+                    statistics: StatisticsCollector::default(),
                 };
                 *this = Self::Union(tracer);
                 Ok(())
@@ -269,6 +438,7 @@ impl Tracer {
                         this.get_options().clone(),
                     )),
                     state: ListTracerState::WaitForStart,
+                    statistics: StatisticsCollector::default(),
                 };
                 *this = Self::List(tracer);
                 Ok(())
@@ -299,6 +469,7 @@ impl Tracer {
                         this.get_options().clone(),
                     )),
                     state: MapTracerState::WaitForKey,
+                    statistics: StatisticsCollector::default(),
                 };
                 *this = Self::Map(tracer);
                 Ok(())
@@ -318,7 +489,7 @@ impl Tracer {
             let tracer = PrimitiveTracer::new(
                 self.get_path().to_owned(),
                 self.get_options().clone(),
-                GenericDataType::LargeUtf8,
+                self.get_options().string_type(),
                 self.get_nullable(),
             );
             *self = Self::Primitive(tracer);
@@ -346,7 +517,7 @@ impl Tracer {
                 "mismatched types, previous {:?} with strategy {:?}, current {:?}",
                 item_type,
                 strategy,
-                GenericDataType::LargeUtf8
+                self.get_options().string_type()
             );
         }
 
@@ -407,6 +578,15 @@ pub struct UnknownTracer {
     pub options: TracingOptions,
     pub nullable: bool,
     pub state: UnknownTracerState,
+    /// Whether the value is not traced: either a non self describing value
+    /// was encountered in `from_type`, the value is stored as JSON or it is
+    /// beyond the depth limit
+    pub deferred: bool,
+    /// The nesting inside the current untraced value: the depth and whether a
+    /// variant marker was seen at the outer level
+    pub untraced_nesting: (usize, bool),
+    /// The statistics of the observed values, see [`TracingStatistics`]
+    pub statistics: StatisticsCollector,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -422,13 +602,62 @@ impl UnknownTracer {
             options,
             nullable: false,
             state: UnknownTracerState::Unfinished,
+            deferred: false,
+            untraced_nesting: (0, false),
+            statistics: StatisticsCollector::default(),
+        }
+    }
+
+    /// Accept an event of a value that is not traced, e.g., as it is stored
+    /// as JSON
+    ///
+    /// Only the nullability of the outer value is recorded.
+    pub fn accept_untraced(&mut self, event: &Event<'_>) {
+        self.deferred = true;
+
+        let (depth, in_variant) = self.untraced_nesting;
+        if depth == 0 && !in_variant {
+            match event {
+                Event::Some => self.nullable = true,
+                Event::Null => {
+                    self.nullable = true;
+                    self.statistics.observe_null();
+                }
+                _ => {}
+            }
         }
+
+        self.untraced_nesting = match event {
+            ev if ev.is_start() => (depth + 1, in_variant),
+            ev if ev.is_end() => match depth.saturating_sub(1) {
+                0 => (0, false),
+                depth => (depth, in_variant),
+            },
+            Event::Variant(_, _) | Event::OwnedVariant(_, _) if depth == 0 => (0, true),
+            ev if ev.is_marker() => (depth, in_variant),
+            _ if depth == 0 => (0, false),
+            _ => (depth, in_variant),
+        };
     }
 
     pub fn to_field(&self, name: &str) -> Result<GenericField> {
         if !matches!(self.state, UnknownTracerState::Finished) {
             fail!("Cannot build field {name} from unfinished tracer");
         }
+        if self.deferred {
+            let field = GenericField::new(name, self.options.string_type(), self.nullable);
+            let Some(fallback) = &self.options.any_fallback else {
+                fail!(
+                    concat!(
+                        "No samples for the non self describing value at {path}. ",
+                        "Consider including the field in the samples or setting ",
+                        "`any_fallback` in `TracingOptions`.",
+                    ),
+                    path = self.path,
+                );
+            };
+            return fallback.apply(&self.path, field);
+        }
         if !self.options.allow_null_fields {
             fail!(concat!(
                 "Encountered null only or unknown field. This error can be ",
@@ -466,7 +695,7 @@ impl UnknownTracer {
     }
 
     pub fn is_complete(&self) -> bool {
-        false
+        self.deferred
     }
 
     pub fn get_type(&self) -> Option<&GenericDataType> {
@@ -482,6 +711,8 @@ pub struct MapTracer {
     pub key_tracer: Box<Tracer>,
     pub value_tracer: Box<Tracer>,
     pub state: MapTracerState,
+    /// The statistics of the observed values, see [`TracingStatistics`]
+    pub statistics: StatisticsCollector,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -499,10 +730,11 @@ impl MapTracer {
         Self {
             nullable,
             options: options.clone(),
-            key_tracer: Box::new(Tracer::new(format!("{path}.$key"), options.clone())),
-            value_tracer: Box::new(Tracer::new(format!("{path}.$value"), options)),
+            key_tracer: Box::new(Tracer::new(format!("{path}.key"), options.clone())),
+            value_tracer: Box::new(Tracer::new(format!("{path}.value"), options)),
             state: MapTracerState::WaitForKey,
             path,
+            statistics: StatisticsCollector::default(),
         }
     }
 
@@ -571,6 +803,8 @@ pub struct ListTracer {
     pub nullable: bool,
     pub item_tracer: Box<Tracer>,
     pub state: ListTracerState,
+    /// The statistics of the observed values, see [`TracingStatistics`]
+    pub statistics: StatisticsCollector,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -584,11 +818,12 @@ pub enum ListTracerState {
 impl ListTracer {
     pub fn new(path: String, options: TracingOptions, nullable: bool) -> Self {
         Self {
-            path: path.clone(),
-            options: options.clone(),
-            item_tracer: Box::new(Tracer::new(path, options)),
+            item_tracer: Box::new(Tracer::new(format!("{path}.item"), options.clone())),
+            path,
+            options,
             nullable,
             state: ListTracerState::WaitForStart,
+            statistics: StatisticsCollector::default(),
         }
     }
 
@@ -605,7 +840,7 @@ impl ListTracer {
     }
 
     pub fn get_type(&self) -> Option<&GenericDataType> {
-        Some(&GenericDataType::LargeList)
+        Some(self.options.list_type())
     }
 
     pub fn to_field(&self, name: &str) -> Result<GenericField> {
@@ -613,7 +848,7 @@ impl ListTracer {
             fail!("Cannot build field {name} from unfinished tracer");
         }
 
-        let mut field = GenericField::new(name, GenericDataType::LargeList, self.nullable);
+        let mut field = GenericField::new(name, self.options.list_type().clone(), self.nullable);
         field.children.push(self.item_tracer.to_field("element")?);
 
         Ok(field)
@@ -647,6 +882,8 @@ pub struct TupleTracer {
     pub nullable: bool,
     pub field_tracers: Vec<Tracer>,
     pub state: TupleTracerState,
+    /// The statistics of the observed values, see [`TracingStatistics`]
+    pub statistics: StatisticsCollector,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -667,6 +904,7 @@ impl TupleTracer {
             field_tracers: Vec::new(),
             nullable,
             state: TupleTracerState::WaitForStart,
+            statistics: StatisticsCollector::default(),
         }
     }
 
@@ -746,6 +984,8 @@ pub struct StructTracer {
     pub state: StructTracerState,
     /// Count how many samples were seen by this tracer
     pub seen_samples: usize,
+    /// The statistics of the observed values, see [`TracingStatistics`]
+    pub statistics: StatisticsCollector,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -846,6 +1086,8 @@ pub struct UnionTracer {
     pub nullable: bool,
     pub variants: Vec<Option<UnionVariant>>,
     pub state: UnionTracerState,
+    /// The statistics of the observed values, see [`TracingStatistics`]
+    pub statistics: StatisticsCollector,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -871,6 +1113,7 @@ impl UnionTracer {
             variants: Vec::new(),
             nullable,
             state: UnionTracerState::WaitForVariant,
+            statistics: StatisticsCollector::default(),
         }
     }
 
@@ -982,6 +1225,13 @@ pub struct PrimitiveTracer {
     pub state: PrimitiveTracerState,
     /// Count how many samples were seen by this tracer
     pub seen_samples: usize,
+    /// The types observed after a type conflict, see
+    /// [`ConflictingTypes`][crate::schema::ConflictingTypes]
+    pub conflicting_types: Vec<GenericDataType>,
+    /// The distinct string values, only tracked if required by the options
+    pub strings: StringCounter,
+    /// The statistics of the observed values, see [`TracingStatistics`]
+    pub statistics: StatisticsCollector,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -1005,6 +1255,9 @@ impl PrimitiveTracer {
             strategy: None,
             state: PrimitiveTracerState::Unfinished,
             seen_samples: 0,
+            conflicting_types: Vec::new(),
+            strings: StringCounter::default(),
+            statistics: StatisticsCollector::default(),
         }
     }
 
@@ -1028,6 +1281,10 @@ impl PrimitiveTracer {
             fail!("Cannot build field {name} from unfinished tracer");
         }
 
+        if matches!(self.strategy, Some(Strategy::InconsistentTypes)) {
+            return self.to_conflicting_field(name);
+        }
+
         if !self.options.allow_null_fields && matches!(self.item_type, D::Null) {
             fail!(
                 concat!(
@@ -1040,14 +1297,19 @@ impl PrimitiveTracer {
 
         match &self.item_type {
             D::Null => Ok(GenericField::new(name, D::Null, true)),
-            dt @ (D::LargeUtf8 | D::Utf8) => {
-                if !self.options.string_dictionary_encoding {
-                    Ok(GenericField::new(name, dt.clone(), self.nullable))
-                } else {
+            D::LargeUtf8 | D::Utf8 => {
+                let dt = self.options.string_type();
+                if let Some(key_type) = self.get_dictionary_key_type() {
                     let field = GenericField::new(name, D::Dictionary, self.nullable)
-                        .with_child(GenericField::new("key", D::U32, self.nullable))
-                        .with_child(GenericField::new("value", dt.clone(), false));
+                        .with_child(GenericField::new("key", key_type, self.nullable))
+                        .with_child(
+                            GenericField::new("value", dt, false)
+                                .with_optional_strategy(self.strategy.clone()),
+                        );
                     Ok(field)
+                } else {
+                    Ok(GenericField::new(name, dt, self.nullable)
+                        .with_optional_strategy(self.strategy.clone()))
                 }
             }
             dt => Ok(GenericField::new(name, dt.clone(), self.nullable)
@@ -1057,6 +1319,71 @@ impl PrimitiveTracer {
 }
 
 impl PrimitiveTracer {
+    /// Determine the index type, if string values are dictionary encoded
+    fn get_dictionary_key_type(&self) -> Option<GenericDataType> {
+        let Some(threshold) = self.options.string_dictionary_threshold else {
+            return self
+                .options
+                .string_dictionary_encoding
+                .then_some(GenericDataType::U32);
+        };
+
+        let distinct = self.strings.distinct();
+        if !threshold.is_satisfied(self.strings.values, distinct) {
+            return None;
+        }
+
+        if distinct <= usize::from(u8::MAX) + 1 {
+            Some(GenericDataType::U8)
+        } else if distinct <= usize::from(u16::MAX) + 1 {
+            Some(GenericDataType::U16)
+        } else if u32::try_from(distinct - 1).is_ok() {
+            Some(GenericDataType::U32)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the field is dropped, as its values have conflicting types
+    fn is_dropped(&self) -> bool {
+        matches!(self.strategy, Some(Strategy::InconsistentTypes))
+            && self.options.conflicting_types == ConflictingTypes::Drop
+    }
+
+    fn to_conflicting_field(&self, name: &str) -> Result<GenericField> {
+        type D = GenericDataType;
+
+        let field = match self.options.conflicting_types {
+            ConflictingTypes::Fail => fail!(
+                "Field {name} has conflicting types {types:?}",
+                types = self.conflicting_types
+            ),
+            ConflictingTypes::Stringify => {
+                GenericField::new(name, self.options.string_type(), self.nullable)
+            }
+            ConflictingTypes::Drop => GenericField::new(name, D::Null, true),
+            ConflictingTypes::Union => {
+                let mut field = GenericField::new(name, D::Union, false);
+                for data_type in &self.conflicting_types {
+                    let data_type = match data_type {
+                        D::LargeUtf8 => self.options.string_type(),
+                        data_type => data_type.clone(),
+                    };
+                    field = field.with_child(GenericField::new(
+                        &data_type.to_string(),
+                        data_type,
+                        false,
+                    ));
+                }
+                if self.nullable {
+                    field = field.with_child(GenericField::new("Null", D::Null, true));
+                }
+                field
+            }
+        };
+        Ok(field.with_strategy(Strategy::InconsistentTypes))
+    }
+
     pub fn get_path(&self) -> &str {
         &self.path
     }
@@ -166,6 +166,14 @@
 //! | `arrow-37`    | `arrow=37`    |
 //! | `arrow2-0-17` | `arrow2=0.17` |
 //! | `arrow2-0-16` | `arrow2=0.16` |
+//!
+//! Further features:
+//!
+//! - `rayon`: build arrays and deserialize them on multiple threads
+//!   (`to_arrow_parallel`, `to_arrow2_parallel`, `from_arrow_parallel`,
+//!   `from_arrow2_parallel`)
+//! - `json`: store arbitrary values as JSON strings with
+//!   [`Strategy::JsonAsUtf8`][crate::schema::Strategy::JsonAsUtf8]
 
 // be more forgiving without any active implementation
 #[cfg_attr(all(not(has_arrow), not(has_arrow2)), allow(unused))]
@@ -214,8 +222,8 @@ pub mod _impl {
                 }
                 pub mod datatypes {
                     pub use $arrow_array::types::{
-                        ArrowPrimitiveType, Date64Type, Decimal128Type, Float16Type, Float32Type, Float64Type,
-                        Int16Type, Int32Type, Int64Type, Int8Type, TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType, TimestampSecondType, UInt16Type, UInt32Type,
+                        ArrowPrimitiveType, Date32Type, Date64Type, Decimal128Type, Float16Type, Float32Type, Float64Type,
+                        Int16Type, Int32Type, Int64Type, Int8Type, TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType, TimestampSecondType, Time64MicrosecondType, Time64NanosecondType, UInt16Type, UInt32Type,
                         UInt64Type, UInt8Type,
                     };
                     pub use $arrow_buffer::ArrowNativeType;
@@ -281,13 +289,25 @@ pub use crate::internal::error::{Error, Result};
 mod arrow_impl;
 
 #[cfg(has_arrow)]
-pub use arrow_impl::api::{from_arrow, to_arrow, ArrowBuilder};
+pub use arrow_impl::api::{
+    from_arrow, from_arrow_columns, from_arrow_iter, from_arrow_range, from_arrow_row,
+    from_arrow_with_defaults, to_arrow, to_arrow_columns, ArrowBuilder,
+};
+
+#[cfg(all(has_arrow, feature = "rayon"))]
+pub use arrow_impl::api::{from_arrow_parallel, to_arrow_parallel};
 
 #[cfg(has_arrow2)]
 mod arrow2_impl;
 
 #[cfg(has_arrow2)]
-pub use arrow2_impl::api::{from_arrow2, to_arrow2, Arrow2Builder};
+pub use arrow2_impl::api::{
+    from_arrow2, from_arrow2_columns, from_arrow2_iter, from_arrow2_range, from_arrow2_row,
+    from_arrow2_with_defaults, to_arrow2, to_arrow2_columns, Arrow2Builder,
+};
+
+#[cfg(all(has_arrow2, feature = "rayon"))]
+pub use arrow2_impl::api::{from_arrow2_parallel, to_arrow2_parallel};
 
 #[deny(missing_docs)]
 pub mod schema;
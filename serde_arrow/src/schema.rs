@@ -15,8 +15,10 @@
 //! - Integers (`u8`, .., `u64`, `i8`, .., `i64`): `UInt8`, .., `Uint64`,
 //!   `Int8`, .. `UInt64`
 //! - Floats (`f32`, `f64`): `Float32`, `Float64`
-//! - Strings (`str`, `String`, ..): `LargeUtf8` with i64 offsets
-//! - Sequences: `LargeList` with i64 offsets
+//! - Strings (`str`, `String`, ..): `LargeUtf8` with i64 offsets, or `Utf8`
+//!   with [`TracingOptions::large_utf8`] set to `false`
+//! - Sequences: `LargeList` with i64 offsets, or `List` with
+//!   [`TracingOptions::large_list`] set to `false`
 //! - Structs / Map / Tuples: `Struct` type
 //! - Enums: dense Unions. Each variant is mapped to a separate field. Its type
 //!   depends on the union type: Field-less variants are mapped to `NULL`. New
@@ -41,7 +43,11 @@
 //! ```
 pub use crate::internal::{
     schema::{SchemaLike, SerdeArrowSchema, Strategy, STRATEGY_KEY},
-    tracing::TracingOptions,
+    tracing::{
+        detectors::StringDetector,
+        statistics::{FieldStatistics, StatisticsValue, TracingStatistics},
+        ConflictingTypes, DepthLimit, DictionaryThreshold, TracingOptions,
+    },
 };
 
 /// Renamed to [`SerdeArrowSchema`]
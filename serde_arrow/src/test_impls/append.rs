@@ -0,0 +1,177 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    _impl::arrow,
+    schema::{SchemaLike, SerdeArrowSchema},
+    utils::Item,
+    Arrow2Builder, ArrowBuilder,
+};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Value {
+    Int(i32),
+    Str(String),
+    Empty,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record {
+    category: Option<String>,
+    tags: Vec<String>,
+    value: Value,
+}
+
+fn schema() -> SerdeArrowSchema {
+    SerdeArrowSchema::from_value(&json!([
+        {
+            "name": "category",
+            "data_type": "Dictionary",
+            "nullable": true,
+            "children": [
+                {"name": "key", "data_type": "U8"},
+                {"name": "value", "data_type": "LargeUtf8"},
+            ],
+        },
+        {
+            "name": "tags",
+            "data_type": "List",
+            "children": [{"name": "element", "data_type": "LargeUtf8"}],
+        },
+        {
+            "name": "value",
+            "data_type": "Union",
+            "children": [
+                {"name": "Int", "data_type": "I32"},
+                {"name": "Str", "data_type": "LargeUtf8"},
+                {"name": "Empty", "data_type": "Null"},
+            ],
+        },
+    ]))
+    .unwrap()
+}
+
+fn records() -> Vec<Record> {
+    vec![
+        Record {
+            category: Some(String::from("a")),
+            tags: vec![String::from("x")],
+            value: Value::Int(1),
+        },
+        Record {
+            category: None,
+            tags: vec![],
+            value: Value::Empty,
+        },
+        Record {
+            category: Some(String::from("b")),
+            tags: vec![String::from("y"), String::from("z")],
+            value: Value::Str(String::from("foo")),
+        },
+        Record {
+            category: Some(String::from("c")),
+            tags: vec![String::from("x")],
+            value: Value::Int(2),
+        },
+        Record {
+            category: Some(String::from("a")),
+            tags: vec![],
+            value: Value::Str(String::from("bar")),
+        },
+    ]
+}
+
+#[test]
+fn arrow_append_remaps_dictionary_and_offsets() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let records = records();
+
+    for split in 0..=records.len() {
+        let mut builder = ArrowBuilder::new(&fields).unwrap();
+        let mut other = ArrowBuilder::new(&fields).unwrap();
+        builder.extend(&records[..split]).unwrap();
+        other.extend(&records[split..]).unwrap();
+        builder.append(other).unwrap();
+
+        let arrays = builder.build_arrays().unwrap();
+        let actual: Vec<Record> = crate::from_arrow(&fields, &arrays).unwrap();
+        assert_eq!(actual, records);
+    }
+}
+
+#[test]
+fn arrow2_append_remaps_dictionary_and_offsets() {
+    let fields = schema().to_arrow2_fields().unwrap();
+    let records = records();
+
+    for split in 0..=records.len() {
+        let mut builder = Arrow2Builder::new(&fields).unwrap();
+        let mut other = Arrow2Builder::new(&fields).unwrap();
+        builder.extend(&records[..split]).unwrap();
+        other.extend(&records[split..]).unwrap();
+        builder.append(other).unwrap();
+
+        let arrays = builder.build_arrays().unwrap();
+        let actual: Vec<Record> = crate::from_arrow2(&fields, &arrays).unwrap();
+        assert_eq!(actual, records);
+    }
+}
+
+#[test]
+fn append_builders_with_different_fields_fails() {
+    let fields = vec![arrow::datatypes::Field::new(
+        "item",
+        arrow::datatypes::DataType::Int32,
+        false,
+    )];
+    let other_fields = vec![arrow::datatypes::Field::new(
+        "item",
+        arrow::datatypes::DataType::Int64,
+        false,
+    )];
+
+    let mut builder = ArrowBuilder::new(&fields).unwrap();
+    let mut other = ArrowBuilder::new(&other_fields).unwrap();
+    builder.push(&Item(1_i32)).unwrap();
+    other.push(&Item(2_i64)).unwrap();
+
+    let Err(err) = builder.append(other) else {
+        panic!("expected an error");
+    };
+    assert!(err
+        .to_string()
+        .contains("Cannot append builder I64 to builder I32"));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn to_arrow_parallel() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let records = (0..100).flat_map(|_| records()).collect::<Vec<_>>();
+
+    let expected = crate::to_arrow(&fields, &records).unwrap();
+    let actual = crate::to_arrow_parallel(&fields, &records).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn to_arrow2_parallel() {
+    let fields = schema().to_arrow2_fields().unwrap();
+    let records = (0..100).flat_map(|_| records()).collect::<Vec<_>>();
+
+    let expected = crate::to_arrow2(&fields, &records).unwrap();
+    let actual = crate::to_arrow2_parallel(&fields, &records).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_empty_input() {
+    let fields = schema().to_arrow2_fields().unwrap();
+    let records: Vec<Record> = Vec::new();
+
+    let arrays = crate::to_arrow2_parallel(&fields, &records).unwrap();
+    assert_eq!(arrays.len(), 3);
+    assert!(arrays.iter().all(|array| array.is_empty()));
+}
@@ -0,0 +1,170 @@
+//! Test that strings are borrowed from the arrays without copying
+use std::{borrow::Cow, collections::BTreeMap};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    schema::{SchemaLike, SerdeArrowSchema},
+    utils::Items,
+};
+
+#[derive(Debug, Deserialize)]
+struct Record<'a> {
+    a: &'a str,
+    // NOTE: serde borrows implicitly only for fields of type `&str` and
+    // `&[u8]`, `Cow` and references nested in other types require
+    // `#[serde(borrow)]`
+    #[serde(borrow)]
+    b: Cow<'a, str>,
+    #[serde(borrow)]
+    c: Option<&'a str>,
+    #[serde(borrow)]
+    d: Vec<&'a str>,
+    #[serde(borrow)]
+    e: BTreeMap<&'a str, &'a str>,
+    f: &'a [u8],
+    #[serde(borrow)]
+    g: Cow<'a, [u8]>,
+}
+
+/// The serialized form of `Record`, as binary arrays are not yet supported
+#[derive(Debug, Serialize)]
+struct Source {
+    a: &'static str,
+    b: &'static str,
+    c: Option<&'static str>,
+    d: Vec<&'static str>,
+    e: BTreeMap<&'static str, &'static str>,
+    f: &'static str,
+    g: &'static str,
+}
+
+fn items() -> Vec<Source> {
+    (0..4)
+        .map(|i| Source {
+            a: "a",
+            b: "b",
+            c: if i % 2 == 0 { Some("c") } else { None },
+            d: vec!["d"; i],
+            e: (0..i).map(|_| ("k", "e")).collect(),
+            f: "f",
+            g: "g",
+        })
+        .collect()
+}
+
+fn schema(string_type: &serde_json::Value) -> SerdeArrowSchema {
+    let field = |name: &str, nullable: bool| {
+        let mut field = string_type.clone();
+        field["name"] = json!(name);
+        field["nullable"] = json!(nullable);
+        field
+    };
+
+    SerdeArrowSchema::from_value(&json!([
+        field("a", false),
+        field("b", false),
+        field("c", true),
+        {"name": "d", "data_type": "LargeList", "children": [field("element", false)]},
+        {
+            "name": "e",
+            "data_type": "Map",
+            "children": [{
+                "name": "entries",
+                "data_type": "Struct",
+                "children": [field("key", false), field("value", false)],
+            }],
+        },
+        field("f", false),
+        field("g", false),
+    ]))
+    .unwrap()
+}
+
+fn string_types() -> Vec<serde_json::Value> {
+    let dictionary = |value_type: &str| {
+        json!({
+            "data_type": "Dictionary",
+            "children": [
+                {"name": "key", "data_type": "U32"},
+                {"name": "value", "data_type": value_type},
+            ],
+        })
+    };
+
+    vec![
+        json!({"data_type": "Utf8"}),
+        json!({"data_type": "LargeUtf8"}),
+        dictionary("Utf8"),
+        dictionary("LargeUtf8"),
+    ]
+}
+
+fn assert_borrowed(records: &[Record<'_>]) {
+    for record in records {
+        assert_eq!(record.a, "a");
+        assert!(matches!(record.b, Cow::Borrowed("b")), "{:?}", record.b);
+        assert!(matches!(record.c, None | Some("c")), "{:?}", record.c);
+        assert!(record.d.iter().all(|d| *d == "d"));
+        for (key, value) in &record.e {
+            assert_eq!((*key, *value), ("k", "e"));
+        }
+        assert_eq!(record.f, b"f");
+        assert!(matches!(record.g, Cow::Borrowed(b"g")), "{:?}", record.g);
+    }
+}
+
+#[test]
+fn arrow_strings_are_borrowed() {
+    for string_type in string_types() {
+        let fields = schema(&string_type).to_arrow_fields().unwrap();
+        let arrays = crate::to_arrow(&fields, &items()).unwrap();
+
+        let actual: Vec<Record> = crate::from_arrow(&fields, &arrays).unwrap();
+        assert_borrowed(&actual);
+
+        let actual = crate::from_arrow_iter::<Record, _>(&fields, &arrays)
+            .unwrap()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_borrowed(&actual);
+    }
+}
+
+#[test]
+fn arrow2_strings_are_borrowed() {
+    for string_type in string_types() {
+        let fields = schema(&string_type).to_arrow2_fields().unwrap();
+        let arrays = crate::to_arrow2(&fields, &items()).unwrap();
+
+        let actual: Vec<Record> = crate::from_arrow2(&fields, &arrays).unwrap();
+        assert_borrowed(&actual);
+
+        let actual: Record = crate::from_arrow2_row(&fields, &arrays, 2).unwrap();
+        assert_borrowed(&[actual]);
+    }
+}
+
+#[test]
+fn borrowed_strings_point_into_the_arrays() {
+    let fields = SerdeArrowSchema::from_value(&json!([{"name": "item", "data_type": "LargeUtf8"}]))
+        .unwrap()
+        .to_arrow2_fields()
+        .unwrap();
+    let arrays = crate::to_arrow2(&fields, &Items(["foo", "bar"])).unwrap();
+
+    let values = arrays[0]
+        .as_any()
+        .downcast_ref::<crate::_impl::arrow2::array::Utf8Array<i64>>()
+        .unwrap()
+        .values()
+        .as_slice()
+        .as_ptr_range();
+
+    let Items(actual): Items<Vec<&str>> = crate::from_arrow2(&fields, &arrays).unwrap();
+    assert_eq!(actual, ["foo", "bar"]);
+    for item in actual {
+        assert!(values.contains(&item.as_ptr()));
+    }
+}
@@ -1,5 +1,8 @@
-use super::utils::Test;
-use crate::{schema::TracingOptions, utils::Item};
+use super::utils::{deserialize_timestamps, Test};
+use crate::{
+    schema::{SchemaLike, SerdeArrowSchema, TracingOptions},
+    utils::Item,
+};
 
 use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
@@ -263,3 +266,149 @@ fn incompatible_date_formats_tracing() {
         .deserialize(&items)
         .check_nulls(&[&[false, false]]);
 }
+
+#[test]
+fn timestamp_units_as_chrono_types() {
+    let expected = [
+        Utc.with_ymd_and_hms(2020, 12, 24, 8, 30, 0).unwrap(),
+        Utc.with_ymd_and_hms(1950, 5, 5, 16, 6, 0).unwrap(),
+    ];
+    let seconds = expected.map(|dt| dt.timestamp());
+
+    // strings are formatted with timezone, if the field defines one
+    for (data_type, factor) in [
+        ("Timestamp(Second, Some(\"UTC\"))", 1),
+        ("Timestamp(Millisecond, Some(\"UTC\"))", 1_000),
+        ("Timestamp(Nanosecond, Some(\"+01:00\"))", 1_000_000_000),
+    ] {
+        let field = json!({"name": "item", "data_type": data_type});
+        let values = seconds.map(|s| s * factor);
+        deserialize_timestamps(field, &values, &expected.map(Item));
+    }
+    for (data_type, factor) in [
+        ("Date64", 1_000),
+        ("Timestamp(Second, None)", 1),
+        ("Timestamp(Microsecond, None)", 1_000_000),
+    ] {
+        let field = json!({"name": "item", "data_type": data_type});
+        let values = seconds.map(|s| s * factor);
+        deserialize_timestamps(field, &values, &expected.map(|dt| Item(dt.naive_utc())));
+    }
+
+    // integers are passed in the unit of the field
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Seconds {
+        #[serde(with = "chrono::serde::ts_seconds")]
+        item: DateTime<Utc>,
+    }
+    deserialize_timestamps(
+        json!({"name": "item", "data_type": "Timestamp(Second, Some(\"UTC\"))"}),
+        &seconds,
+        &expected.map(|item| Seconds { item }),
+    );
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Milliseconds {
+        #[serde(with = "chrono::serde::ts_milliseconds")]
+        item: DateTime<Utc>,
+    }
+    deserialize_timestamps(
+        json!({"name": "item", "data_type": "Date64"}),
+        &seconds.map(|s| s * 1_000),
+        &expected.map(|item| Milliseconds { item }),
+    );
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Microseconds {
+        #[serde(with = "chrono::naive::serde::ts_microseconds")]
+        item: NaiveDateTime,
+    }
+    deserialize_timestamps(
+        json!({"name": "item", "data_type": "Timestamp(Microsecond, None)"}),
+        &seconds.map(|s| s * 1_000_000),
+        &expected.map(|item| Microseconds {
+            item: item.naive_utc(),
+        }),
+    );
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Nanoseconds {
+        #[serde(with = "chrono::serde::ts_nanoseconds_option")]
+        item: Option<DateTime<Utc>>,
+    }
+    deserialize_timestamps(
+        json!({"name": "item", "data_type": "Timestamp(Nanosecond, Some(\"UTC\"))"}),
+        &seconds.map(|s| s * 1_000_000_000),
+        &expected.map(|item| Nanoseconds { item: Some(item) }),
+    );
+}
+
+#[test]
+fn timestamp_strategies_with_chrono_types() {
+    let expected = [Utc.with_ymd_and_hms(2023, 5, 5, 16, 6, 0).unwrap()];
+    let values = [expected[0].timestamp_millis()];
+
+    // for Date64 fields the strategy determines the timezone
+    let field = json!({"name": "item", "data_type": "Date64", "strategy": "UtcStrAsDate64"});
+    deserialize_timestamps(field, &values, &expected.map(Item));
+
+    let field = json!({"name": "item", "data_type": "Date64", "strategy": "NaiveStrAsDate64"});
+    deserialize_timestamps(field, &values, &expected.map(|dt| Item(dt.naive_utc())));
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct T {
+        #[serde(with = "chrono::serde::ts_milliseconds")]
+        item: DateTime<Utc>,
+    }
+    for strategy in ["UtcStrAsDate64", "NaiveStrAsDate64"] {
+        let field = json!({"name": "item", "data_type": "Date64", "strategy": strategy});
+        deserialize_timestamps(field, &values, &expected.map(|item| T { item }));
+    }
+}
+
+#[test]
+fn timestamps_with_mismatched_timezones() {
+    let schema = SerdeArrowSchema::from_value(&json!([
+        {"name": "item", "data_type": "Date64"},
+    ]))
+    .unwrap();
+    let fields = schema.to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, &[Item(0_i64)]).unwrap();
+
+    let res = crate::from_arrow::<Vec<Item<DateTime<Utc>>>, _>(&fields, &arrays);
+    assert!(res.is_err());
+}
+
+#[test]
+fn timestamps_with_sub_second_precision() {
+    let expected = [
+        Utc.timestamp_opt(-1, 500_000_000).unwrap(),
+        Utc.timestamp_opt(1_700_000_000, 123_456_789).unwrap(),
+    ];
+
+    let field = json!({"name": "item", "data_type": "Timestamp(Nanosecond, None)"});
+    let values = expected.map(|dt| dt.timestamp_nanos_opt().unwrap());
+    deserialize_timestamps(field, &values, &expected.map(|dt| Item(dt.naive_utc())));
+
+    let field = json!({"name": "item", "data_type": "Date64"});
+    let values = expected.map(|dt| dt.timestamp_millis());
+    let expected = [
+        Utc.timestamp_opt(-1, 500_000_000).unwrap(),
+        Utc.timestamp_opt(1_700_000_000, 123_000_000).unwrap(),
+    ];
+    deserialize_timestamps(field, &values, &expected.map(|dt| Item(dt.naive_utc())));
+    deserialize_timestamps(
+        json!({"name": "item", "data_type": "Date64"}),
+        &values,
+        &["1969-12-31T23:59:59.500", "2023-11-14T22:13:20.123"].map(|s| Item(s.to_owned())),
+    );
+}
+
+#[test]
+fn timestamps_in_self_describing_types() {
+    let field = json!({"name": "item", "data_type": "Timestamp(Second, Some(\"UTC\"))"});
+    deserialize_timestamps(field, &[0], &[json!({"item": 0})]);
+
+    let field = json!({"name": "item", "data_type": "Date64", "strategy": "UtcStrAsDate64"});
+    deserialize_timestamps(field, &[0], &[json!({"item": "1970-01-01T00:00:00Z"})]);
+}
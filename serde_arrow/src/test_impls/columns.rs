@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::schema::{SchemaLike, SerdeArrowSchema, TracingOptions};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record {
+    id: u64,
+    name: String,
+    tags: Vec<String>,
+    score: Option<f32>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Columns {
+    id: Vec<u64>,
+    name: Vec<String>,
+    tags: Vec<Vec<String>>,
+    score: Vec<Option<f32>>,
+}
+
+fn records() -> Vec<Record> {
+    (0..5)
+        .map(|i| Record {
+            id: i,
+            name: format!("item {i}"),
+            tags: vec![String::from("tag"); i as usize % 3],
+            score: if i % 2 == 0 { Some(i as f32) } else { None },
+        })
+        .collect()
+}
+
+fn columns() -> Columns {
+    let records = records();
+    Columns {
+        id: records.iter().map(|r| r.id).collect(),
+        name: records.iter().map(|r| r.name.clone()).collect(),
+        tags: records.iter().map(|r| r.tags.clone()).collect(),
+        score: records.iter().map(|r| r.score).collect(),
+    }
+}
+
+fn schema() -> SerdeArrowSchema {
+    SerdeArrowSchema::from_type::<Record>(TracingOptions::default()).unwrap()
+}
+
+#[test]
+fn arrow_columns_match_rows() {
+    let fields = schema().to_arrow_fields().unwrap();
+
+    let from_rows = crate::to_arrow(&fields, &records()).unwrap();
+    let from_columns = crate::to_arrow_columns(&fields, &columns()).unwrap();
+    assert_eq!(from_rows, from_columns);
+
+    let actual: Columns = crate::from_arrow_columns(&fields, &from_rows).unwrap();
+    assert_eq!(actual, columns());
+}
+
+#[test]
+fn arrow2_columns_match_rows() {
+    let fields = schema().to_arrow2_fields().unwrap();
+
+    let from_rows = crate::to_arrow2(&fields, &records()).unwrap();
+    let from_columns = crate::to_arrow2_columns(&fields, &columns()).unwrap();
+    assert_eq!(from_rows, from_columns);
+
+    let actual: Columns = crate::from_arrow2_columns(&fields, &from_rows).unwrap();
+    assert_eq!(actual, columns());
+}
+
+#[test]
+fn columns_in_arbitrary_order_as_maps() {
+    let fields = SerdeArrowSchema::from_value(&json!([
+        {"name": "a", "data_type": "I32"},
+        {"name": "b", "data_type": "I32"},
+    ]))
+    .unwrap()
+    .to_arrow2_fields()
+    .unwrap();
+
+    let mut columns = BTreeMap::new();
+    columns.insert("b", vec![4, 5, 6]);
+    columns.insert("a", vec![1, 2, 3]);
+
+    let arrays = crate::to_arrow2_columns(&fields, &columns).unwrap();
+    let actual: BTreeMap<String, Vec<i32>> = crate::from_arrow2_columns(&fields, &arrays).unwrap();
+
+    let mut expected = BTreeMap::new();
+    expected.insert(String::from("a"), vec![1, 2, 3]);
+    expected.insert(String::from("b"), vec![4, 5, 6]);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn empty_columns() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let empty = Columns {
+        id: vec![],
+        name: vec![],
+        tags: vec![],
+        score: vec![],
+    };
+
+    let arrays = crate::to_arrow_columns(&fields, &empty).unwrap();
+    assert_eq!(arrays[0].len(), 0);
+
+    let actual: Columns = crate::from_arrow_columns(&fields, &arrays).unwrap();
+    assert_eq!(actual, empty);
+}
+
+#[test]
+fn invalid_columns() {
+    let fields = SerdeArrowSchema::from_value(&json!([
+        {"name": "a", "data_type": "I32"},
+        {"name": "b", "data_type": "I32"},
+    ]))
+    .unwrap()
+    .to_arrow_fields()
+    .unwrap();
+
+    let mut columns = BTreeMap::new();
+    columns.insert("a", vec![1, 2, 3]);
+    let err = crate::to_arrow_columns(&fields, &columns).unwrap_err();
+    assert!(
+        err.to_string().contains("Missing column b"),
+        "unexpected error: {err}"
+    );
+
+    columns.insert("b", vec![4, 5]);
+    let err = crate::to_arrow_columns(&fields, &columns).unwrap_err();
+    assert!(
+        err.to_string().contains("Column b has 2 rows, expected 3"),
+        "unexpected error: {err}"
+    );
+
+    #[derive(Serialize)]
+    struct Unknown {
+        a: Vec<i32>,
+        c: Vec<i32>,
+    }
+
+    let columns = Unknown {
+        a: vec![1],
+        c: vec![2],
+    };
+    let err = crate::to_arrow_columns(&fields, &columns).unwrap_err();
+    assert!(
+        err.to_string().contains("Unknown column c"),
+        "unexpected error: {err}"
+    );
+}
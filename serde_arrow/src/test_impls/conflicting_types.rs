@@ -0,0 +1,178 @@
+use serde_json::{json, Value};
+
+use crate::{
+    internal::schema::{GenericDataType, GenericField, Strategy},
+    schema::{ConflictingTypes, SchemaLike, SerdeArrowSchema, TracingOptions},
+};
+
+fn items() -> Vec<Value> {
+    vec![
+        json!({"a": 1, "b": true}),
+        json!({"a": "two", "b": false}),
+        json!({"a": null, "b": true}),
+        json!({"a": 4.5, "b": null}),
+    ]
+}
+
+fn trace(conflicting_types: ConflictingTypes) -> SerdeArrowSchema {
+    let options = TracingOptions::default().conflicting_types(conflicting_types);
+    SerdeArrowSchema::from_samples(&items(), options).unwrap()
+}
+
+fn roundtrip(schema: &SerdeArrowSchema) -> Vec<Value> {
+    let fields = schema.to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, &items()).unwrap();
+    let arrow_items: Vec<Value> = crate::from_arrow(&fields, &arrays).unwrap();
+
+    let fields = schema.to_arrow2_fields().unwrap();
+    let arrays = crate::to_arrow2(&fields, &items()).unwrap();
+    let arrow2_items: Vec<Value> = crate::from_arrow2(&fields, &arrays).unwrap();
+
+    assert_eq!(arrow_items, arrow2_items);
+    arrow_items
+}
+
+#[test]
+fn conflicting_types_fail_by_default() {
+    let err = SerdeArrowSchema::from_samples(&items(), TracingOptions::default()).unwrap_err();
+    assert!(
+        err.to_string().contains("Cannot accept event"),
+        "unexpected error: {err}"
+    );
+}
+
+#[test]
+fn conflicting_types_stringify() {
+    let schema = trace(ConflictingTypes::Stringify);
+    assert_eq!(
+        schema.fields[0],
+        GenericField::new("a", GenericDataType::LargeUtf8, true)
+            .with_strategy(Strategy::InconsistentTypes),
+    );
+    assert_eq!(
+        schema.fields[1],
+        GenericField::new("b", GenericDataType::Bool, true)
+    );
+
+    assert_eq!(
+        roundtrip(&schema),
+        vec![
+            json!({"a": "1", "b": true}),
+            json!({"a": "two", "b": false}),
+            json!({"a": null, "b": true}),
+            json!({"a": "4.5", "b": null}),
+        ],
+    );
+}
+
+#[test]
+fn conflicting_types_union() {
+    let schema = trace(ConflictingTypes::Union);
+    assert_eq!(
+        schema.fields[0],
+        GenericField::new("a", GenericDataType::Union, false)
+            .with_strategy(Strategy::InconsistentTypes)
+            .with_child(GenericField::new("U64", GenericDataType::U64, false))
+            .with_child(GenericField::new(
+                "LargeUtf8",
+                GenericDataType::LargeUtf8,
+                false
+            ))
+            .with_child(GenericField::new("F64", GenericDataType::F64, false))
+            .with_child(GenericField::new("Null", GenericDataType::Null, true)),
+    );
+    assert_eq!(roundtrip(&schema), items());
+}
+
+#[test]
+fn conflicting_types_union_with_coerced_numbers() {
+    let items = vec![json!({"a": 1}), json!({"a": 2.5}), json!({"a": "x"})];
+    let options = TracingOptions::default()
+        .coerce_numbers(true)
+        .conflicting_types(ConflictingTypes::Union);
+    let schema = SerdeArrowSchema::from_samples(&items, options).unwrap();
+    assert_eq!(
+        schema.fields[0],
+        GenericField::new("a", GenericDataType::Union, false)
+            .with_strategy(Strategy::InconsistentTypes)
+            .with_child(GenericField::new("F64", GenericDataType::F64, false))
+            .with_child(GenericField::new(
+                "LargeUtf8",
+                GenericDataType::LargeUtf8,
+                false
+            )),
+    );
+
+    let fields = schema.to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, &items).unwrap();
+    let arrow_items: Vec<Value> = crate::from_arrow(&fields, &arrays).unwrap();
+
+    let fields = schema.to_arrow2_fields().unwrap();
+    let arrays = crate::to_arrow2(&fields, &items).unwrap();
+    let arrow2_items: Vec<Value> = crate::from_arrow2(&fields, &arrays).unwrap();
+
+    let expected = vec![json!({"a": 1.0}), json!({"a": 2.5}), json!({"a": "x"})];
+    assert_eq!(arrow_items, expected);
+    assert_eq!(arrow2_items, expected);
+}
+
+#[test]
+fn conflicting_types_drop() {
+    let schema = trace(ConflictingTypes::Drop);
+    assert_eq!(
+        schema.fields[0],
+        GenericField::new("a", GenericDataType::Null, true)
+            .with_strategy(Strategy::InconsistentTypes),
+    );
+
+    assert_eq!(
+        roundtrip(&schema),
+        vec![
+            json!({"a": null, "b": true}),
+            json!({"a": null, "b": false}),
+            json!({"a": null, "b": true}),
+            json!({"a": null, "b": null}),
+        ],
+    );
+}
+
+#[test]
+fn dropped_fields_are_reported() {
+    let items = vec![
+        json!({"a": 1, "b": {"c": true, "d": 1}}),
+        json!({"a": "two", "b": {"c": "three", "d": 2}}),
+    ];
+    let options = TracingOptions::default().conflicting_types(ConflictingTypes::Drop);
+    let (_, statistics) = SerdeArrowSchema::from_samples_with_statistics(&items, options).unwrap();
+    assert_eq!(statistics.dropped_fields(), ["$.a", "$.b.c"]);
+
+    let options = TracingOptions::default().conflicting_types(ConflictingTypes::Stringify);
+    let (_, statistics) = SerdeArrowSchema::from_samples_with_statistics(&items, options).unwrap();
+    assert!(statistics.dropped_fields().is_empty());
+}
+
+#[test]
+fn conflicting_types_only_affect_conflicting_fields() {
+    let items = vec![json!({"a": 1}), json!({"a": 2})];
+    let options = TracingOptions::default().conflicting_types(ConflictingTypes::Union);
+    let schema = SerdeArrowSchema::from_samples(&items, options).unwrap();
+    assert_eq!(
+        schema.fields,
+        vec![GenericField::new("a", GenericDataType::U64, false)],
+    );
+}
+
+#[test]
+fn stringify_requires_the_strategy() {
+    let schema = SerdeArrowSchema::from_value(&json!([
+        {"name": "a", "data_type": "LargeUtf8"},
+    ]))
+    .unwrap();
+    let fields = schema.to_arrow_fields().unwrap();
+
+    let err = crate::to_arrow(&fields, &[json!({"a": 1})]).unwrap_err();
+    assert!(
+        err.to_string().contains("serialize_u64 is not implemented"),
+        "unexpected error: {err}"
+    );
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::schema::{SchemaLike, SerdeArrowSchema, TracingOptions};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct OldRecord {
+    b: String,
+    a: u8,
+    unused: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record {
+    a: u8,
+    b: String,
+    c: Option<u32>,
+    #[serde(default)]
+    d: Vec<String>,
+    #[serde(default = "default_e")]
+    e: f64,
+}
+
+fn default_e() -> f64 {
+    42.0
+}
+
+fn old_records() -> Vec<OldRecord> {
+    (0..3)
+        .map(|i| OldRecord {
+            b: format!("b{i}"),
+            a: i,
+            unused: true,
+        })
+        .collect()
+}
+
+fn expected() -> Vec<Record> {
+    (0..3)
+        .map(|i| Record {
+            a: i,
+            b: format!("b{i}"),
+            c: None,
+            d: vec![],
+            e: 42.0,
+        })
+        .collect()
+}
+
+fn schemas() -> (SerdeArrowSchema, SerdeArrowSchema) {
+    let options = TracingOptions::default();
+    (
+        SerdeArrowSchema::from_type::<Record>(options.clone()).unwrap(),
+        SerdeArrowSchema::from_type::<OldRecord>(options).unwrap(),
+    )
+}
+
+#[test]
+fn arrow_missing_fields() {
+    let (target, schema) = schemas();
+    let target = target.to_arrow_fields().unwrap();
+    let fields = schema.to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, &old_records()).unwrap();
+
+    let actual: Vec<Record> = crate::from_arrow_with_defaults(&target, &fields, &arrays).unwrap();
+    assert_eq!(actual, expected());
+}
+
+#[test]
+fn arrow2_missing_fields() {
+    let (target, schema) = schemas();
+    let target = target.to_arrow2_fields().unwrap();
+    let fields = schema.to_arrow2_fields().unwrap();
+    let arrays = crate::to_arrow2(&fields, &old_records()).unwrap();
+
+    let actual: Vec<Record> = crate::from_arrow2_with_defaults(&target, &fields, &arrays).unwrap();
+    assert_eq!(actual, expected());
+}
+
+#[test]
+fn missing_nullable_fields_are_null() {
+    let (target, schema) = schemas();
+    let target = target.to_arrow2_fields().unwrap();
+    let fields = schema.to_arrow2_fields().unwrap();
+    let arrays = crate::to_arrow2(&fields, &old_records()).unwrap();
+
+    let actual: Vec<serde_json::Value> =
+        crate::from_arrow2_with_defaults(&target, &fields, &arrays).unwrap();
+    assert_eq!(actual[1], json!({"a": 1, "b": "b1", "c": null}));
+}
+
+#[test]
+fn missing_required_fields() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Required {
+        a: u8,
+        f: u8,
+    }
+
+    let target = SerdeArrowSchema::from_type::<Required>(TracingOptions::default())
+        .unwrap()
+        .to_arrow2_fields()
+        .unwrap();
+    let fields = schemas().1.to_arrow2_fields().unwrap();
+    let arrays = crate::to_arrow2(&fields, &old_records()).unwrap();
+
+    let err = crate::from_arrow2_with_defaults::<Vec<Required>, _>(&target, &fields, &arrays)
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("missing field `f`"),
+        "unexpected error: {err}"
+    );
+}
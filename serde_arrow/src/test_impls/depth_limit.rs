@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    internal::schema::{GenericDataType, GenericField, Strategy},
+    schema::{DepthLimit, SchemaLike, SerdeArrowSchema, TracingOptions},
+};
+
+use super::utils::{ResultAsserts, Test};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Tree {
+    value: i32,
+    left: Option<Box<Tree>>,
+    right: Option<Box<Tree>>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Node {
+    label: Option<String>,
+    next: Option<Box<Node>>,
+}
+
+fn leaf(value: i32) -> Option<Box<Tree>> {
+    Some(Box::new(Tree {
+        value,
+        left: None,
+        right: None,
+    }))
+}
+
+fn trees() -> Vec<Tree> {
+    vec![
+        Tree {
+            value: 0,
+            left: Some(Box::new(Tree {
+                value: 1,
+                left: Some(Box::new(Tree {
+                    value: 2,
+                    left: leaf(3),
+                    right: None,
+                })),
+                right: leaf(4),
+            })),
+            right: None,
+        },
+        Tree {
+            value: 5,
+            left: None,
+            right: leaf(6),
+        },
+    ]
+}
+
+#[test]
+fn recursive_types_fail_per_default() {
+    SerdeArrowSchema::from_type::<Tree>(TracingOptions::default())
+        .assert_error("too deeply nested type detected at $.left.left.left");
+    SerdeArrowSchema::from_type::<Tree>(TracingOptions::default().max_depth(3))
+        .assert_error("(max_depth = 3)");
+}
+
+#[test]
+fn drop_from_type() {
+    let options = TracingOptions::default()
+        .max_depth(2)
+        .depth_limit(DepthLimit::Drop);
+    let schema = SerdeArrowSchema::from_type::<Node>(options).unwrap();
+
+    let dropped = |name: &str| {
+        GenericField::new(name, GenericDataType::Null, true)
+            .with_strategy(Strategy::InconsistentTypes)
+    };
+    assert_eq!(
+        schema.fields,
+        vec![
+            GenericField::new("label", GenericDataType::LargeUtf8, true),
+            GenericField::new("next", GenericDataType::Struct, true)
+                .with_child(GenericField::new("label", GenericDataType::LargeUtf8, true))
+                .with_child(dropped("next")),
+        ]
+    );
+}
+
+#[test]
+fn drop_roundtrip() {
+    let node = |label: &str, next: Option<Box<Node>>| {
+        Some(Box::new(Node {
+            label: Some(label.to_owned()),
+            next,
+        }))
+    };
+    let items = [*node("a", node("b", node("c", None))).unwrap()];
+    let expected = [*node("a", node("b", None)).unwrap()];
+
+    let options = TracingOptions::default()
+        .max_depth(2)
+        .depth_limit(DepthLimit::Drop);
+
+    Test::new()
+        .trace_schema_from_type::<Node>(options)
+        .serialize(&items)
+        .deserialize(&expected);
+}
+
+#[test]
+fn drop_from_samples() {
+    let items = [
+        json!({"a": {"b": {"c": 1}}}),
+        json!({"a": {"b": [1, 2, 3]}}),
+    ];
+    let options = TracingOptions::default()
+        .max_depth(2)
+        .depth_limit(DepthLimit::Drop);
+
+    Test::new()
+        .with_schema(json!([
+            {
+                "name": "a",
+                "data_type": "Struct",
+                "strategy": "MapAsStruct",
+                "children": [
+                    {"name": "b", "data_type": "Null", "nullable": true, "strategy": "InconsistentTypes"},
+                ],
+            },
+        ]))
+        .trace_schema_from_samples(&items, options)
+        .serialize(&items)
+        .deserialize(&[json!({"a": {"b": null}}), json!({"a": {"b": null}})]);
+}
+
+#[test]
+fn primitives_are_not_cut_off() {
+    let items = [json!({"a": {"b": 1}}), json!({"a": {"b": null}})];
+    let options = TracingOptions::default()
+        .max_depth(2)
+        .depth_limit(DepthLimit::Drop);
+
+    Test::new()
+        .with_schema(json!([
+            {
+                "name": "a",
+                "data_type": "Struct",
+                "strategy": "MapAsStruct",
+                "children": [
+                    {"name": "b", "data_type": "U64", "nullable": true},
+                ],
+            },
+        ]))
+        .trace_schema_from_samples(&items, options)
+        .serialize(&items)
+        .deserialize(&items);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_roundtrip() {
+    let options = TracingOptions::default()
+        .max_depth(2)
+        .depth_limit(DepthLimit::JsonAsUtf8);
+
+    let json_field = |name: &str, nullable: bool| {
+        GenericField::new(name, GenericDataType::LargeUtf8, nullable)
+            .with_strategy(Strategy::JsonAsUtf8)
+    };
+    let subtree = |name: &str| {
+        GenericField::new(name, GenericDataType::Struct, true)
+            .with_child(GenericField::new("value", GenericDataType::I32, false))
+            .with_child(json_field("left", true))
+            .with_child(json_field("right", true))
+    };
+
+    let schema = SerdeArrowSchema::from_type::<Tree>(options.clone()).unwrap();
+    assert_eq!(
+        schema.fields,
+        vec![
+            GenericField::new("value", GenericDataType::I32, false),
+            subtree("left"),
+            subtree("right"),
+        ]
+    );
+
+    Test::new()
+        .trace_schema_from_type::<Tree>(options.clone())
+        .trace_schema_from_samples(&trees(), options)
+        .serialize(&trees())
+        .deserialize(&trees());
+}
+
+#[cfg(not(feature = "json"))]
+#[test]
+fn json_requires_the_json_feature() {
+    let options = TracingOptions::default()
+        .max_depth(2)
+        .depth_limit(DepthLimit::JsonAsUtf8);
+
+    SerdeArrowSchema::from_type::<Tree>(options.clone())
+        .assert_error("requires the `json` feature");
+    SerdeArrowSchema::from_samples(&trees(), options).assert_error("requires the `json` feature");
+}
+
+#[test]
+fn budget_errors_report_incomplete_paths() {
+    #[allow(unused)]
+    #[derive(Deserialize)]
+    struct Record {
+        a: Choice,
+        b: Option<Choice>,
+    }
+
+    #[allow(unused)]
+    #[derive(Deserialize)]
+    enum Choice {
+        A(u8),
+        B(String),
+        C(bool),
+    }
+
+    let res = SerdeArrowSchema::from_type::<Record>(TracingOptions::default().from_type_budget(1));
+    res.assert_error("Could not determine schema from the type after 1 iterations.");
+    res.assert_error("The values at $.a.B, $.a.C, $.b.B, $.b.C were not traced.");
+}
@@ -1,6 +1,10 @@
 use super::utils::Test;
-use crate::{schema::TracingOptions, utils::Item};
+use crate::{
+    schema::{DictionaryThreshold, SchemaLike, SerdeArrowSchema, TracingOptions},
+    utils::Item,
+};
 
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 #[test]
@@ -78,3 +82,397 @@ fn examples_nullable() {
         }
     }
 }
+
+#[test]
+fn persistent_dictionaries_arrow() {
+    use crate::_impl::arrow::{
+        array::{Array, DictionaryArray, LargeStringArray},
+        datatypes::UInt32Type,
+    };
+    use crate::{schema::SchemaLike, utils::DictionaryDelta, ArrowBuilder};
+
+    let fields = Vec::<crate::_impl::arrow::datatypes::Field>::from_value(&json!([{
+        "name": "item",
+        "data_type": "Dictionary",
+        "children": [
+            {"name": "key", "data_type": "U32"},
+            {"name": "value", "data_type": "LargeUtf8"},
+        ]
+    }]))
+    .unwrap();
+
+    let mut builder = ArrowBuilder::new(&fields)
+        .unwrap()
+        .persistent_dictionaries(true);
+
+    let decode = |arrays: &[std::sync::Arc<dyn Array>]| {
+        let array = arrays[0]
+            .as_any()
+            .downcast_ref::<DictionaryArray<UInt32Type>>()
+            .unwrap();
+        let values = array
+            .values()
+            .as_any()
+            .downcast_ref::<LargeStringArray>()
+            .unwrap();
+        let keys = array.keys().values().to_vec();
+        let values = (0..values.len())
+            .map(|idx| values.value(idx).to_owned())
+            .collect::<Vec<_>>();
+        (keys, values)
+    };
+
+    builder.extend(&[Item("a"), Item("b"), Item("a")]).unwrap();
+    let arrays = builder.build_arrays().unwrap();
+    assert_eq!(decode(&arrays), (vec![0, 1, 0], vec![s("a"), s("b")]));
+    assert_eq!(
+        builder.dictionary_deltas(),
+        vec![DictionaryDelta {
+            path: s("$.item"),
+            new_values: 0..2
+        }]
+    );
+
+    builder.extend(&[Item("c"), Item("a"), Item("c")]).unwrap();
+    let arrays = builder.build_arrays().unwrap();
+    assert_eq!(
+        decode(&arrays),
+        (vec![2, 0, 2], vec![s("a"), s("b"), s("c")])
+    );
+    assert_eq!(
+        builder.dictionary_deltas(),
+        vec![DictionaryDelta {
+            path: s("$.item"),
+            new_values: 2..3
+        }]
+    );
+
+    builder.extend(&[Item("b")]).unwrap();
+    let arrays = builder.build_arrays().unwrap();
+    assert_eq!(decode(&arrays), (vec![1], vec![s("a"), s("b"), s("c")]));
+    assert_eq!(
+        builder.dictionary_deltas(),
+        vec![DictionaryDelta {
+            path: s("$.item"),
+            new_values: 3..3
+        }]
+    );
+}
+
+#[test]
+fn persistent_dictionaries_arrow2_nested() {
+    use crate::{schema::SchemaLike, utils::DictionaryDelta, Arrow2Builder};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        tags: Vec<String>,
+    }
+
+    let fields = Vec::<crate::_impl::arrow2::datatypes::Field>::from_value(&json!([{
+        "name": "tags",
+        "data_type": "LargeList",
+        "children": [{
+            "name": "element",
+            "data_type": "Dictionary",
+            "children": [
+                {"name": "key", "data_type": "U16"},
+                {"name": "value", "data_type": "Utf8"},
+            ]
+        }],
+    }]))
+    .unwrap();
+
+    let mut builder = Arrow2Builder::new(&fields)
+        .unwrap()
+        .persistent_dictionaries(true);
+
+    let first = vec![Record {
+        tags: vec![s("x"), s("y")],
+    }];
+    builder.extend(&first).unwrap();
+    let arrays = builder.build_arrays().unwrap();
+    let actual: Vec<Record> = crate::from_arrow2(&fields, &arrays).unwrap();
+    assert_eq!(actual, first);
+    assert_eq!(
+        builder.dictionary_deltas(),
+        vec![DictionaryDelta {
+            path: s("$.tags.item"),
+            new_values: 0..2
+        }]
+    );
+
+    let second = vec![
+        Record { tags: vec![s("y")] },
+        Record {
+            tags: vec![s("z"), s("x")],
+        },
+    ];
+    builder.extend(&second).unwrap();
+    let arrays = builder.build_arrays().unwrap();
+    let actual: Vec<Record> = crate::from_arrow2(&fields, &arrays).unwrap();
+    assert_eq!(actual, second);
+    assert_eq!(
+        builder.dictionary_deltas(),
+        vec![DictionaryDelta {
+            path: s("$.tags.item"),
+            new_values: 2..3
+        }]
+    );
+}
+
+#[test]
+fn dictionary_deltas_use_the_tracing_paths() {
+    use std::collections::BTreeMap;
+
+    use crate::{schema::SchemaLike, utils::DictionaryDelta, ArrowBuilder};
+
+    let fields = Vec::<crate::_impl::arrow::datatypes::Field>::from_value(&json!([{
+        "name": "item",
+        "data_type": "Map",
+        "children": [{
+            "name": "entries",
+            "data_type": "Struct",
+            "children": [
+                {"name": "keys", "data_type": "LargeUtf8"},
+                {
+                    "name": "values",
+                    "data_type": "Dictionary",
+                    "children": [
+                        {"name": "key", "data_type": "U32"},
+                        {"name": "value", "data_type": "LargeUtf8"},
+                    ],
+                },
+            ],
+        }],
+    }]))
+    .unwrap();
+
+    let mut builder = ArrowBuilder::new(&fields)
+        .unwrap()
+        .persistent_dictionaries(true);
+    builder
+        .push(&Item(BTreeMap::from([("a", "x"), ("b", "y")])))
+        .unwrap();
+    builder.build_arrays().unwrap();
+
+    assert_eq!(
+        builder.dictionary_deltas(),
+        vec![DictionaryDelta {
+            path: s("$.item.value"),
+            new_values: 0..2
+        }]
+    );
+}
+
+#[test]
+fn non_persistent_dictionaries_restart() {
+    use crate::{schema::SchemaLike, utils::DictionaryDelta, Arrow2Builder};
+
+    let fields = Vec::<crate::_impl::arrow2::datatypes::Field>::from_value(&json!([{
+        "name": "item",
+        "data_type": "Dictionary",
+        "children": [
+            {"name": "key", "data_type": "U32"},
+            {"name": "value", "data_type": "LargeUtf8"},
+        ]
+    }]))
+    .unwrap();
+
+    let mut builder = Arrow2Builder::new(&fields).unwrap();
+
+    builder.extend(&[Item("a"), Item("b")]).unwrap();
+    builder.build_arrays().unwrap();
+
+    builder.extend(&[Item("b")]).unwrap();
+    let arrays = builder.build_arrays().unwrap();
+    let actual: Vec<Item<String>> = crate::from_arrow2(&fields, &arrays).unwrap();
+    assert_eq!(actual, vec![Item(s("b"))]);
+    assert_eq!(
+        builder.dictionary_deltas(),
+        vec![DictionaryDelta {
+            path: s("$.item"),
+            new_values: 0..1
+        }]
+    );
+}
+
+#[test]
+fn tracing_with_max_distinct_threshold() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        country: String,
+        name: Option<String>,
+    }
+
+    let items = [
+        Record {
+            country: s("DE"),
+            name: Some(s("a")),
+        },
+        Record {
+            country: s("FR"),
+            name: None,
+        },
+        Record {
+            country: s("DE"),
+            name: Some(s("b")),
+        },
+        Record {
+            country: s("DE"),
+            name: Some(s("c")),
+        },
+    ];
+
+    Test::new()
+        .with_schema(json!([
+            {
+                "name": "country",
+                "data_type": "Dictionary",
+                "children": [
+                    {"name": "key", "data_type": "U8"},
+                    {"name": "value", "data_type": "LargeUtf8"},
+                ],
+            },
+            {"name": "name", "data_type": "LargeUtf8", "nullable": true},
+        ]))
+        .trace_schema_from_samples(
+            &items,
+            TracingOptions::new()
+                .string_dictionary_encoding(true)
+                .string_dictionary_threshold(Some(DictionaryThreshold::MaxDistinct(2))),
+        )
+        .serialize(&items)
+        .deserialize(&items);
+}
+
+#[test]
+fn tracing_with_max_ratio_threshold() {
+    let items = [
+        Item(Some(s("a"))),
+        Item(None),
+        Item(Some(s("b"))),
+        Item(Some(s("a"))),
+        Item(Some(s("a"))),
+    ];
+
+    Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "Dictionary",
+            "nullable": true,
+            "children": [
+                {"name": "key", "data_type": "U8", "nullable": true},
+                {"name": "value", "data_type": "LargeUtf8"},
+            ],
+        }]))
+        .trace_schema_from_samples(
+            &items,
+            TracingOptions::new()
+                .string_dictionary_threshold(Some(DictionaryThreshold::MaxRatio(0.5))),
+        )
+        .serialize(&items)
+        .deserialize(&items);
+
+    Test::new()
+        .with_schema(json!([{"name": "item", "data_type": "LargeUtf8", "nullable": true}]))
+        .trace_schema_from_samples(
+            &items,
+            TracingOptions::new()
+                .string_dictionary_threshold(Some(DictionaryThreshold::MaxRatio(0.25))),
+        );
+}
+
+#[test]
+fn tracing_with_threshold_selects_narrowest_index() {
+    for (num_distinct, key_type) in [(256, "U8"), (257, "U16"), (65_536, "U16"), (65_537, "U32")] {
+        let items = (0..num_distinct)
+            .map(|idx| Item(idx.to_string()))
+            .collect::<Vec<_>>();
+
+        let schema = SerdeArrowSchema::from_samples(
+            &items,
+            TracingOptions::new()
+                .string_dictionary_threshold(Some(DictionaryThreshold::MaxDistinct(usize::MAX))),
+        )
+        .unwrap();
+        let expected = SerdeArrowSchema::from_value(&json!([{
+            "name": "item",
+            "data_type": "Dictionary",
+            "children": [
+                {"name": "key", "data_type": key_type},
+                {"name": "value", "data_type": "LargeUtf8"},
+            ],
+        }]))
+        .unwrap();
+
+        assert_eq!(schema, expected, "{num_distinct} distinct values");
+    }
+}
+
+#[test]
+fn tracing_with_threshold_index_overflow() {
+    let samples = (0..2).map(|idx| Item(idx.to_string())).collect::<Vec<_>>();
+    let items = (0..300)
+        .map(|idx| Item(idx.to_string()))
+        .collect::<Vec<_>>();
+
+    let mut test = Test::new().trace_schema_from_samples(
+        &samples,
+        TracingOptions::new()
+            .string_dictionary_threshold(Some(DictionaryThreshold::MaxDistinct(10))),
+    );
+
+    assert!(test.try_serialize_arrow(&items).is_err());
+    assert!(test.try_serialize_arrow2(&items).is_err());
+}
+
+#[test]
+fn tracing_with_threshold_from_type() {
+    Test::new()
+        .with_schema(json!([{"name": "item", "data_type": "LargeUtf8"}]))
+        .trace_schema_from_type::<Item<String>>(
+            TracingOptions::new()
+                .string_dictionary_threshold(Some(DictionaryThreshold::MaxDistinct(10))),
+        );
+}
+
+#[test]
+fn statistics() {
+    #[derive(Serialize)]
+    struct Record {
+        tags: Vec<String>,
+        value: Option<String>,
+        number: i32,
+    }
+
+    let items = [
+        Record {
+            tags: vec![s("a"), s("b"), s("a")],
+            value: Some(s("x")),
+            number: 0,
+        },
+        Record {
+            tags: vec![s("b"), s("c")],
+            value: None,
+            number: 1,
+        },
+    ];
+
+    let (schema, statistics) =
+        SerdeArrowSchema::from_samples_with_statistics(&items, TracingOptions::default()).unwrap();
+    assert_eq!(
+        schema,
+        SerdeArrowSchema::from_samples(&items, TracingOptions::default()).unwrap()
+    );
+
+    let stats = statistics
+        .iter()
+        .filter(|(_, stats)| stats.string_values != 0)
+        .map(|(path, stats)| (path, stats.string_values, stats.distinct_strings))
+        .collect::<Vec<_>>();
+    assert_eq!(stats, vec![("$.tags.item", 5, 3), ("$.value", 1, 1)]);
+}
+
+fn s(value: &str) -> String {
+    value.to_owned()
+}
@@ -5,7 +5,11 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
-use crate::{_impl::arrow, utils::Item};
+use crate::{
+    _impl::arrow,
+    schema::{SchemaLike, SerdeArrowSchema},
+    utils::Item,
+};
 
 use super::utils::Test;
 
@@ -131,3 +135,123 @@ fn bigdecimal_too_small_precision() {
         .expect_err("Expected error");
     assert!(err.to_string().contains("not enough precision"));
 }
+
+#[test]
+fn decimals_as_floats() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Float {
+        item: f64,
+    }
+
+    Test::new()
+        .with_schema(json!([{"name": "item", "data_type": "Decimal128(5, 2)"}]))
+        .serialize(&[Item("0.20"), Item("-1.50")])
+        .deserialize(&[Float { item: 0.2 }, Float { item: -1.5 }]);
+}
+
+#[test]
+fn decimals_as_integers() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Integers {
+        a: i64,
+        b: i128,
+    }
+
+    let mut test = Test::new()
+        .with_schema(json!([
+            {"name": "a", "data_type": "Decimal128(5, 2)"},
+            {"name": "b", "data_type": "Decimal128(5, 2)"},
+        ]))
+        .serialize(&[json!({"a": "12.00", "b": "-3"})])
+        .deserialize(&[Integers { a: 12, b: -3 }]);
+
+    test.try_serialize_arrow(&[json!({"a": "12.50", "b": "1"})])
+        .unwrap();
+    let fields = test.get_arrow_fields();
+    let err = crate::from_arrow::<Vec<Integers>, _>(&fields, test.arrays.arrow.as_ref().unwrap())
+        .expect_err("Expected error");
+    assert!(err.to_string().contains("without loss"), "{err}");
+}
+
+#[test]
+fn decimals_as_parts() {
+    Test::new()
+        .with_schema(json!([{"name": "item", "data_type": "Decimal128(5, 2)"}]))
+        .serialize(&[Item("0.20"), Item("-1.50")])
+        .deserialize(&[Item((20_i128, 2_i8)), Item((-150_i128, 2_i8))]);
+}
+
+#[test]
+fn decimals_as_arbitrary_precision_numbers() {
+    use std::collections::BTreeMap;
+
+    let number = |s: &str| {
+        let mut res = BTreeMap::new();
+        res.insert(String::from("$serde_json::private::Number"), s.to_owned());
+        Item(res)
+    };
+
+    Test::new()
+        .with_schema(json!([{"name": "item", "data_type": "Decimal128(5, 2)"}]))
+        .serialize(&[Item("0.20"), Item("-1.50")])
+        .deserialize(&[number("0.20"), number("-1.50")]);
+}
+
+#[test]
+fn decimals_in_self_describing_types() {
+    Test::new()
+        .with_schema(json!([{"name": "item", "data_type": "Decimal128(5, 2)"}]))
+        .serialize(&[Item("0.20"), Item("-1.50")])
+        .deserialize(&[json!({"item": "0.20"}), json!({"item": "-1.50"})]);
+
+    Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "Decimal128(5, 2)",
+            "strategy": "Decimal128AsF64",
+        }]))
+        .serialize(&[Item("0.20"), Item("-1.50")])
+        .deserialize(&[json!({"item": 0.2}), json!({"item": -1.5})]);
+}
+
+#[test]
+fn decimals_forced_to_strings() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Float {
+        item: f64,
+    }
+
+    let mut test = Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "Decimal128(5, 2)",
+            "strategy": "Decimal128AsStr",
+        }]))
+        .serialize(&[Item("0.20")])
+        .deserialize(&[Item(String::from("0.20"))]);
+
+    let fields = test.get_arrow_fields();
+    let res = crate::from_arrow::<Vec<Float>, _>(&fields, test.arrays.arrow.as_ref().unwrap());
+    assert!(res.is_err());
+
+    test.try_serialize_arrow2(&[Item("0.20")]).unwrap();
+    let fields = test.get_arrow2_fields();
+    let res = crate::from_arrow2::<Vec<Float>, _>(&fields, test.arrays.arrow2.as_ref().unwrap());
+    assert!(res.is_err());
+}
+
+#[test]
+fn decimal_strategies_are_validated() {
+    let schema = SerdeArrowSchema::from_value(&json!([{
+        "name": "item",
+        "data_type": "F64",
+        "strategy": "Decimal128AsStr",
+    }]))
+    .unwrap();
+
+    let fields = schema.to_arrow_fields().unwrap();
+    let err = crate::ArrowBuilder::new(&fields).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("invalid strategy for F64: Decimal128AsStr"));
+}
@@ -0,0 +1,342 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    _impl::arrow::{
+        array::{Array, LargeStringArray},
+        datatypes::{DataType, Field},
+    },
+    internal::schema::{GenericDataType, GenericField, Strategy},
+    schema::{SchemaLike, SerdeArrowSchema, TracingOptions},
+    utils::Item,
+    ArrowBuilder,
+};
+
+use super::utils::Test;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record {
+    id: u32,
+    payload: Value,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Typed {
+    id: u32,
+    inner: Inner,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Inner {
+    label: String,
+    values: Vec<f64>,
+    kind: Kind,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Kind {
+    A,
+    B(i32),
+}
+
+fn json_options() -> TracingOptions {
+    TracingOptions::default()
+        .override_field("$.payload", json!({"strategy": "JsonAsUtf8"}))
+        .unwrap()
+}
+
+fn json_strings(test: &Test, idx: usize) -> Vec<Option<String>> {
+    let arrays = test.arrays.arrow.as_ref().unwrap();
+    let array = arrays[idx]
+        .as_any()
+        .downcast_ref::<LargeStringArray>()
+        .unwrap();
+    (0..array.len())
+        .map(|i| (!array.is_null(i)).then(|| array.value(i).to_owned()))
+        .collect()
+}
+
+#[test]
+fn values_of_varying_shape_from_samples() {
+    let items = [
+        Record {
+            id: 0,
+            payload: json!({"a": 1, "b": [true, null]}),
+        },
+        Record {
+            id: 1,
+            payload: json!("hello"),
+        },
+        Record {
+            id: 2,
+            payload: json!([1, {"c": 2.5}]),
+        },
+    ];
+
+    Test::new()
+        .with_schema(json!([
+            {"name": "id", "data_type": "U32"},
+            {"name": "payload", "data_type": "LargeUtf8", "strategy": "JsonAsUtf8"},
+        ]))
+        .trace_schema_from_samples(&items, json_options())
+        .serialize(&items)
+        .also(|test| {
+            assert_eq!(
+                json_strings(test, 1),
+                vec![
+                    Some(String::from(r#"{"a":1,"b":[true,null]}"#)),
+                    Some(String::from(r#""hello""#)),
+                    Some(String::from(r#"[1,{"c":2.5}]"#)),
+                ]
+            );
+        })
+        .deserialize(&items);
+}
+
+#[test]
+fn values_from_type() {
+    let items = [
+        Record {
+            id: 0,
+            payload: json!({"a": 1}),
+        },
+        Record {
+            id: 1,
+            payload: json!(null),
+        },
+    ];
+
+    Test::new()
+        .with_schema(json!([
+            {"name": "id", "data_type": "U32"},
+            {"name": "payload", "data_type": "LargeUtf8", "strategy": "JsonAsUtf8"},
+        ]))
+        .trace_schema_from_type::<Record>(json_options())
+        .serialize(&items)
+        .deserialize(&items);
+}
+
+#[test]
+fn nullable_values() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        payload: Option<Value>,
+    }
+
+    let items = [
+        Record {
+            payload: Some(json!({"a": [1, 2]})),
+        },
+        Record { payload: None },
+        Record {
+            payload: Some(json!(3)),
+        },
+    ];
+
+    Test::new()
+        .with_schema(json!([
+            {"name": "payload", "data_type": "LargeUtf8", "strategy": "JsonAsUtf8", "nullable": true},
+        ]))
+        .trace_schema_from_samples(&items, json_options())
+        .trace_schema_from_type::<Record>(json_options())
+        .serialize(&items)
+        .check_nulls(&[&[false, true, false]])
+        .deserialize(&items);
+}
+
+#[test]
+fn typed_values() {
+    let items = [
+        Typed {
+            id: 0,
+            inner: Inner {
+                label: String::from("foo"),
+                values: vec![1.0, 2.5],
+                kind: Kind::A,
+            },
+        },
+        Typed {
+            id: 1,
+            inner: Inner {
+                label: String::from("bar"),
+                values: vec![],
+                kind: Kind::B(13),
+            },
+        },
+    ];
+
+    let options = TracingOptions::default()
+        .override_field("$.inner", json!({"strategy": "JsonAsUtf8"}))
+        .unwrap();
+
+    Test::new()
+        .with_schema(json!([
+            {"name": "id", "data_type": "U32"},
+            {"name": "inner", "data_type": "LargeUtf8", "strategy": "JsonAsUtf8"},
+        ]))
+        .trace_schema_from_samples(&items, options.clone())
+        .trace_schema_from_type::<Typed>(options)
+        .serialize(&items)
+        .also(|test| {
+            assert_eq!(
+                json_strings(test, 1),
+                vec![
+                    Some(String::from(
+                        r#"{"label":"foo","values":[1.0,2.5],"kind":"A"}"#
+                    )),
+                    Some(String::from(
+                        r#"{"label":"bar","values":[],"kind":{"B":13}}"#
+                    )),
+                ]
+            );
+        })
+        .deserialize(&items);
+}
+
+#[test]
+fn nested_list_items() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        items: Vec<Value>,
+    }
+
+    let items = [
+        Record {
+            items: vec![json!(1), json!({"a": "b"})],
+        },
+        Record { items: vec![] },
+    ];
+    let options = TracingOptions::default()
+        .override_field("$.items.item", json!({"strategy": "JsonAsUtf8"}))
+        .unwrap();
+
+    Test::new()
+        .with_schema(json!([
+            {
+                "name": "items",
+                "data_type": "LargeList",
+                "children": [
+                    {"name": "element", "data_type": "LargeUtf8", "strategy": "JsonAsUtf8"},
+                ],
+            },
+        ]))
+        .trace_schema_from_samples(&items, options.clone())
+        .trace_schema_from_type::<Record>(options)
+        .serialize(&items)
+        .deserialize(&items);
+}
+
+#[test]
+fn any_fallback() {
+    let options = TracingOptions::default()
+        .any_fallback(json!({"strategy": "JsonAsUtf8"}))
+        .unwrap();
+    let schema = SerdeArrowSchema::from_type::<Record>(options).unwrap();
+
+    assert_eq!(
+        schema.fields[1],
+        GenericField::new("payload", GenericDataType::LargeUtf8, false)
+            .with_strategy(Strategy::JsonAsUtf8),
+    );
+}
+
+#[test]
+fn any_fallback_roundtrip() {
+    let items = [
+        Record {
+            id: 0,
+            payload: json!(13),
+        },
+        Record {
+            id: 1,
+            payload: json!({"a": [1, 2.5], "b": null}),
+        },
+        Record {
+            id: 2,
+            payload: json!(true),
+        },
+    ];
+    let options = TracingOptions::default()
+        .any_fallback(json!({"strategy": "JsonAsUtf8"}))
+        .unwrap();
+
+    Test::new()
+        .with_schema(json!([
+            {"name": "id", "data_type": "U32"},
+            {"name": "payload", "data_type": "LargeUtf8", "strategy": "JsonAsUtf8"},
+        ]))
+        .trace_schema_from_type::<Record>(options)
+        .serialize(&items)
+        .deserialize(&items);
+}
+
+#[test]
+fn utf8_with_json_strategy() {
+    let items = [Item(json!({"a": 1})), Item(json!([1, 2]))];
+
+    Test::new()
+        .with_schema(json!([
+            {"name": "item", "data_type": "Utf8", "strategy": "JsonAsUtf8"},
+        ]))
+        .serialize(&items)
+        .deserialize(&items);
+}
+
+#[test]
+fn failed_values_are_not_stored() {
+    let fields = Vec::<Field>::from_value(&json!([
+        {"name": "item", "data_type": "LargeUtf8", "strategy": "JsonAsUtf8"},
+    ]))
+    .unwrap();
+    let mut builder = ArrowBuilder::new(&fields).unwrap();
+
+    // JSON objects require string keys
+    let err = builder
+        .push(&Item(BTreeMap::from([((1, 2), 3)])))
+        .unwrap_err();
+    assert!(err.to_string().contains("key must be a string"));
+
+    builder.push(&Item(json!({"a": 1}))).unwrap();
+    let arrays = builder.build_arrays().unwrap();
+    let array = arrays[0]
+        .as_any()
+        .downcast_ref::<LargeStringArray>()
+        .unwrap();
+    assert_eq!(array.len(), 1);
+    assert_eq!(array.value(0), r#"{"a":1}"#);
+}
+
+#[test]
+fn arrow_fields_use_the_json_extension() {
+    let fields = Vec::<Field>::from_value(&json!([
+        {"name": "payload", "data_type": "LargeUtf8", "strategy": "JsonAsUtf8"},
+    ]))
+    .unwrap();
+
+    assert_eq!(fields[0].data_type(), &DataType::LargeUtf8);
+    assert_eq!(
+        fields[0]
+            .metadata()
+            .get("ARROW:extension:name")
+            .map(String::as_str),
+        Some("arrow.json")
+    );
+
+    // fields written by other libraries only carry the extension name
+    let field = Field::new("payload", DataType::Utf8, false).with_metadata(
+        [(
+            String::from("ARROW:extension:name"),
+            String::from("arrow.json"),
+        )]
+        .into_iter()
+        .collect(),
+    );
+    let schema = SerdeArrowSchema::from_arrow_fields(&[field]).unwrap();
+    assert_eq!(
+        schema.fields[0],
+        GenericField::new("payload", GenericDataType::Utf8, false)
+            .with_strategy(Strategy::JsonAsUtf8),
+    );
+}
@@ -1,6 +1,11 @@
+use std::collections::BTreeMap;
+
 use serde_json::json;
 
-use crate::schema::TracingOptions;
+use crate::{
+    _impl::arrow::datatypes::Field,
+    schema::{SchemaLike, SerdeArrowSchema, TracingOptions},
+};
 
 use super::utils::{ResultAsserts, Test};
 
@@ -103,3 +108,155 @@ fn serde_json_nullable_strings_non_nullable_field() {
     test.try_serialize_arrow2(&items)
         .assert_error("cannot push null for non-nullable array");
 }
+
+/// Deserialize the arrays of the items into `serde_json::Value` and compare
+/// with the JSON representation of the items
+fn assert_json_round_trip<T: serde::Serialize>(items: &[T], options: TracingOptions) {
+    let expected = serde_json::to_value(items).unwrap();
+    let schema = SerdeArrowSchema::from_samples(items, options).unwrap();
+
+    let fields = schema.to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, items).unwrap();
+    let actual: serde_json::Value = crate::from_arrow(&fields, &arrays).unwrap();
+    assert_eq!(actual, expected);
+
+    let fields = schema.to_arrow2_fields().unwrap();
+    let arrays = crate::to_arrow2(&fields, items).unwrap();
+    let actual: serde_json::Value = crate::from_arrow2(&fields, &arrays).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn deserialize_nullable_fields_into_json() {
+    #[derive(serde::Serialize)]
+    struct Inner {
+        x: u8,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Record {
+        a: Option<i32>,
+        b: Option<Vec<Option<String>>>,
+        c: Option<Inner>,
+        d: (u8, Option<f64>),
+    }
+
+    let items = [
+        Record {
+            a: Some(1),
+            b: Some(vec![Some(String::from("x")), None]),
+            c: None,
+            d: (1, None),
+        },
+        Record {
+            a: None,
+            b: None,
+            c: Some(Inner { x: 2 }),
+            d: (2, Some(0.5)),
+        },
+        Record {
+            a: None,
+            b: Some(vec![]),
+            c: None,
+            d: (3, None),
+        },
+    ];
+    assert_json_round_trip(&items, TracingOptions::default());
+}
+
+#[test]
+fn deserialize_unions_into_json() {
+    #[derive(serde::Serialize)]
+    enum Value {
+        Unit,
+        Int(i32),
+        Struct { x: String },
+        Tuple(u8, bool),
+    }
+
+    #[derive(serde::Serialize)]
+    struct Record {
+        value: Value,
+    }
+
+    let items = [
+        Record { value: Value::Unit },
+        Record {
+            value: Value::Int(3),
+        },
+        Record {
+            value: Value::Struct {
+                x: String::from("y"),
+            },
+        },
+        Record {
+            value: Value::Tuple(4, true),
+        },
+    ];
+    assert_json_round_trip(&items, TracingOptions::default().allow_null_fields(true));
+}
+
+#[test]
+fn deserialize_maps_with_non_string_keys_into_json() {
+    #[derive(serde::Serialize)]
+    struct Record {
+        ints: BTreeMap<i64, String>,
+        bytes: BTreeMap<u8, bool>,
+    }
+
+    let items = [
+        Record {
+            ints: BTreeMap::from([(-1, String::from("a")), (2, String::from("b"))]),
+            bytes: BTreeMap::from([(1, true)]),
+        },
+        Record {
+            ints: BTreeMap::new(),
+            bytes: BTreeMap::from([(0, false), (1, true)]),
+        },
+    ];
+    assert_json_round_trip(&items, TracingOptions::default().map_as_struct(false));
+
+    // typed maps still receive the keys in their original type
+    let fields =
+        Vec::<Field>::from_samples(&items, TracingOptions::default().map_as_struct(false)).unwrap();
+    let arrays = crate::to_arrow(&fields, &items).unwrap();
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Typed {
+        ints: BTreeMap<i64, String>,
+    }
+    let actual: Vec<Typed> = crate::from_arrow(&fields, &arrays).unwrap();
+    assert_eq!(actual[0].ints, items[0].ints);
+}
+
+#[test]
+fn deserialize_decimals_and_dates_into_json() {
+    #[derive(serde::Serialize)]
+    struct Record {
+        decimal: &'static str,
+        date: &'static str,
+        timestamp: i64,
+    }
+
+    let items = [
+        Record {
+            decimal: "1.23",
+            date: "2023-05-05T16:06:00Z",
+            timestamp: 1_000,
+        },
+        Record {
+            decimal: "-0.50",
+            date: "1969-12-31T23:59:59.500Z",
+            timestamp: -1,
+        },
+    ];
+    let fields = Vec::<Field>::from_value(&json!([
+        {"name": "decimal", "data_type": "Decimal128(5, 2)"},
+        {"name": "date", "data_type": "Date64", "strategy": "UtcStrAsDate64"},
+        {"name": "timestamp", "data_type": "Date64"},
+    ]))
+    .unwrap();
+    let arrays = crate::to_arrow(&fields, &items).unwrap();
+    let actual: serde_json::Value = crate::from_arrow(&fields, &arrays).unwrap();
+    assert_eq!(actual, serde_json::to_value(items).unwrap());
+}
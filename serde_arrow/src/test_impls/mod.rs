@@ -1,15 +1,35 @@
 mod utils;
 
+mod append;
+mod borrowed;
 mod chrono;
+mod columns;
+mod conflicting_types;
+mod defaults;
+mod depth_limit;
 mod dictionary;
 mod examples;
+#[cfg(feature = "json")]
+mod json_as_utf8;
 mod json_values;
 mod list;
 mod macros;
 mod map;
+mod numeric_conversion;
+mod offset_width;
+mod overrides;
+mod parse_str;
 mod primitives;
+mod row_access;
+mod row_iterator;
+mod sliced;
+mod statistics;
+mod string_detectors;
 mod r#struct;
+mod time;
+mod transcode;
 mod tuple;
+mod type_and_samples;
 mod r#union;
 mod wrappers;
 
@@ -0,0 +1,300 @@
+use half::f16;
+use serde_json::json;
+
+use super::utils::Test;
+use crate::{
+    schema::{SchemaLike, SerdeArrowSchema},
+    utils::{Item, NumericConversion},
+    Arrow2Builder, ArrowBuilder,
+};
+
+fn try_build_arrow<T: serde::Serialize>(
+    data_type: &str,
+    policy: NumericConversion,
+    items: &[T],
+) -> crate::Result<Vec<crate::_impl::arrow::array::ArrayRef>> {
+    let fields = SerdeArrowSchema::from_value(&json!([{"name": "item", "data_type": data_type}]))?
+        .to_arrow_fields()?;
+    let mut builder = ArrowBuilder::new(&fields)?.numeric_conversion(policy);
+    builder.extend(items)?;
+    builder.build_arrays()
+}
+
+fn try_build_arrow2<T: serde::Serialize>(
+    data_type: &str,
+    policy: NumericConversion,
+    items: &[T],
+) -> crate::Result<Vec<Box<dyn crate::_impl::arrow2::array::Array>>> {
+    let fields = SerdeArrowSchema::from_value(&json!([{"name": "item", "data_type": data_type}]))?
+        .to_arrow2_fields()?;
+    let mut builder = Arrow2Builder::new(&fields)?.numeric_conversion(policy);
+    builder.extend(items)?;
+    builder.build_arrays()
+}
+
+fn roundtrip<T: serde::Serialize, R: serde::de::DeserializeOwned>(
+    data_type: &str,
+    policy: NumericConversion,
+    items: &[T],
+) -> crate::Result<(Vec<R>, Vec<R>)> {
+    let schema = SerdeArrowSchema::from_value(&json!([{"name": "item", "data_type": data_type}]))?;
+
+    let arrow_fields = schema.to_arrow_fields()?;
+    let arrays = try_build_arrow(data_type, policy, items)?;
+    let arrow = crate::from_arrow(&arrow_fields, &arrays)?;
+
+    let arrow2_fields = schema.to_arrow2_fields()?;
+    let arrays = try_build_arrow2(data_type, policy, items)?;
+    let arrow2 = crate::from_arrow2(&arrow2_fields, &arrays)?;
+
+    Ok((arrow, arrow2))
+}
+
+fn s(value: &str) -> String {
+    value.to_owned()
+}
+
+#[test]
+fn default_conversions_are_lossy() {
+    Test::new()
+        .with_schema(json!([{"name": "item", "data_type": "F32"}]))
+        .serialize(&[Item(0.1_f64)])
+        .deserialize(&[Item(0.1_f32)]);
+
+    Test::new()
+        .with_schema(json!([{"name": "item", "data_type": "F64"}]))
+        .serialize(&[Item((1_i64 << 53) + 1)])
+        .deserialize(&[Item((1_i64 << 53) as f64)]);
+
+    Test::new()
+        .with_schema(json!([{"name": "item", "data_type": "I32"}]))
+        .serialize(&[Item(2.5_f64)])
+        .deserialize(&[Item(2_i32)]);
+
+    let mut test = Test::new().with_schema(json!([{"name": "item", "data_type": "I8"}]));
+    let err = test.try_serialize_arrow(&[Item(128_i64)]).unwrap_err();
+    assert!(err.to_string().contains("value out of range"));
+}
+
+#[test]
+fn lossy_float_narrowing() {
+    let (arrow, arrow2) =
+        roundtrip::<_, Item<f32>>("F32", NumericConversion::Lossy, &[Item(0.1_f64)]).unwrap();
+    assert_eq!(arrow, vec![Item(0.1_f32)]);
+    assert_eq!(arrow2, vec![Item(0.1_f32)]);
+}
+
+#[test]
+fn lossy_float_narrowing_fails_out_of_range() {
+    let err = try_build_arrow("F32", NumericConversion::Lossy, &[Item(1e300_f64)]).unwrap_err();
+    assert!(err.to_string().contains("value out of range"));
+
+    let err = try_build_arrow2("F16", NumericConversion::Lossy, &[Item(1e6_f32)]).unwrap_err();
+    assert!(err.to_string().contains("value out of range"));
+}
+
+#[test]
+fn strict_float_narrowing() {
+    let (arrow, arrow2) = roundtrip::<_, Item<f32>>(
+        "F32",
+        NumericConversion::Strict,
+        &[Item(0.5_f64), Item(f64::NAN), Item(f64::INFINITY)],
+    )
+    .unwrap();
+    for items in [arrow, arrow2] {
+        assert_eq!(items[0], Item(0.5));
+        assert!(items[1].0.is_nan());
+        assert_eq!(items[2], Item(f32::INFINITY));
+    }
+
+    let err = try_build_arrow("F32", NumericConversion::Strict, &[Item(0.1_f64)]).unwrap_err();
+    assert!(err.to_string().contains("without loss of precision"));
+}
+
+#[test]
+fn saturating_float_narrowing() {
+    let (arrow, arrow2) = roundtrip::<_, Item<f32>>(
+        "F32",
+        NumericConversion::Saturating,
+        &[Item(1e300_f64), Item(-1e300_f64)],
+    )
+    .unwrap();
+    assert_eq!(arrow, vec![Item(f32::MAX), Item(f32::MIN)]);
+    assert_eq!(arrow2, vec![Item(f32::MAX), Item(f32::MIN)]);
+}
+
+#[test]
+fn int_to_float_precision() {
+    let large = (1_i64 << 53) + 1;
+
+    let (arrow, arrow2) =
+        roundtrip::<_, Item<f64>>("F64", NumericConversion::Lossy, &[Item(large)]).unwrap();
+    assert_eq!(arrow, vec![Item((1_i64 << 53) as f64)]);
+    assert_eq!(arrow2, vec![Item((1_i64 << 53) as f64)]);
+
+    let err = try_build_arrow2("F64", NumericConversion::Strict, &[Item(large)]).unwrap_err();
+    assert!(err.to_string().contains("without loss of precision"));
+
+    let (arrow, arrow2) =
+        roundtrip::<_, Item<f32>>("F32", NumericConversion::Strict, &[Item(1_u64 << 40)]).unwrap();
+    assert_eq!(arrow, vec![Item((1_u64 << 40) as f32)]);
+    assert_eq!(arrow2, vec![Item((1_u64 << 40) as f32)]);
+}
+
+#[test]
+fn float_to_int() {
+    let (arrow, arrow2) = roundtrip::<_, Item<i32>>(
+        "I32",
+        NumericConversion::Strict,
+        &[Item(2.0_f64), Item(-3.0_f64)],
+    )
+    .unwrap();
+    assert_eq!(arrow, vec![Item(2), Item(-3)]);
+    assert_eq!(arrow2, vec![Item(2), Item(-3)]);
+
+    let err = try_build_arrow("I32", NumericConversion::Strict, &[Item(2.5_f64)]).unwrap_err();
+    assert!(err.to_string().contains("fractional part"));
+
+    let (arrow, arrow2) = roundtrip::<_, Item<i32>>(
+        "I32",
+        NumericConversion::Lossy,
+        &[Item(2.5_f64), Item(-2.5_f64)],
+    )
+    .unwrap();
+    assert_eq!(arrow, vec![Item(2), Item(-2)]);
+    assert_eq!(arrow2, vec![Item(2), Item(-2)]);
+
+    let err = try_build_arrow2("U8", NumericConversion::Lossy, &[Item(256.0_f32)]).unwrap_err();
+    assert!(err.to_string().contains("value out of range"));
+
+    let err = try_build_arrow("I64", NumericConversion::Lossy, &[Item(f64::NAN)]).unwrap_err();
+    assert!(err.to_string().contains("value out of range"));
+
+    let (arrow, arrow2) = roundtrip::<_, Item<u8>>(
+        "U8",
+        NumericConversion::Saturating,
+        &[Item(300.5_f64), Item(-1.0_f64), Item(f64::NAN)],
+    )
+    .unwrap();
+    assert_eq!(arrow, vec![Item(255), Item(0), Item(0)]);
+    assert_eq!(arrow2, vec![Item(255), Item(0), Item(0)]);
+}
+
+#[test]
+fn int_narrowing() {
+    let err = try_build_arrow("I8", NumericConversion::Lossy, &[Item(128_i64)]).unwrap_err();
+    assert!(err.to_string().contains("value out of range"));
+
+    let err = try_build_arrow2("U32", NumericConversion::Strict, &[Item(-1_i32)]).unwrap_err();
+    assert!(err.to_string().contains("value out of range"));
+
+    let (arrow, arrow2) = roundtrip::<_, Item<i8>>(
+        "I8",
+        NumericConversion::Saturating,
+        &[Item(1000_i64), Item(-1000_i64), Item(i64::MAX)],
+    )
+    .unwrap();
+    assert_eq!(arrow, vec![Item(127), Item(-128), Item(127)]);
+    assert_eq!(arrow2, vec![Item(127), Item(-128), Item(127)]);
+}
+
+#[test]
+fn float_to_decimal() {
+    let (arrow, arrow2) = roundtrip::<_, Item<String>>(
+        "Decimal128(5, 2)",
+        NumericConversion::Strict,
+        &[Item(1.25_f64), Item(-3.5_f64), Item(1.1_f64)],
+    )
+    .unwrap();
+    let expected = vec![Item(s("1.25")), Item(s("-3.50")), Item(s("1.10"))];
+    assert_eq!(arrow, expected);
+    assert_eq!(arrow2, expected);
+
+    let (arrow, arrow2) = roundtrip::<_, Item<String>>(
+        "Decimal128(5, 2)",
+        NumericConversion::Strict,
+        &[Item(0.5_f32), Item(-2.75_f32)],
+    )
+    .unwrap();
+    assert_eq!(arrow, vec![Item(s("0.50")), Item(s("-2.75"))]);
+    assert_eq!(arrow2, vec![Item(s("0.50")), Item(s("-2.75"))]);
+
+    let err = try_build_arrow(
+        "Decimal128(5, 2)",
+        NumericConversion::Strict,
+        &[Item(0.125)],
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("without loss of precision"));
+
+    let (arrow, arrow2) = roundtrip::<_, Item<String>>(
+        "Decimal128(5, 2)",
+        NumericConversion::Lossy,
+        &[Item(0.125_f64), Item(0.29_f64)],
+    )
+    .unwrap();
+    assert_eq!(arrow, vec![Item(s("0.13")), Item(s("0.29"))]);
+    assert_eq!(arrow2, vec![Item(s("0.13")), Item(s("0.29"))]);
+
+    for policy in [NumericConversion::Strict, NumericConversion::Lossy] {
+        let err = try_build_arrow("Decimal128(5, 2)", policy, &[Item(1e10_f64)]).unwrap_err();
+        assert!(err.to_string().contains("value out of range"));
+
+        let err = try_build_arrow2("Decimal128(5, 2)", policy, &[Item(f32::NAN)]).unwrap_err();
+        assert!(err.to_string().contains("value out of range"));
+    }
+
+    let (arrow, arrow2) = roundtrip::<_, Item<String>>(
+        "Decimal128(5, 2)",
+        NumericConversion::Saturating,
+        &[Item(1e10_f64), Item(-1e10_f64), Item(f64::NAN)],
+    )
+    .unwrap();
+    let expected = vec![Item(s("999.99")), Item(s("-999.99")), Item(s("0.00"))];
+    assert_eq!(arrow, expected);
+    assert_eq!(arrow2, expected);
+}
+
+#[test]
+fn default_float_to_decimal_checks_the_precision() {
+    let mut test =
+        Test::new().with_schema(json!([{"name": "item", "data_type": "Decimal128(5, 2)"}]));
+    let err = test.try_serialize_arrow(&[Item(1e10_f64)]).unwrap_err();
+    assert!(err.to_string().contains("value out of range"));
+
+    let err = test.try_serialize_arrow2(&[Item(1e10_f64)]).unwrap_err();
+    assert!(err.to_string().contains("value out of range"));
+}
+
+#[test]
+fn f16_rounding() {
+    let (arrow, arrow2) = roundtrip::<_, Item<f32>>(
+        "F16",
+        NumericConversion::Strict,
+        &[Item(0.5_f32), Item(1024.0_f32)],
+    )
+    .unwrap();
+    assert_eq!(arrow, vec![Item(0.5), Item(1024.0)]);
+    assert_eq!(arrow2, vec![Item(0.5), Item(1024.0)]);
+
+    let err = try_build_arrow("F16", NumericConversion::Strict, &[Item(0.1_f32)]).unwrap_err();
+    assert!(err.to_string().contains("without loss of precision"));
+
+    let (arrow, arrow2) =
+        roundtrip::<_, Item<f32>>("F16", NumericConversion::Strict, &[Item(1024_u32)]).unwrap();
+    assert_eq!(arrow, vec![Item(1024.0)]);
+    assert_eq!(arrow2, vec![Item(1024.0)]);
+
+    let err = try_build_arrow2("F16", NumericConversion::Strict, &[Item(2049_u32)]).unwrap_err();
+    assert!(err.to_string().contains("without loss of precision"));
+
+    let (arrow, arrow2) =
+        roundtrip::<_, Item<f32>>("F16", NumericConversion::Lossy, &[Item(0.1_f32)]).unwrap();
+    assert_eq!(arrow, vec![Item(f16::from_f32(0.1).to_f32())]);
+    assert_eq!(arrow2, vec![Item(f16::from_f32(0.1).to_f32())]);
+
+    let (arrow, arrow2) =
+        roundtrip::<_, Item<f32>>("F16", NumericConversion::Saturating, &[Item(1e6_f64)]).unwrap();
+    assert_eq!(arrow, vec![Item(f16::MAX.to_f32())]);
+    assert_eq!(arrow2, vec![Item(f16::MAX.to_f32())]);
+}
@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    schema::{ConflictingTypes, TracingOptions},
+    utils::Item,
+};
+
+use super::utils::Test;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record {
+    name: String,
+    tags: Vec<String>,
+    nested: Vec<Vec<u8>>,
+    attributes: BTreeMap<String, String>,
+}
+
+fn records() -> Vec<Record> {
+    vec![
+        Record {
+            name: String::from("foo"),
+            tags: vec![String::from("a"), String::from("b")],
+            nested: vec![vec![1, 2], vec![]],
+            attributes: BTreeMap::from([(String::from("x"), String::from("y"))]),
+        },
+        Record {
+            name: String::from("bar"),
+            tags: vec![],
+            nested: vec![vec![3]],
+            attributes: BTreeMap::new(),
+        },
+    ]
+}
+
+#[test]
+fn small_offsets() {
+    let items = records();
+    let options = TracingOptions::default()
+        .large_utf8(false)
+        .large_list(false)
+        .map_as_struct(false);
+
+    Test::new()
+        .with_schema(json!([
+            {"name": "name", "data_type": "Utf8"},
+            {
+                "name": "tags",
+                "data_type": "List",
+                "children": [{"name": "element", "data_type": "Utf8"}],
+            },
+            {
+                "name": "nested",
+                "data_type": "List",
+                "children": [{
+                    "name": "element",
+                    "data_type": "List",
+                    "children": [{"name": "element", "data_type": "U8"}],
+                }],
+            },
+            {
+                "name": "attributes",
+                "data_type": "Map",
+                "children": [{
+                    "name": "entries",
+                    "data_type": "Struct",
+                    "children": [
+                        {"name": "key", "data_type": "Utf8"},
+                        {"name": "value", "data_type": "Utf8"},
+                    ],
+                }],
+            },
+        ]))
+        .trace_schema_from_type::<Record>(options.clone())
+        .trace_schema_from_samples(&items, options)
+        .serialize(&items)
+        .deserialize(&items);
+}
+
+#[test]
+fn mixed_offsets() {
+    let items = [Item(vec![String::from("a")]), Item(vec![])];
+    let options = TracingOptions::default().large_utf8(false);
+
+    Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "LargeList",
+            "children": [{"name": "element", "data_type": "Utf8"}],
+        }]))
+        .trace_schema_from_type::<Item<Vec<String>>>(options.clone())
+        .trace_schema_from_samples(&items, options)
+        .serialize(&items)
+        .deserialize(&items);
+}
+
+#[test]
+fn small_offsets_dictionary() {
+    let items = [Item(String::from("a")), Item(String::from("b"))];
+    let options = TracingOptions::default()
+        .large_utf8(false)
+        .string_dictionary_encoding(true);
+
+    Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "Dictionary",
+            "children": [
+                {"name": "key", "data_type": "U32"},
+                {"name": "value", "data_type": "Utf8"},
+            ],
+        }]))
+        .trace_schema_from_type::<Item<String>>(options.clone())
+        .trace_schema_from_samples(&items, options)
+        .serialize(&items)
+        .deserialize(&items);
+}
+
+#[test]
+fn small_offsets_conflicting_types() {
+    #[derive(Serialize)]
+    #[serde(untagged)]
+    enum Value {
+        Str(&'static str),
+        Int(i64),
+    }
+
+    let items = [Item(Value::Str("a")), Item(Value::Int(1))];
+
+    Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "Utf8",
+            "strategy": "InconsistentTypes",
+        }]))
+        .trace_schema_from_samples(
+            &items,
+            TracingOptions::default()
+                .large_utf8(false)
+                .conflicting_types(ConflictingTypes::Stringify),
+        );
+
+    Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "Union",
+            "strategy": "InconsistentTypes",
+            "children": [
+                {"name": "Utf8", "data_type": "Utf8"},
+                {"name": "I64", "data_type": "I64"},
+            ],
+        }]))
+        .trace_schema_from_samples(
+            &items,
+            TracingOptions::default()
+                .large_utf8(false)
+                .conflicting_types(ConflictingTypes::Union),
+        )
+        .serialize(&items);
+}
@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    internal::schema::{GenericDataType, GenericField, Strategy},
+    schema::{SchemaLike, SerdeArrowSchema, TracingOptions},
+};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record {
+    price: f64,
+    user: User,
+    ts: String,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct User {
+    id: String,
+    scores: BTreeMap<String, i64>,
+}
+
+fn field(schema: &SerdeArrowSchema, name: &str) -> GenericField {
+    schema
+        .fields
+        .iter()
+        .find(|field| field.name == name)
+        .unwrap()
+        .clone()
+}
+
+#[test]
+fn override_data_types() {
+    let options = TracingOptions::default()
+        .map_as_struct(false)
+        .override_field("$.price", json!({"data_type": "Decimal128(18, 4)"}))
+        .unwrap()
+        .override_field("$.user.id", json!({"data_type": "Dictionary"}))
+        .unwrap()
+        .override_field(
+            "$.ts",
+            json!({"data_type": "Date64", "strategy": "NaiveStrAsDate64"}),
+        )
+        .unwrap()
+        .override_field("$.tags", json!({"data_type": "List"}))
+        .unwrap()
+        .override_field("$.user.scores.value", json!({"data_type": "I32"}))
+        .unwrap();
+
+    let schema = SerdeArrowSchema::from_type::<Record>(options).unwrap();
+
+    assert_eq!(
+        field(&schema, "price"),
+        GenericField::new("price", GenericDataType::Decimal128(18, 4), false)
+    );
+    assert_eq!(
+        field(&schema, "ts"),
+        GenericField::new("ts", GenericDataType::Date64, false)
+            .with_strategy(Strategy::NaiveStrAsDate64)
+    );
+    assert_eq!(
+        field(&schema, "tags"),
+        GenericField::new("tags", GenericDataType::List, false).with_child(GenericField::new(
+            "element",
+            GenericDataType::LargeUtf8,
+            false
+        ))
+    );
+
+    let user = field(&schema, "user");
+    assert_eq!(
+        user.children[0],
+        GenericField::new("id", GenericDataType::Dictionary, false)
+            .with_child(GenericField::new("key", GenericDataType::U32, false))
+            .with_child(GenericField::new(
+                "value",
+                GenericDataType::LargeUtf8,
+                false
+            ))
+    );
+    assert_eq!(
+        user.children[1].children[0].children[1],
+        GenericField::new("value", GenericDataType::I32, false)
+    );
+
+    let items = vec![Record {
+        price: 12.5,
+        user: User {
+            id: String::from("foo"),
+            scores: BTreeMap::from([(String::from("a"), 1)]),
+        },
+        ts: String::from("2023-11-14T22:13:20"),
+        tags: vec![String::from("bar")],
+    }];
+
+    let fields = schema.to_arrow2_fields().unwrap();
+    let arrays = crate::to_arrow2(&fields, &items).unwrap();
+    let actual: Vec<Record> = crate::from_arrow2(&fields, &arrays).unwrap();
+    assert_eq!(actual, items);
+}
+
+#[test]
+fn override_subtree_from_samples() {
+    let items = vec![
+        json!({"a": {"b": 1, "c": "x"}}),
+        json!({"a": {"b": 2, "c": "y"}}),
+    ];
+
+    let options = TracingOptions::default()
+        .override_field(
+            "$.a",
+            json!({
+                "data_type": "Struct",
+                "nullable": true,
+                "children": [
+                    {"name": "b", "data_type": "U8"},
+                    {"name": "c", "data_type": "Utf8"},
+                ],
+            }),
+        )
+        .unwrap();
+    let schema = SerdeArrowSchema::from_samples(&items, options).unwrap();
+
+    let expected = SerdeArrowSchema::from_value(&json!([{
+        "name": "a",
+        "data_type": "Struct",
+        "nullable": true,
+        "children": [
+            {"name": "b", "data_type": "U8"},
+            {"name": "c", "data_type": "Utf8"},
+        ],
+    }]))
+    .unwrap();
+    assert_eq!(schema, expected);
+}
+
+#[test]
+fn override_list_items_from_samples() {
+    let items = vec![json!({"a": [1, 2]}), json!({"a": [3]})];
+
+    let options = TracingOptions::default()
+        .override_field("$.a.item", json!({"data_type": "U16", "nullable": true}))
+        .unwrap();
+    let schema = SerdeArrowSchema::from_samples(&items, options).unwrap();
+
+    assert_eq!(
+        field(&schema, "a").children[0],
+        GenericField::new("element", GenericDataType::U16, true)
+    );
+}
+
+#[test]
+fn invalid_overrides() {
+    fn assert_error(path: &str, value: serde_json::Value, expected: &str) {
+        let err = TracingOptions::default()
+            .map_as_struct(false)
+            .override_field(path, value)
+            .and_then(SerdeArrowSchema::from_type::<Record>)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains(expected),
+            "unexpected error: {err}"
+        );
+    }
+
+    assert_error(
+        "price",
+        json!({"data_type": "F32"}),
+        "paths must start with \"$\"",
+    );
+    assert_error(
+        "$.prize",
+        json!({"data_type": "F32"}),
+        "Cannot override the field at $.prize: the path was not traced",
+    );
+    assert_error(
+        "$.price",
+        json!({"datatype": "F32"}),
+        "unknown field `datatype`",
+    );
+    assert_error(
+        "$.user",
+        json!({"data_type": "I64"}),
+        "Cannot override the data type Struct of $.user with I64 without children",
+    );
+    assert_error(
+        "$.price",
+        json!({"data_type": "LargeList"}),
+        "requires children for the data type LargeList",
+    );
+    assert_error(
+        "$.user",
+        json!({"strategy": "NaiveStrAsDate64"}),
+        "Invalid override of $.user: invalid strategy for Struct field",
+    );
+}
+
+#[test]
+fn nullable_fields_cannot_be_made_non_nullable() {
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Nullable {
+        a: Option<u8>,
+    }
+
+    let options = TracingOptions::default()
+        .override_field("$.a", json!({"nullable": false}))
+        .unwrap();
+    let err = SerdeArrowSchema::from_type::<Nullable>(options).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Cannot override the nullable field at $.a as non-nullable"),
+        "unexpected error: {err}"
+    );
+}
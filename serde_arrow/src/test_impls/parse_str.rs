@@ -0,0 +1,223 @@
+use super::utils::Test;
+use crate::{
+    schema::{SchemaLike, SerdeArrowSchema, TracingOptions},
+    utils::Item,
+};
+
+use serde_json::json;
+
+#[test]
+fn integers_from_str() {
+    let items = [Item("42"), Item("-13"), Item("+7")];
+
+    Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "I64",
+            "strategy": "StrAsPrimitive",
+        }]))
+        .trace_schema_from_samples(&items, TracingOptions::default().guess_numbers(true))
+        .serialize(&items)
+        .deserialize(&[Item(42_i64), Item(-13), Item(7)])
+        .check_nulls(&[&[false, false, false]]);
+}
+
+#[test]
+fn nullable_integers_from_str() {
+    let items = [Item(Some("1")), Item(None), Item(Some("3"))];
+
+    Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "I64",
+            "nullable": true,
+            "strategy": "StrAsPrimitive",
+        }]))
+        .trace_schema_from_samples(&items, TracingOptions::default().guess_numbers(true))
+        .serialize(&items)
+        .deserialize(&[Item(Some(1_i64)), Item(None), Item(Some(3))])
+        .check_nulls(&[&[false, true, false]]);
+}
+
+#[test]
+fn floats_from_str() {
+    let items = [Item("3.5"), Item("1"), Item("-2e3")];
+
+    Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "F64",
+            "strategy": "StrAsPrimitive",
+        }]))
+        .trace_schema_from_samples(&items, TracingOptions::default().guess_numbers(true))
+        .serialize(&items)
+        .deserialize(&[Item(3.5_f64), Item(1.0), Item(-2000.0)])
+        .check_nulls(&[&[false, false, false]]);
+}
+
+#[test]
+fn f32_from_str() {
+    let items = [Item("3.5"), Item("-1")];
+
+    Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "F32",
+            "strategy": "StrAsPrimitive",
+        }]))
+        .serialize(&items)
+        .deserialize(&[Item(3.5_f32), Item(-1.0)]);
+}
+
+#[test]
+fn u8_from_str() {
+    let items = [Item("0"), Item("255")];
+
+    Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "U8",
+            "strategy": "StrAsPrimitive",
+        }]))
+        .serialize(&items)
+        .deserialize(&[Item(0_u8), Item(255)]);
+}
+
+#[test]
+fn bools_from_str() {
+    let items = [Item("true"), Item("false")];
+
+    Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "Bool",
+            "strategy": "StrAsPrimitive",
+        }]))
+        .serialize(&items)
+        .deserialize(&[Item(true), Item(false)]);
+}
+
+#[test]
+fn numbers_are_still_accepted() {
+    let items = [Item(1_i64), Item(2)];
+
+    Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "I64",
+            "strategy": "StrAsPrimitive",
+        }]))
+        .serialize(&items)
+        .deserialize(&items);
+}
+
+#[test]
+fn numbers_mixed_with_numeric_strings() {
+    let items = [json!({"item": "-5"}), json!({"item": 7})];
+
+    Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "I64",
+            "strategy": "StrAsPrimitive",
+        }]))
+        .trace_schema_from_samples(&items, TracingOptions::default().guess_numbers(true))
+        .serialize(&items)
+        .deserialize(&[json!({"item": -5}), json!({"item": 7})]);
+
+    let items = [
+        json!({"item": "2.5"}),
+        json!({"item": 7}),
+        json!({"item": -1}),
+    ];
+
+    Test::new()
+        .with_schema(json!([{
+            "name": "item",
+            "data_type": "F64",
+            "strategy": "StrAsPrimitive",
+        }]))
+        .trace_schema_from_samples(&items, TracingOptions::default().guess_numbers(true))
+        .serialize(&items)
+        .deserialize(&[
+            json!({"item": 2.5}),
+            json!({"item": 7.0}),
+            json!({"item": -1.0}),
+        ]);
+}
+
+#[test]
+fn mixed_strings_are_traced_as_strings() {
+    let items = [Item("42"), Item("foo")];
+
+    Test::new()
+        .with_schema(json!([{"name": "item", "data_type": "LargeUtf8"}]))
+        .trace_schema_from_samples(&items, TracingOptions::default().guess_numbers(true));
+}
+
+#[test]
+fn numbers_are_not_guessed_by_default() {
+    let items = [Item("42"), Item("13")];
+
+    Test::new()
+        .with_schema(json!([{"name": "item", "data_type": "LargeUtf8"}]))
+        .trace_schema_from_samples(&items, TracingOptions::default());
+}
+
+#[test]
+fn invalid_strings_fail() {
+    let mut test = Test::new().with_schema(json!([{
+        "name": "item",
+        "data_type": "I32",
+        "strategy": "StrAsPrimitive",
+    }]));
+
+    let err = test.try_serialize_arrow(&[Item("1.5")]).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Cannot parse string \"1.5\" as i32"));
+
+    let err = test.try_serialize_arrow2(&[Item(" 1")]).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Cannot parse string \" 1\" as i32"));
+}
+
+#[test]
+fn out_of_range_strings_fail() {
+    let mut test = Test::new().with_schema(json!([{
+        "name": "item",
+        "data_type": "U8",
+        "strategy": "StrAsPrimitive",
+    }]));
+
+    let err = test.try_serialize_arrow(&[Item("256")]).unwrap_err();
+    assert!(err.to_string().contains("value out of range"));
+
+    let err = test.try_serialize_arrow2(&[Item("-1")]).unwrap_err();
+    assert!(err.to_string().contains("value out of range"));
+}
+
+#[test]
+fn strings_without_strategy_fail() {
+    let mut test = Test::new().with_schema(json!([{"name": "item", "data_type": "I64"}]));
+
+    let err = test.try_serialize_arrow(&[Item("1")]).unwrap_err();
+    assert!(err.to_string().contains("serialize_str is not implemented"));
+}
+
+#[test]
+fn strategy_is_rejected_for_strings() {
+    let schema = SerdeArrowSchema::from_value(&json!([{
+        "name": "item",
+        "data_type": "LargeUtf8",
+        "strategy": "StrAsPrimitive",
+    }]))
+    .unwrap();
+
+    let fields = schema.to_arrow_fields().unwrap();
+    let err = crate::ArrowBuilder::new(&fields).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("invalid strategy for LargeUtf8: StrAsPrimitive"));
+}
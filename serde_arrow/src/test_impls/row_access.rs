@@ -0,0 +1,126 @@
+use super::row_iterator::{records, schema, Record};
+
+#[test]
+fn arrow_every_row() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, &records()).unwrap();
+
+    for (idx, expected) in records().iter().enumerate() {
+        let actual: Record = crate::from_arrow_row(&fields, &arrays, idx).unwrap();
+        assert_eq!(&actual, expected);
+    }
+}
+
+#[test]
+fn arrow2_every_row() {
+    let fields = schema().to_arrow2_fields().unwrap();
+    let arrays = crate::to_arrow2(&fields, &records()).unwrap();
+
+    for (idx, expected) in records().iter().enumerate() {
+        let actual: Record = crate::from_arrow2_row(&fields, &arrays, idx).unwrap();
+        assert_eq!(&actual, expected);
+    }
+}
+
+#[test]
+fn arrow_every_range() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, &records()).unwrap();
+    let expected = records();
+
+    for start in 0..=expected.len() {
+        for end in start..=expected.len() {
+            let actual: Vec<Record> =
+                crate::from_arrow_range(&fields, &arrays, start..end).unwrap();
+            assert_eq!(actual, &expected[start..end], "range {start}..{end}");
+        }
+    }
+}
+
+#[test]
+fn arrow2_every_range() {
+    let fields = schema().to_arrow2_fields().unwrap();
+    let arrays = crate::to_arrow2(&fields, &records()).unwrap();
+    let expected = records();
+
+    for start in 0..=expected.len() {
+        for end in start..=expected.len() {
+            let actual: Vec<Record> =
+                crate::from_arrow2_range(&fields, &arrays, start..end).unwrap();
+            assert_eq!(actual, &expected[start..end], "range {start}..{end}");
+        }
+    }
+}
+
+#[test]
+fn row_out_of_bounds() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, &records()).unwrap();
+
+    let err = crate::from_arrow_row::<Record, _>(&fields, &arrays, 10).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Cannot get row 10 of arrays with 10 rows"),
+        "unexpected error: {err}"
+    );
+}
+
+#[test]
+fn invalid_ranges() {
+    let fields = schema().to_arrow2_fields().unwrap();
+    let arrays = crate::to_arrow2(&fields, &records()).unwrap();
+
+    let err = crate::from_arrow2_range::<Vec<Record>, _>(&fields, &arrays, 5..11).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Invalid row range 5..11 for 10 rows"),
+        "unexpected error: {err}"
+    );
+
+    #[allow(clippy::reversed_empty_ranges)]
+    let err = crate::from_arrow2_range::<Vec<Record>, _>(&fields, &arrays, 5..4).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Invalid row range 5..4 for 10 rows"),
+        "unexpected error: {err}"
+    );
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn arrow_parallel() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let expected = (0..100).flat_map(|_| records()).collect::<Vec<_>>();
+    let arrays = crate::to_arrow(&fields, &expected).unwrap();
+
+    let actual: Vec<Record> = crate::from_arrow_parallel(&fields, &arrays).unwrap();
+    assert_eq!(actual, expected);
+
+    let sliced = arrays
+        .iter()
+        .map(|array| array.slice(3, 250))
+        .collect::<Vec<_>>();
+    let actual: Vec<Record> = crate::from_arrow_parallel(&fields, &sliced).unwrap();
+    assert_eq!(actual, &expected[3..253]);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn arrow2_parallel() {
+    let fields = schema().to_arrow2_fields().unwrap();
+    let expected = (0..100).flat_map(|_| records()).collect::<Vec<_>>();
+    let arrays = crate::to_arrow2(&fields, &expected).unwrap();
+
+    let actual: Vec<Record> = crate::from_arrow2_parallel(&fields, &arrays).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_empty_arrays() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, &Vec::<Record>::new()).unwrap();
+
+    let actual: Vec<Record> = crate::from_arrow_parallel(&fields, &arrays).unwrap();
+    assert!(actual.is_empty());
+}
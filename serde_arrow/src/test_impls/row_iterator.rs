@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::schema::{SchemaLike, SerdeArrowSchema, TracingOptions};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum Value {
+    Int(i64),
+    Str(String),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Inner {
+    x: f32,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    id: u32,
+    name: String,
+    inner: Option<Inner>,
+    nested: Vec<Vec<u8>>,
+    scores: BTreeMap<String, i64>,
+    value: Value,
+}
+
+pub fn records() -> Vec<Record> {
+    (0..10_u32)
+        .map(|i| Record {
+            id: i,
+            name: format!("record {i}"),
+            inner: (i % 3 != 0).then(|| Inner {
+                x: i as f32,
+                tags: (0..i % 4).map(|j| format!("tag {j}")).collect(),
+            }),
+            nested: (0..i % 3).map(|j| vec![j as u8; j as usize]).collect(),
+            scores: (0..i % 2)
+                .map(|j| (format!("score {j}"), (i * j) as i64))
+                .collect(),
+            value: if i % 2 == 0 {
+                Value::Int(i as i64)
+            } else {
+                Value::Str(format!("value {i}"))
+            },
+        })
+        .collect()
+}
+
+pub fn schema() -> SerdeArrowSchema {
+    let options = TracingOptions::default()
+        .map_as_struct(false)
+        .string_dictionary_encoding(true);
+    SerdeArrowSchema::from_samples(&records(), options).unwrap()
+}
+
+#[test]
+fn arrow_iter_matches_from_arrow() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, &records()).unwrap();
+
+    let actual = crate::from_arrow_iter::<Record, _>(&fields, &arrays)
+        .unwrap()
+        .collect::<crate::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(actual, records());
+}
+
+#[test]
+fn arrow2_iter_matches_from_arrow2() {
+    let fields = schema().to_arrow2_fields().unwrap();
+    let arrays = crate::to_arrow2(&fields, &records()).unwrap();
+
+    let actual = crate::from_arrow2_iter::<Record, _>(&fields, &arrays)
+        .unwrap()
+        .collect::<crate::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(actual, records());
+}
+
+#[test]
+fn size_hint_counts_remaining_rows() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, &records()).unwrap();
+
+    let mut rows = crate::from_arrow_iter::<Record, _>(&fields, &arrays).unwrap();
+    assert_eq!(rows.size_hint(), (10, Some(10)));
+
+    rows.next().unwrap().unwrap();
+    assert_eq!(rows.len(), 9);
+
+    rows.nth(5).unwrap().unwrap();
+    assert_eq!(rows.len(), 3);
+
+    assert!(rows.nth(5).is_none());
+    assert_eq!(rows.len(), 0);
+    assert!(rows.next().is_none());
+}
+
+#[test]
+fn nth_seeks_to_every_row() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, &records()).unwrap();
+    let expected = records();
+
+    for (idx, expected) in expected.iter().enumerate() {
+        let mut rows = crate::from_arrow_iter::<Record, _>(&fields, &arrays).unwrap();
+        let actual = rows.nth(idx).unwrap().unwrap();
+        assert_eq!(&actual, expected);
+
+        // continuing after the seek yields the following rows
+        let rest = rows.collect::<crate::Result<Vec<_>>>().unwrap();
+        assert_eq!(rest.len(), 9 - idx);
+    }
+}
+
+#[test]
+fn skip_and_step_by() {
+    let fields = schema().to_arrow2_fields().unwrap();
+    let arrays = crate::to_arrow2(&fields, &records()).unwrap();
+
+    let actual = crate::from_arrow2_iter::<Record, _>(&fields, &arrays)
+        .unwrap()
+        .skip(1)
+        .step_by(3)
+        .map(|record| record.unwrap().id)
+        .collect::<Vec<_>>();
+    assert_eq!(actual, vec![1, 4, 7]);
+}
+
+#[test]
+fn strings_are_borrowed_from_the_arrays() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Borrowed<'a> {
+        id: u32,
+        name: &'a str,
+        #[serde(borrow)]
+        inner: Option<BorrowedInner<'a>>,
+        // ignoring unions is not supported
+        value: Value,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct BorrowedInner<'a> {
+        #[serde(borrow)]
+        tags: Vec<&'a str>,
+    }
+
+    let fields = schema().to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, &records()).unwrap();
+
+    let rows = crate::from_arrow_iter::<Borrowed, _>(&fields, &arrays)
+        .unwrap()
+        .collect::<crate::Result<Vec<_>>>()
+        .unwrap();
+
+    assert_eq!(rows[4].name, "record 4");
+    assert_eq!(rows[5].value, Value::Str(String::from("value 5")));
+    assert_eq!(rows[3].inner, None);
+    assert_eq!(
+        rows[7].inner,
+        Some(BorrowedInner {
+            tags: vec!["tag 0", "tag 1", "tag 2"],
+        })
+    );
+}
+
+#[test]
+fn errors_do_not_stop_the_iteration() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Small {
+        id: u32,
+        nested: Vec<Vec<u8>>,
+        value: u8,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Large {
+        id: u32,
+        nested: Vec<Vec<u8>>,
+        value: u32,
+    }
+
+    let items = [
+        Large {
+            id: 0,
+            nested: vec![vec![1, 2]],
+            value: 1,
+        },
+        Large {
+            id: 1,
+            nested: vec![vec![3], vec![4]],
+            value: 1000,
+        },
+        Large {
+            id: 2,
+            nested: vec![vec![5]],
+            value: 2,
+        },
+    ];
+
+    let fields = SerdeArrowSchema::from_type::<Large>(TracingOptions::default())
+        .unwrap()
+        .to_arrow_fields()
+        .unwrap();
+    let arrays = crate::to_arrow(&fields, &items).unwrap();
+
+    let mut rows = crate::from_arrow_iter::<Small, _>(&fields, &arrays).unwrap();
+    assert_eq!(
+        rows.next().unwrap().unwrap(),
+        Small {
+            id: 0,
+            nested: vec![vec![1, 2]],
+            value: 1,
+        }
+    );
+    assert!(rows.next().unwrap().is_err());
+    assert_eq!(
+        rows.next().unwrap().unwrap(),
+        Small {
+            id: 2,
+            nested: vec![vec![5]],
+            value: 2,
+        }
+    );
+    assert!(rows.next().is_none());
+}
+
+#[test]
+fn empty_arrays() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, &Vec::<Record>::new()).unwrap();
+
+    let mut rows = crate::from_arrow_iter::<Record, _>(&fields, &arrays).unwrap();
+    assert_eq!(rows.len(), 0);
+    assert!(rows.next().is_none());
+}
@@ -0,0 +1,471 @@
+//! Test deserialization of sliced arrays and other non-trivial layouts
+use std::{collections::BTreeMap, sync::Arc};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    _impl::{arrow, arrow2},
+    schema::{SchemaLike, SerdeArrowSchema, TracingOptions},
+};
+
+// arrow-version:replace: use arrow_select_{version} as arrow_select;
+use arrow_select_50 as arrow_select;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Value {
+    Int(i64),
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Inner {
+    x: f64,
+    values: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Record {
+    id: u32,
+    flag: Option<bool>,
+    name: Option<String>,
+    tags: Option<Vec<String>>,
+    nested: Vec<Option<Vec<i16>>>,
+    inner: Option<Inner>,
+    scores: BTreeMap<String, u32>,
+    value: Value,
+}
+
+fn random_string(rng: &mut StdRng) -> String {
+    let len = rng.gen_range(0..5);
+    (0..len).map(|_| rng.gen_range('a'..='e')).collect()
+}
+
+fn random_records(rng: &mut StdRng, len: usize) -> Vec<Record> {
+    (0..len)
+        .map(|id| Record {
+            id: id as u32,
+            flag: rng.gen_bool(0.7).then(|| rng.gen()),
+            name: rng.gen_bool(0.7).then(|| random_string(rng)),
+            tags: rng.gen_bool(0.5).then(|| {
+                let len = rng.gen_range(0..4);
+                (0..len).map(|_| random_string(rng)).collect()
+            }),
+            nested: {
+                let len = rng.gen_range(0..3);
+                (0..len)
+                    .map(|_| {
+                        rng.gen_bool(0.6).then(|| {
+                            let len = rng.gen_range(0..4);
+                            (0..len).map(|_| rng.gen()).collect()
+                        })
+                    })
+                    .collect()
+            },
+            inner: rng.gen_bool(0.6).then(|| Inner {
+                x: rng.gen(),
+                values: {
+                    let len = rng.gen_range(0..4);
+                    (0..len).map(|_| rng.gen()).collect()
+                },
+            }),
+            scores: {
+                let len = rng.gen_range(0..3);
+                (0..len).map(|_| (random_string(rng), rng.gen())).collect()
+            },
+            value: match rng.gen_range(0..3) {
+                0 => Value::Int(rng.gen()),
+                1 => Value::Text(random_string(rng)),
+                _ => Value::Bytes({
+                    let len = rng.gen_range(0..3);
+                    (0..len).map(|_| rng.gen()).collect()
+                }),
+            },
+        })
+        .collect()
+}
+
+fn schemas(records: &[Record]) -> Vec<SerdeArrowSchema> {
+    [false, true]
+        .into_iter()
+        .map(|dictionary| {
+            let options = TracingOptions::default()
+                .map_as_struct(false)
+                .string_dictionary_encoding(dictionary);
+            SerdeArrowSchema::from_samples(records, options).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn arrow_random_slices() {
+    let rng = &mut StdRng::seed_from_u64(42);
+    let records = random_records(rng, 50);
+
+    for schema in schemas(&records) {
+        let fields = schema.to_arrow_fields().unwrap();
+        let arrays = crate::to_arrow(&fields, &records).unwrap();
+
+        for _ in 0..50 {
+            let offset = rng.gen_range(0..=records.len());
+            let len = rng.gen_range(0..=records.len() - offset);
+            let expected = &records[offset..offset + len];
+
+            let sliced = arrays
+                .iter()
+                .map(|array| array.slice(offset, len))
+                .collect::<Vec<_>>();
+
+            let actual: Vec<Record> = crate::from_arrow(&fields, &sliced).unwrap();
+            assert_eq!(actual, expected, "slice {offset}..{}", offset + len);
+
+            let actual = crate::from_arrow_iter::<Record, _>(&fields, &sliced)
+                .unwrap()
+                .collect::<crate::Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(actual, expected, "slice {offset}..{}", offset + len);
+
+            if len > 0 {
+                let row = rng.gen_range(0..len);
+                let actual: Record = crate::from_arrow_row(&fields, &sliced, row).unwrap();
+                assert_eq!(actual, expected[row], "row {row} of slice {offset}");
+            }
+        }
+    }
+}
+
+#[test]
+fn arrow2_random_slices() {
+    let rng = &mut StdRng::seed_from_u64(13);
+    let records = random_records(rng, 50);
+
+    for schema in schemas(&records) {
+        let fields = schema.to_arrow2_fields().unwrap();
+        let arrays = crate::to_arrow2(&fields, &records).unwrap();
+
+        for _ in 0..50 {
+            let offset = rng.gen_range(0..=records.len());
+            let len = rng.gen_range(0..=records.len() - offset);
+            let expected = &records[offset..offset + len];
+
+            let sliced = arrays
+                .iter()
+                .map(|array| array.sliced(offset, len))
+                .collect::<Vec<_>>();
+
+            let actual: Vec<Record> = crate::from_arrow2(&fields, &sliced).unwrap();
+            assert_eq!(actual, expected, "slice {offset}..{}", offset + len);
+
+            let actual = crate::from_arrow2_iter::<Record, _>(&fields, &sliced)
+                .unwrap()
+                .collect::<crate::Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(actual, expected, "slice {offset}..{}", offset + len);
+        }
+    }
+}
+
+/// Check all arrow entry points against the expected records
+fn assert_arrow_records(
+    fields: &[arrow::datatypes::Field],
+    arrays: &[arrow::array::ArrayRef],
+    expected: &[Record],
+    context: &str,
+) {
+    let actual: Vec<Record> = crate::from_arrow(fields, arrays).unwrap();
+    assert_eq!(actual, expected, "{context}");
+
+    let actual = crate::from_arrow_iter::<Record, _>(fields, arrays)
+        .unwrap()
+        .collect::<crate::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(actual, expected, "{context}");
+
+    for (row, expected) in expected.iter().enumerate() {
+        let actual: Record = crate::from_arrow_row(fields, arrays, row).unwrap();
+        assert_eq!(&actual, expected, "row {row} of {context}");
+    }
+}
+
+/// Take the given rows of an array
+///
+/// arrow 50 does not implement `take` for dense unions, `interleave` selects
+/// the rows with the generic `MutableArrayData` implementation instead.
+fn take_rows(array: &arrow::array::ArrayRef, rows: &[u32]) -> arrow::array::ArrayRef {
+    use arrow::{
+        array::PrimitiveArray,
+        datatypes::{DataType, UInt32Type, UnionMode},
+    };
+
+    if let DataType::Union(_, UnionMode::Dense) = array.data_type() {
+        let indices = rows
+            .iter()
+            .map(|&row| (0, row as usize))
+            .collect::<Vec<_>>();
+        arrow_select::interleave::interleave(&[array.as_ref()], &indices).unwrap()
+    } else {
+        arrow_select::take::take(
+            array.as_ref(),
+            &PrimitiveArray::<UInt32Type>::from(rows.to_vec()),
+            None,
+        )
+        .unwrap()
+    }
+}
+
+#[test]
+fn arrow_concat() {
+    let rng = &mut StdRng::seed_from_u64(21);
+    let records = random_records(rng, 60);
+
+    for schema in schemas(&records) {
+        let fields = schema.to_arrow_fields().unwrap();
+
+        for _ in 0..20 {
+            let mid = rng.gen_range(0..=records.len());
+            let head = crate::to_arrow(&fields, &records[..mid]).unwrap();
+            let tail = crate::to_arrow(&fields, &records[mid..]).unwrap();
+
+            let concatenated = head
+                .iter()
+                .zip(&tail)
+                .map(|(head, tail)| {
+                    arrow_select::concat::concat(&[head.as_ref(), tail.as_ref()]).unwrap()
+                })
+                .collect::<Vec<_>>();
+            assert_arrow_records(
+                &fields,
+                &concatenated,
+                &records,
+                &format!("concat at {mid}"),
+            );
+
+            // slicing the concatenated arrays results in non-zero offsets
+            let offset = rng.gen_range(0..=records.len());
+            let len = rng.gen_range(0..=records.len() - offset);
+            let sliced = concatenated
+                .iter()
+                .map(|array| array.slice(offset, len))
+                .collect::<Vec<_>>();
+            assert_arrow_records(
+                &fields,
+                &sliced,
+                &records[offset..offset + len],
+                &format!("slice {offset}..{} of concat at {mid}", offset + len),
+            );
+        }
+    }
+}
+
+#[test]
+fn arrow_filter() {
+    use arrow::array::BooleanArray;
+
+    let rng = &mut StdRng::seed_from_u64(22);
+    let records = random_records(rng, 60);
+
+    for schema in schemas(&records) {
+        let fields = schema.to_arrow_fields().unwrap();
+        let arrays = crate::to_arrow(&fields, &records).unwrap();
+
+        for iteration in 0..20 {
+            let mask = (0..records.len())
+                .map(|_| rng.gen_bool(0.5))
+                .collect::<Vec<_>>();
+            // filter sliced arrays to start from non-zero offsets
+            let offset = rng.gen_range(0..=records.len());
+            let predicate = BooleanArray::from(mask[offset..].to_vec());
+            let filtered = arrays
+                .iter()
+                .map(|array| {
+                    let array = array.slice(offset, records.len() - offset);
+                    arrow_select::filter::filter(array.as_ref(), &predicate).unwrap()
+                })
+                .collect::<Vec<_>>();
+            let expected = records[offset..]
+                .iter()
+                .zip(&mask[offset..])
+                .filter(|(_, &keep)| keep)
+                .map(|(record, _)| record.clone())
+                .collect::<Vec<_>>();
+            assert_arrow_records(
+                &fields,
+                &filtered,
+                &expected,
+                &format!("filter {iteration}"),
+            );
+        }
+    }
+}
+
+#[test]
+fn arrow_take() {
+    let rng = &mut StdRng::seed_from_u64(23);
+    let records = random_records(rng, 60);
+
+    for schema in schemas(&records) {
+        let fields = schema.to_arrow_fields().unwrap();
+        let arrays = crate::to_arrow(&fields, &records).unwrap();
+
+        for iteration in 0..20 {
+            // unordered rows with repetitions
+            let len = rng.gen_range(0..2 * records.len());
+            let rows = (0..len)
+                .map(|_| rng.gen_range(0..records.len() as u32))
+                .collect::<Vec<_>>();
+            let expected = rows
+                .iter()
+                .map(|&row| records[row as usize].clone())
+                .collect::<Vec<_>>();
+
+            let taken = arrays
+                .iter()
+                .map(|array| take_rows(array, &rows))
+                .collect::<Vec<_>>();
+            assert_arrow_records(&fields, &taken, &expected, &format!("take {iteration}"));
+        }
+    }
+}
+
+#[test]
+fn arrow_slices_of_slices() {
+    let rng = &mut StdRng::seed_from_u64(7);
+    let records = random_records(rng, 40);
+
+    let fields = schemas(&records)[0].to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, &records).unwrap();
+
+    let sliced = arrays
+        .iter()
+        .map(|array| array.slice(5, 30).slice(10, 15))
+        .collect::<Vec<_>>();
+
+    let actual: Vec<Record> = crate::from_arrow(&fields, &sliced).unwrap();
+    assert_eq!(actual, &records[15..30]);
+}
+
+/// Build `[[0, 1], null, [4]]`, where the null entry covers the values `[2, 3]`
+#[test]
+fn arrow_null_list_with_non_empty_segment() {
+    use arrow::{
+        array::{make_array, Array, ArrayData, ArrayRef, PrimitiveArray},
+        buffer::Buffer,
+        datatypes::{DataType, Field, Int32Type},
+    };
+
+    let values = PrimitiveArray::<Int32Type>::from(vec![0, 1, 2, 3, 4]);
+    let item_field = Field::new("element", DataType::Int32, false);
+    let data = ArrayData::builder(DataType::List(Arc::new(item_field.clone())))
+        .len(3)
+        .add_buffer(Buffer::from_slice_ref([0_i32, 2, 4, 5]))
+        .add_child_data(values.into_data())
+        .null_bit_buffer(Some(Buffer::from([0b101_u8])))
+        .build()
+        .unwrap();
+    let array: ArrayRef = make_array(data);
+
+    let fields = vec![Field::new(
+        "items",
+        DataType::List(Arc::new(item_field)),
+        true,
+    )];
+    let arrays = vec![array.clone()];
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Row {
+        items: Option<Vec<i32>>,
+    }
+
+    let actual: Vec<Row> = crate::from_arrow(&fields, &arrays).unwrap();
+    assert_eq!(
+        actual,
+        vec![
+            Row {
+                items: Some(vec![0, 1])
+            },
+            Row { items: None },
+            Row {
+                items: Some(vec![4])
+            },
+        ]
+    );
+
+    let row: Row = crate::from_arrow_row(&fields, &arrays, 2).unwrap();
+    assert_eq!(row.items, Some(vec![4]));
+
+    let sliced = vec![array.slice(1, 2)];
+    let actual: Vec<Row> = crate::from_arrow(&fields, &sliced).unwrap();
+    assert_eq!(
+        actual,
+        vec![
+            Row { items: None },
+            Row {
+                items: Some(vec![4])
+            },
+        ]
+    );
+
+    let items = |rows: &[Row]| rows.iter().map(|row| row.items.clone()).collect::<Vec<_>>();
+
+    let concatenated =
+        vec![arrow_select::concat::concat(&[array.slice(1, 2).as_ref(), array.as_ref()]).unwrap()];
+    let actual: Vec<Row> = crate::from_arrow(&fields, &concatenated).unwrap();
+    assert_eq!(
+        items(&actual),
+        vec![None, Some(vec![4]), Some(vec![0, 1]), None, Some(vec![4])]
+    );
+
+    let predicate = arrow::array::BooleanArray::from(vec![false, true, true]);
+    let filtered = vec![arrow_select::filter::filter(array.as_ref(), &predicate).unwrap()];
+    let actual: Vec<Row> = crate::from_arrow(&fields, &filtered).unwrap();
+    assert_eq!(items(&actual), vec![None, Some(vec![4])]);
+
+    let taken = vec![take_rows(&array, &[2, 1, 0, 1])];
+    let actual: Vec<Row> = crate::from_arrow(&fields, &taken).unwrap();
+    assert_eq!(
+        items(&actual),
+        vec![Some(vec![4]), None, Some(vec![0, 1]), None]
+    );
+}
+
+/// Build `[[0, 1], null, [4]]`, where the null entry covers the values `[2, 3]`
+#[test]
+fn arrow2_null_list_with_non_empty_segment() {
+    use arrow2::{
+        array::{Array, ListArray, PrimitiveArray},
+        bitmap::Bitmap,
+        datatypes::{DataType, Field},
+        offset::OffsetsBuffer,
+    };
+
+    let item_field = Field::new("element", DataType::Int32, false);
+    let data_type = DataType::List(Box::new(item_field.clone()));
+    let array = ListArray::<i32>::new(
+        data_type.clone(),
+        OffsetsBuffer::try_from(vec![0, 2, 4, 5]).unwrap(),
+        Box::new(PrimitiveArray::<i32>::from_vec(vec![0, 1, 2, 3, 4])),
+        Some(Bitmap::from([true, false, true])),
+    );
+    let arrays: Vec<Box<dyn Array>> = vec![array.boxed()];
+    let fields = vec![Field::new("items", data_type, true)];
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Row {
+        items: Option<Vec<i32>>,
+    }
+
+    let actual: Vec<Row> = crate::from_arrow2(&fields, &arrays).unwrap();
+    assert_eq!(
+        actual,
+        vec![
+            Row {
+                items: Some(vec![0, 1])
+            },
+            Row { items: None },
+            Row {
+                items: Some(vec![4])
+            },
+        ]
+    );
+}
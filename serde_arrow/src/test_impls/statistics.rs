@@ -0,0 +1,261 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::schema::{
+    FieldStatistics, SchemaLike, SerdeArrowSchema, StatisticsValue, StringDetector, TracingOptions,
+    TracingStatistics,
+};
+
+fn trace<T: Serialize + ?Sized>(items: &T, options: TracingOptions) -> TracingStatistics {
+    let (schema, statistics) =
+        SerdeArrowSchema::from_samples_with_statistics(items, options.clone()).unwrap();
+    assert_eq!(
+        schema,
+        SerdeArrowSchema::from_samples(items, options).unwrap()
+    );
+    statistics
+}
+
+fn get<'a>(statistics: &'a TracingStatistics, path: &str) -> &'a FieldStatistics {
+    statistics
+        .get(path)
+        .unwrap_or_else(|| panic!("no statistics for {path}"))
+}
+
+#[test]
+fn numbers() {
+    #[derive(Serialize)]
+    struct Record {
+        int: Option<i32>,
+        uint: u64,
+        float: f64,
+    }
+
+    let items = [
+        Record {
+            int: Some(-3),
+            uint: u64::MAX,
+            float: 0.5,
+        },
+        Record {
+            int: None,
+            uint: 2,
+            float: f64::NAN,
+        },
+        Record {
+            int: Some(10),
+            uint: 2,
+            float: -1.5,
+        },
+    ];
+    let statistics = trace(&items, TracingOptions::default());
+
+    let int = get(&statistics, "$.int");
+    assert_eq!(int.values, 2);
+    assert_eq!(int.nulls, 1);
+    assert_eq!(int.min, Some(StatisticsValue::Int(-3)));
+    assert_eq!(int.max, Some(StatisticsValue::Int(10)));
+    assert_eq!(int.distinct_values, 2);
+
+    let uint = get(&statistics, "$.uint");
+    assert_eq!(uint.values, 3);
+    assert_eq!(uint.nulls, 0);
+    assert_eq!(uint.min, Some(StatisticsValue::Int(2)));
+    assert_eq!(uint.max, Some(StatisticsValue::Int(u64::MAX.into())));
+    assert_eq!(uint.distinct_values, 2);
+
+    let float = get(&statistics, "$.float");
+    assert_eq!(float.values, 3);
+    assert_eq!(float.min, Some(StatisticsValue::Float(-1.5)));
+    assert_eq!(float.max, Some(StatisticsValue::Float(0.5)));
+
+    let root = get(&statistics, "$");
+    assert_eq!(root.values, 3);
+    assert_eq!(root.nulls, 0);
+}
+
+#[test]
+fn strings() {
+    let items = [
+        json!({"name": "a", "date": "2024-03-01", "num": "10"}),
+        json!({"name": "abc", "date": "2023-12-31", "num": "-2"}),
+        json!({"name": "", "date": null, "num": "3"}),
+        json!({"name": "abc"}),
+    ];
+    let statistics = trace(
+        &items,
+        TracingOptions::default()
+            .guess_numbers(true)
+            .string_detectors(vec![StringDetector::Date]),
+    );
+
+    let name = get(&statistics, "$.name");
+    assert_eq!(name.values, 4);
+    assert_eq!(name.string_length_range, Some((0, 3)));
+    assert_eq!(name.distinct_values, 3);
+    assert_eq!(name.string_values, 4);
+    assert_eq!(name.distinct_strings, 3);
+    assert_eq!(name.min, None);
+    assert_eq!(name.max, None);
+
+    let date = get(&statistics, "$.date");
+    assert_eq!(date.values, 2);
+    assert_eq!(date.nulls, 2);
+    assert_eq!(
+        date.min,
+        Some(StatisticsValue::Temporal(String::from("2023-12-31")))
+    );
+    assert_eq!(
+        date.max,
+        Some(StatisticsValue::Temporal(String::from("2024-03-01")))
+    );
+
+    let num = get(&statistics, "$.num");
+    assert_eq!(num.nulls, 1);
+    assert_eq!(num.min, Some(StatisticsValue::Int(-2)));
+    assert_eq!(num.max, Some(StatisticsValue::Int(10)));
+    assert_eq!(num.string_length_range, Some((1, 2)));
+}
+
+#[test]
+fn datetimes() {
+    let items = [
+        json!({"naive": "2024-01-01T12:00:00", "utc": "2024-01-01T12:00:00Z"}),
+        json!({"naive": "2024-01-01T09:30:00.5", "utc": "2024-01-01T11:00:00+02:00"}),
+    ];
+    let statistics = trace(
+        &items,
+        TracingOptions::default()
+            .guess_dates(true)
+            .string_detectors(vec![StringDetector::OffsetDateTime]),
+    );
+
+    let naive = get(&statistics, "$.naive");
+    assert_eq!(
+        naive.min,
+        Some(StatisticsValue::Temporal(String::from(
+            "2024-01-01T09:30:00.5"
+        )))
+    );
+    assert_eq!(
+        naive.max,
+        Some(StatisticsValue::Temporal(String::from(
+            "2024-01-01T12:00:00"
+        )))
+    );
+
+    let utc = get(&statistics, "$.utc");
+    assert_eq!(
+        utc.min,
+        Some(StatisticsValue::Temporal(String::from(
+            "2024-01-01T11:00:00+02:00"
+        )))
+    );
+    assert_eq!(
+        utc.max,
+        Some(StatisticsValue::Temporal(String::from(
+            "2024-01-01T12:00:00Z"
+        )))
+    );
+}
+
+#[test]
+fn lists_and_maps() {
+    #[derive(Serialize)]
+    struct Record {
+        tags: Option<Vec<u8>>,
+        attributes: HashMap<String, u8>,
+    }
+
+    let items = [
+        Record {
+            tags: Some(vec![1, 2, 3]),
+            attributes: HashMap::from([(String::from("a"), 1)]),
+        },
+        Record {
+            tags: None,
+            attributes: HashMap::new(),
+        },
+        Record {
+            tags: Some(vec![]),
+            attributes: HashMap::from([(String::from("a"), 1), (String::from("b"), 2)]),
+        },
+    ];
+
+    let statistics = trace(&items, TracingOptions::default().map_as_struct(false));
+
+    let tags = get(&statistics, "$.tags");
+    assert_eq!(tags.values, 2);
+    assert_eq!(tags.nulls, 1);
+    assert_eq!(tags.length_range, Some((0, 3)));
+
+    let items_stats = get(&statistics, "$.tags.item");
+    assert_eq!(items_stats.values, 3);
+    assert_eq!(items_stats.min, Some(StatisticsValue::Int(1)));
+    assert_eq!(items_stats.max, Some(StatisticsValue::Int(3)));
+
+    let attributes = get(&statistics, "$.attributes");
+    assert_eq!(attributes.values, 3);
+    assert_eq!(attributes.length_range, Some((0, 2)));
+
+    let keys = get(&statistics, "$.attributes.key");
+    assert_eq!(keys.values, 3);
+    assert_eq!(keys.distinct_values, 2);
+
+    let statistics = trace(&items, TracingOptions::default().map_as_struct(true));
+    assert_eq!(get(&statistics, "$.attributes").length_range, Some((0, 2)));
+    assert_eq!(get(&statistics, "$.attributes.a").values, 2);
+    assert_eq!(get(&statistics, "$.attributes.a").nulls, 1);
+    assert_eq!(get(&statistics, "$.attributes.b").values, 1);
+    assert_eq!(get(&statistics, "$.attributes.b").nulls, 2);
+}
+
+#[test]
+fn enums() {
+    #[derive(Serialize)]
+    enum Shape {
+        Circle(f32),
+        Rectangle { width: f32, height: f32 },
+        Empty,
+    }
+
+    #[derive(Serialize)]
+    struct Record {
+        shape: Shape,
+    }
+
+    let items = [
+        Record {
+            shape: Shape::Circle(1.0),
+        },
+        Record {
+            shape: Shape::Empty,
+        },
+        Record {
+            shape: Shape::Circle(2.0),
+        },
+        Record {
+            shape: Shape::Rectangle {
+                width: 1.0,
+                height: 2.0,
+            },
+        },
+    ];
+
+    let statistics = trace(&items, TracingOptions::default().allow_null_fields(true));
+
+    let shape = get(&statistics, "$.shape");
+    assert_eq!(shape.values, 4);
+    assert_eq!(
+        shape.variant_counts,
+        BTreeMap::from([
+            (String::from("Circle"), 2),
+            (String::from("Empty"), 1),
+            (String::from("Rectangle"), 1),
+        ])
+    );
+    assert_eq!(get(&statistics, "$.shape.Circle").values, 2);
+    assert_eq!(get(&statistics, "$.shape.Rectangle.width").values, 1);
+}
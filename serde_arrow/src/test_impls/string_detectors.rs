@@ -0,0 +1,301 @@
+use chrono::{NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    _impl::{arrow, arrow2},
+    internal::schema::{GenericDataType as T, GenericField, GenericTimeUnit, Strategy},
+    schema::{SchemaLike, SerdeArrowSchema, StringDetector as D, TracingOptions},
+};
+
+fn trace(items: &[Value], detectors: Vec<D>) -> SerdeArrowSchema {
+    let options = TracingOptions::default().string_detectors(detectors);
+    SerdeArrowSchema::from_samples(items, options).unwrap()
+}
+
+fn roundtrip(schema: &SerdeArrowSchema, items: &[Value]) -> Vec<Value> {
+    let fields = schema.to_arrow_fields().unwrap();
+    let arrays = crate::to_arrow(&fields, items).unwrap();
+    let arrow_items: Vec<Value> = crate::from_arrow(&fields, &arrays).unwrap();
+
+    let fields = schema.to_arrow2_fields().unwrap();
+    let arrays = crate::to_arrow2(&fields, items).unwrap();
+    let arrow2_items: Vec<Value> = crate::from_arrow2(&fields, &arrays).unwrap();
+
+    assert_eq!(arrow_items, arrow2_items);
+    arrow_items
+}
+
+fn assert_detected(
+    values: Value,
+    detectors: Vec<D>,
+    expected: GenericField,
+    roundtrip_values: Value,
+) {
+    let items = values
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|value| json!({"a": value}))
+        .collect::<Vec<_>>();
+    let schema = trace(&items, detectors);
+    assert_eq!(schema.fields, vec![expected]);
+
+    let expected_items = roundtrip_values
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|value| json!({"a": value}))
+        .collect::<Vec<_>>();
+    assert_eq!(roundtrip(&schema, &items), expected_items);
+}
+
+#[test]
+fn no_detectors_by_default() {
+    let items = vec![json!({"a": "2024-01-31"}), json!({"a": "12.50"})];
+    let schema = SerdeArrowSchema::from_samples(&items, TracingOptions::default()).unwrap();
+    assert_eq!(
+        schema.fields,
+        vec![GenericField::new("a", T::LargeUtf8, false)]
+    );
+}
+
+#[test]
+fn dates() {
+    assert_detected(
+        json!(["2024-01-31", null, "1969-12-31"]),
+        vec![D::Date],
+        GenericField::new("a", T::Date32, true),
+        json!(["2024-01-31", null, "1969-12-31"]),
+    );
+}
+
+#[test]
+fn times() {
+    assert_detected(
+        json!(["12:30:00", "23:59:59.123456789", "00:00:00.5"]),
+        vec![D::Time],
+        GenericField::new("a", T::Time64(GenericTimeUnit::Nanosecond), false),
+        json!(["12:30:00", "23:59:59.123456789", "00:00:00.500"]),
+    );
+}
+
+#[test]
+fn naive_datetimes() {
+    assert_detected(
+        json!(["2024-01-31T12:00:00", "2024-01-31 13:00:00.250"]),
+        vec![D::NaiveDateTime],
+        GenericField::new("a", T::Date64, false).with_strategy(Strategy::NaiveStrAsDate64),
+        json!(["2024-01-31T12:00:00", "2024-01-31T13:00:00.250"]),
+    );
+}
+
+#[test]
+fn offset_datetimes() {
+    assert_detected(
+        json!([
+            "2024-01-31T12:00:00+02:00",
+            "2024-01-31 12:00:00Z",
+            "2024-01-31T12:00:00-0130"
+        ]),
+        vec![D::OffsetDateTime],
+        GenericField::new("a", T::Date64, false).with_strategy(Strategy::UtcStrAsDate64),
+        json!([
+            "2024-01-31T10:00:00Z",
+            "2024-01-31T12:00:00Z",
+            "2024-01-31T13:30:00Z"
+        ]),
+    );
+}
+
+#[test]
+fn decimals() {
+    assert_detected(
+        json!(["12.50", "-3.125", null, "1200"]),
+        vec![D::Decimal],
+        GenericField::new("a", T::Decimal128(7, 3), true).with_strategy(Strategy::Decimal128AsStr),
+        json!(["12.500", "-3.125", null, "1200.000"]),
+    );
+}
+
+#[test]
+fn integers_and_floats() {
+    assert_detected(
+        json!(["1", "-2"]),
+        vec![D::Integer, D::Float],
+        GenericField::new("a", T::I64, false).with_strategy(Strategy::StrAsPrimitive),
+        json!([1, -2]),
+    );
+    assert_detected(
+        json!(["1", "2.5"]),
+        vec![D::Integer, D::Float],
+        GenericField::new("a", T::F64, false).with_strategy(Strategy::StrAsPrimitive),
+        json!([1.0, 2.5]),
+    );
+}
+
+#[test]
+fn uuids() {
+    assert_detected(
+        json!([
+            "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "a8098c1a-f86e-11da-bd1a-00112444be1e"
+        ]),
+        vec![D::Uuid],
+        GenericField::new("a", T::LargeUtf8, false).with_strategy(Strategy::UuidStr),
+        json!([
+            "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "a8098c1a-f86e-11da-bd1a-00112444be1e"
+        ]),
+    );
+}
+
+#[test]
+fn detectors_are_tried_in_order() {
+    let items = vec![json!({"a": "42"})];
+
+    let schema = trace(&items, vec![D::Integer, D::Decimal]);
+    assert_eq!(schema.fields[0].data_type, T::I64);
+
+    let schema = trace(&items, vec![D::Decimal, D::Integer]);
+    assert_eq!(schema.fields[0].data_type, T::Decimal128(2, 0));
+}
+
+#[test]
+fn coercions() {
+    let field = |values: Value, detectors: Vec<D>| {
+        let items = values
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|value| json!({"a": value}))
+            .collect::<Vec<_>>();
+        trace(&items, detectors).fields[0].clone()
+    };
+
+    assert_eq!(
+        field(
+            json!(["2024-01-31", "2024-01-31T12:00:00"]),
+            vec![D::Date, D::NaiveDateTime]
+        ),
+        GenericField::new("a", T::Date64, false).with_strategy(Strategy::NaiveStrAsDate64),
+    );
+    assert_eq!(
+        field(
+            json!(["2024-01-31T12:00:00Z", "2024-01-31T12:00:00+01:00"]),
+            vec![D::UtcDateTime, D::OffsetDateTime]
+        ),
+        GenericField::new("a", T::Date64, false).with_strategy(Strategy::UtcStrAsDate64),
+    );
+    assert_eq!(
+        field(json!(["42", "0.25"]), vec![D::Integer, D::Decimal]),
+        GenericField::new("a", T::Decimal128(21, 2), false)
+            .with_strategy(Strategy::Decimal128AsStr),
+    );
+    assert_eq!(
+        field(json!(["1e3", "0.25"]), vec![D::Decimal, D::Float]),
+        GenericField::new("a", T::F64, false).with_strategy(Strategy::StrAsPrimitive),
+    );
+    assert_eq!(
+        field(
+            json!(["12345678901234567890123456789", "0.123456789012"]),
+            vec![D::Decimal]
+        ),
+        GenericField::new("a", T::LargeUtf8, false),
+    );
+    assert_eq!(
+        field(json!(["2024-01-31", "12:30:00"]), vec![D::Date, D::Time]),
+        GenericField::new("a", T::LargeUtf8, false),
+    );
+    assert_eq!(
+        field(json!(["2024-01-31", "foo"]), vec![D::Date]),
+        GenericField::new("a", T::LargeUtf8, false),
+    );
+}
+
+#[test]
+fn uuid_strategy_rejects_other_strings() {
+    let schema = SerdeArrowSchema::from_value(&json!([
+        {"name": "a", "data_type": "LargeUtf8", "strategy": "UuidStr"},
+    ]))
+    .unwrap();
+    let fields = schema.to_arrow_fields().unwrap();
+
+    let err = crate::to_arrow(&fields, &[json!({"a": "foo"})]).unwrap_err();
+    assert!(
+        err.to_string().contains("not a UUID"),
+        "unexpected error: {err}"
+    );
+}
+
+#[test]
+fn temporal_data_types() {
+    let schema = SerdeArrowSchema::from_value(&json!([
+        {"name": "date", "data_type": "Date32"},
+        {"name": "time", "data_type": "Time64(Microsecond)"},
+    ]))
+    .unwrap();
+
+    let fields = schema.to_arrow_fields().unwrap();
+    assert_eq!(fields[0].data_type(), &arrow::datatypes::DataType::Date32);
+    assert_eq!(
+        fields[1].data_type(),
+        &arrow::datatypes::DataType::Time64(arrow::datatypes::TimeUnit::Microsecond)
+    );
+    assert_eq!(
+        SerdeArrowSchema::from_arrow_fields(&fields).unwrap(),
+        schema
+    );
+
+    let fields = schema.to_arrow2_fields().unwrap();
+    assert_eq!(fields[0].data_type, arrow2::datatypes::DataType::Date32);
+    assert_eq!(
+        fields[1].data_type,
+        arrow2::datatypes::DataType::Time64(arrow2::datatypes::TimeUnit::Microsecond)
+    );
+    assert_eq!(
+        SerdeArrowSchema::from_arrow2_fields(&fields).unwrap(),
+        schema
+    );
+
+    let schema = SerdeArrowSchema::from_value(&json!([
+        {"name": "time", "data_type": "Time64(Second)"},
+    ]))
+    .unwrap();
+    let err = schema.to_arrow_fields().unwrap_err();
+    assert!(
+        err.to_string().contains("does not support the unit Second"),
+        "unexpected error: {err}"
+    );
+    let err = schema.fields[0].validate().unwrap_err();
+    assert!(
+        err.to_string().contains("only Microsecond and Nanosecond"),
+        "unexpected error: {err}"
+    );
+}
+
+#[test]
+fn chrono_dates_and_times() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        date: NaiveDate,
+        time: NaiveTime,
+    }
+
+    let items = vec![
+        Record {
+            date: NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            time: NaiveTime::from_hms_milli_opt(12, 30, 0, 125).unwrap(),
+        },
+        Record {
+            date: NaiveDate::from_ymd_opt(1900, 2, 28).unwrap(),
+            time: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+        },
+    ];
+
+    let options = TracingOptions::default().string_detectors(vec![D::Date, D::Time]);
+    let fields = Vec::<arrow::datatypes::Field>::from_samples(&items, options).unwrap();
+    let arrays = crate::to_arrow(&fields, &items).unwrap();
+    let actual: Vec<Record> = crate::from_arrow(&fields, &arrays).unwrap();
+    assert_eq!(actual, items);
+}
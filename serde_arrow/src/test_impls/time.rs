@@ -0,0 +1,99 @@
+use serde::Deserialize;
+use serde_json::json;
+use time::{macros::datetime, OffsetDateTime, PrimitiveDateTime};
+
+use super::utils::deserialize_timestamps;
+use crate::utils::Item;
+
+fn expected() -> [OffsetDateTime; 2] {
+    [
+        datetime!(2020-12-24 08:30:00.123 UTC),
+        datetime!(1950-05-05 16:06:00 UTC),
+    ]
+}
+
+fn nanoseconds() -> [i64; 2] {
+    expected().map(|dt| dt.unix_timestamp_nanos() as i64)
+}
+
+#[test]
+fn offset_date_times() {
+    for (data_type, factor) in [
+        ("Date64", 1_000_000),
+        ("Timestamp(Millisecond, Some(\"UTC\"))", 1_000_000),
+        ("Timestamp(Microsecond, None)", 1_000),
+        ("Timestamp(Nanosecond, Some(\"UTC\"))", 1),
+    ] {
+        let field = json!({"name": "item", "data_type": data_type});
+        let values = nanoseconds().map(|ns| ns / factor);
+        deserialize_timestamps(field, &values, &expected().map(Item));
+    }
+}
+
+#[test]
+fn primitive_date_times() {
+    let field = json!({"name": "item", "data_type": "Timestamp(Nanosecond, None)"});
+    let expected = expected().map(|dt| Item(PrimitiveDateTime::new(dt.date(), dt.time())));
+    deserialize_timestamps(field, &nanoseconds(), &expected);
+}
+
+#[test]
+fn rfc3339() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct T {
+        #[serde(with = "time::serde::rfc3339")]
+        item: OffsetDateTime,
+    }
+
+    let field = json!({"name": "item", "data_type": "Timestamp(Microsecond, Some(\"UTC\"))"});
+    let values = nanoseconds().map(|ns| ns / 1_000);
+    deserialize_timestamps(field, &values, &expected().map(|item| T { item }));
+}
+
+#[test]
+fn unix_timestamps() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Seconds {
+        #[serde(with = "time::serde::timestamp")]
+        item: OffsetDateTime,
+    }
+
+    let field = json!({"name": "item", "data_type": "Timestamp(Second, Some(\"UTC\"))"});
+    let values = [
+        datetime!(2020-12-24 08:30:00 UTC),
+        datetime!(1950-05-05 16:06:00 UTC),
+    ];
+    deserialize_timestamps(
+        field,
+        &values.map(|dt| dt.unix_timestamp()),
+        &values.map(|item| Seconds { item }),
+    );
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Milliseconds {
+        #[serde(with = "time::serde::timestamp::milliseconds")]
+        item: OffsetDateTime,
+    }
+
+    let field = json!({"name": "item", "data_type": "Date64"});
+    let values = nanoseconds().map(|ns| ns / 1_000_000);
+    deserialize_timestamps(
+        field,
+        &values,
+        &expected().map(|item| Milliseconds { item }),
+    );
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Nanoseconds {
+        #[serde(with = "time::serde::timestamp::nanoseconds::option")]
+        item: Option<OffsetDateTime>,
+    }
+
+    let field =
+        json!({"name": "item", "data_type": "Timestamp(Nanosecond, None)", "nullable": true});
+    deserialize_timestamps(
+        field,
+        &nanoseconds(),
+        &expected().map(|item| Nanoseconds { item: Some(item) }),
+    );
+}
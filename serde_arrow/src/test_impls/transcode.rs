@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    schema::{SchemaLike, SerdeArrowSchema, TracingOptions},
+    Arrow2Builder, ArrowBuilder,
+};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Inner {
+    x: f32,
+    y: Option<bool>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record {
+    id: u32,
+    name: Option<String>,
+    tags: Vec<String>,
+    inner: Inner,
+    scores: BTreeMap<String, i64>,
+}
+
+fn records() -> Vec<Record> {
+    vec![
+        Record {
+            id: 1,
+            name: Some(String::from("foo")),
+            tags: vec![String::from("a"), String::from("b")],
+            inner: Inner { x: 0.5, y: None },
+            scores: BTreeMap::from([(String::from("a"), 1), (String::from("b"), -2)]),
+        },
+        Record {
+            id: 2,
+            name: None,
+            tags: vec![],
+            inner: Inner {
+                x: -1.0,
+                y: Some(true),
+            },
+            scores: BTreeMap::new(),
+        },
+    ]
+}
+
+fn schema() -> SerdeArrowSchema {
+    let options = TracingOptions::default()
+        .map_as_struct(false)
+        .string_dictionary_encoding(true);
+    SerdeArrowSchema::from_type::<Record>(options).unwrap()
+}
+
+#[test]
+fn arrow_push_from_deserializer() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let records = records();
+    let expected = crate::to_arrow(&fields, &records).unwrap();
+
+    let mut builder = ArrowBuilder::new(&fields).unwrap();
+    for record in &records {
+        let json = serde_json::to_string(record).unwrap();
+        builder
+            .push_from_deserializer(&mut serde_json::Deserializer::from_str(&json))
+            .unwrap();
+    }
+    assert_eq!(builder.build_arrays().unwrap(), expected);
+}
+
+#[test]
+fn arrow_extend_from_deserializer() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let records = records();
+    let expected = crate::to_arrow(&fields, &records).unwrap();
+
+    let json = serde_json::to_string(&records).unwrap();
+    let mut builder = ArrowBuilder::new(&fields).unwrap();
+    builder
+        .extend_from_deserializer(&mut serde_json::Deserializer::from_str(&json))
+        .unwrap();
+    assert_eq!(builder.build_arrays().unwrap(), expected);
+}
+
+#[test]
+fn arrow2_push_from_deserializer() {
+    let fields = schema().to_arrow2_fields().unwrap();
+    let records = records();
+    let expected = crate::to_arrow2(&fields, &records).unwrap();
+
+    let mut builder = Arrow2Builder::new(&fields).unwrap();
+    for record in &records {
+        let json = serde_json::to_vec(record).unwrap();
+        builder
+            .push_from_deserializer(&mut serde_json::Deserializer::from_slice(&json))
+            .unwrap();
+    }
+    assert_eq!(builder.build_arrays().unwrap(), expected);
+}
+
+#[test]
+fn arrow2_extend_from_deserializer() {
+    let fields = schema().to_arrow2_fields().unwrap();
+    let records = records();
+    let expected = crate::to_arrow2(&fields, &records).unwrap();
+
+    let json = serde_json::to_string(&records).unwrap();
+    let mut builder = Arrow2Builder::new(&fields).unwrap();
+    builder
+        .extend_from_deserializer(&mut serde_json::Deserializer::from_str(&json))
+        .unwrap();
+    assert_eq!(builder.build_arrays().unwrap(), expected);
+}
+
+#[test]
+fn deserializer_errors_are_reported() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let mut builder = ArrowBuilder::new(&fields).unwrap();
+
+    let err = builder
+        .push_from_deserializer(&mut serde_json::Deserializer::from_str(r#"{"id": 1,"#))
+        .unwrap_err();
+    assert!(err.to_string().contains("EOF"), "{err}");
+}
+
+#[test]
+fn serialization_errors_are_reported() {
+    let fields = schema().to_arrow_fields().unwrap();
+    let mut builder = ArrowBuilder::new(&fields).unwrap();
+
+    let json =
+        r#"{"id": -1, "name": null, "tags": [], "inner": {"x": 1.0, "y": null}, "scores": {}}"#;
+    let err = builder
+        .push_from_deserializer(&mut serde_json::Deserializer::from_str(json))
+        .unwrap_err();
+    assert!(err.to_string().contains("value out of range"), "{err}");
+}
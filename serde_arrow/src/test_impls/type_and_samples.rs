@@ -0,0 +1,279 @@
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    internal::schema::{GenericDataType, GenericField, Strategy},
+    schema::{SchemaLike, SerdeArrowSchema, TracingOptions},
+};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record {
+    id: u32,
+    payload: Value,
+    extra: Option<Value>,
+    tags: Vec<Value>,
+}
+
+fn field(schema: &SerdeArrowSchema, name: &str) -> GenericField {
+    schema
+        .fields
+        .iter()
+        .find(|field| field.name == name)
+        .unwrap()
+        .clone()
+}
+
+#[test]
+fn values_are_traced_from_samples() {
+    let samples = [
+        Record {
+            id: 0,
+            payload: json!({"a": 1, "b": "x"}),
+            extra: None,
+            tags: vec![json!(true)],
+        },
+        Record {
+            id: 1,
+            payload: json!({"a": 2, "b": "y"}),
+            extra: Some(json!(1.5)),
+            tags: vec![],
+        },
+    ];
+
+    let schema =
+        SerdeArrowSchema::from_type_and_samples::<Record, _>(&samples, TracingOptions::default())
+            .unwrap();
+
+    assert_eq!(
+        field(&schema, "id"),
+        GenericField::new("id", GenericDataType::U32, false)
+    );
+    assert_eq!(
+        field(&schema, "payload"),
+        GenericField::new("payload", GenericDataType::Struct, false)
+            .with_strategy(Strategy::MapAsStruct)
+            .with_child(GenericField::new("a", GenericDataType::U64, false))
+            .with_child(GenericField::new("b", GenericDataType::LargeUtf8, false))
+    );
+    assert_eq!(
+        field(&schema, "extra"),
+        GenericField::new("extra", GenericDataType::F64, true)
+    );
+    assert_eq!(
+        field(&schema, "tags"),
+        GenericField::new("tags", GenericDataType::LargeList, false).with_child(GenericField::new(
+            "element",
+            GenericDataType::Bool,
+            false
+        ))
+    );
+}
+
+#[test]
+fn values_without_samples_fail() {
+    let samples = [Record {
+        id: 0,
+        payload: json!(1),
+        extra: None,
+        tags: vec![],
+    }];
+
+    let err =
+        SerdeArrowSchema::from_type_and_samples::<Record, _>(&samples, TracingOptions::default())
+            .unwrap_err()
+            .to_string();
+
+    assert!(err.contains("No samples for the non self describing value at $.extra."));
+    assert!(err.contains("`any_fallback`"));
+}
+
+#[test]
+fn values_without_samples_use_the_fallback() {
+    let samples = [Record {
+        id: 0,
+        payload: json!(1),
+        extra: None,
+        tags: vec![],
+    }];
+    let options = TracingOptions::default().any_fallback(json!({})).unwrap();
+
+    let schema = SerdeArrowSchema::from_type_and_samples::<Record, _>(&samples, options).unwrap();
+
+    assert_eq!(
+        field(&schema, "payload"),
+        GenericField::new("payload", GenericDataType::U64, false)
+    );
+    assert_eq!(
+        field(&schema, "extra"),
+        GenericField::new("extra", GenericDataType::LargeUtf8, true)
+    );
+    assert_eq!(
+        field(&schema, "tags"),
+        GenericField::new("tags", GenericDataType::LargeList, false).with_child(GenericField::new(
+            "element",
+            GenericDataType::LargeUtf8,
+            false
+        ))
+    );
+}
+
+#[test]
+fn from_type_uses_the_fallback() {
+    let options = TracingOptions::default()
+        .large_utf8(false)
+        .any_fallback(json!({}))
+        .unwrap();
+
+    let schema = SerdeArrowSchema::from_type::<Record>(options).unwrap();
+
+    assert_eq!(
+        field(&schema, "payload"),
+        GenericField::new("payload", GenericDataType::Utf8, false)
+    );
+    assert_eq!(
+        field(&schema, "extra"),
+        GenericField::new("extra", GenericDataType::Utf8, true)
+    );
+}
+
+#[test]
+fn fallback_data_type() {
+    let options = TracingOptions::default()
+        .any_fallback(json!({"data_type": "Dictionary"}))
+        .unwrap();
+
+    let schema = SerdeArrowSchema::from_type::<Record>(options).unwrap();
+
+    assert_eq!(
+        field(&schema, "payload"),
+        GenericField::new("payload", GenericDataType::Dictionary, false)
+            .with_child(GenericField::new("key", GenericDataType::U32, false))
+            .with_child(GenericField::new(
+                "value",
+                GenericDataType::LargeUtf8,
+                false
+            ))
+    );
+}
+
+#[test]
+fn flattened_structs_are_traced_from_samples() {
+    #[derive(Serialize, Deserialize)]
+    struct Outer {
+        id: u32,
+        #[serde(flatten)]
+        inner: Inner,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Inner {
+        value: f32,
+        label: Option<String>,
+        shape: Shape,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    enum Shape {
+        Circle(f32),
+        Empty,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Record {
+        name: String,
+        outer: Outer,
+        count: i64,
+    }
+
+    let samples = [
+        Record {
+            name: String::from("a"),
+            outer: Outer {
+                id: 0,
+                inner: Inner {
+                    value: 1.0,
+                    label: None,
+                    shape: Shape::Circle(2.0),
+                },
+            },
+            count: 3,
+        },
+        Record {
+            name: String::from("b"),
+            outer: Outer {
+                id: 1,
+                inner: Inner {
+                    value: 2.0,
+                    label: Some(String::from("x")),
+                    shape: Shape::Empty,
+                },
+            },
+            count: 4,
+        },
+    ];
+
+    let options = TracingOptions::default().allow_null_fields(true);
+    let schema =
+        SerdeArrowSchema::from_type_and_samples::<Record, _>(&samples, options.clone()).unwrap();
+    assert_eq!(
+        schema,
+        SerdeArrowSchema::from_samples(&samples, options.clone()).unwrap()
+    );
+
+    let samples = samples.map(|record| record.outer);
+    let schema =
+        SerdeArrowSchema::from_type_and_samples::<Outer, _>(&samples, options.clone()).unwrap();
+    assert_eq!(
+        schema,
+        SerdeArrowSchema::from_samples(&samples, options).unwrap()
+    );
+}
+
+#[test]
+fn flattened_structs_with_typed_fields() {
+    #[derive(Serialize, Deserialize)]
+    struct Record {
+        id: u32,
+        #[serde(flatten)]
+        meta: Meta,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Meta {
+        created: DateTime<Utc>,
+        flag: char,
+        scores: Vec<u8>,
+    }
+
+    let samples = [
+        Record {
+            id: 0,
+            meta: Meta {
+                created: Utc.with_ymd_and_hms(2024, 1, 31, 12, 0, 0).unwrap(),
+                flag: 'y',
+                scores: vec![1, 2],
+            },
+        },
+        Record {
+            id: 1,
+            meta: Meta {
+                created: Utc.with_ymd_and_hms(2024, 2, 1, 8, 30, 0).unwrap(),
+                flag: 'n',
+                scores: vec![],
+            },
+        },
+    ];
+
+    let schema =
+        SerdeArrowSchema::from_type_and_samples::<Record, _>(&samples, TracingOptions::default())
+            .unwrap();
+    assert_eq!(
+        schema,
+        SerdeArrowSchema::from_samples(&samples, TracingOptions::default()).unwrap()
+    );
+    assert_eq!(
+        field(&schema, "flag"),
+        GenericField::new("flag", GenericDataType::U32, false)
+    );
+}
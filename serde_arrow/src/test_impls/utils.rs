@@ -73,7 +73,7 @@ impl Test {
 }
 
 impl Test {
-    fn get_arrow_fields(&self) -> Cow<'_, Vec<arrow::datatypes::Field>> {
+    pub fn get_arrow_fields(&self) -> Cow<'_, Vec<arrow::datatypes::Field>> {
         match self.schema.as_ref() {
             Some(schema) => Cow::Owned(
                 schema
@@ -89,7 +89,7 @@ impl Test {
         }
     }
 
-    fn get_arrow2_fields(&self) -> Cow<'_, Vec<arrow2::datatypes::Field>> {
+    pub fn get_arrow2_fields(&self) -> Cow<'_, Vec<arrow2::datatypes::Field>> {
         match self.schema.as_ref() {
             Some(schema) => Cow::Owned(
                 schema
@@ -165,6 +165,19 @@ impl Test {
         let arrays = builder.build_arrays()?;
         assert_eq!(self.arrays.arrow, Some(arrays));
 
+        let mut builder = crate::ArrowBuilder::new(&fields)?;
+        builder.extend(items)?;
+        builder.extend(items)?;
+        let expected = builder.build_arrays()?;
+
+        let mut builder = crate::ArrowBuilder::new(&fields)?;
+        let mut other = crate::ArrowBuilder::new(&fields)?;
+        builder.extend(items)?;
+        other.extend(items)?;
+        builder.append(other)?;
+        let actual = builder.build_arrays()?;
+        assert_eq!(actual, expected);
+
         Ok(())
     }
 
@@ -184,6 +197,19 @@ impl Test {
         let arrays = builder.build_arrays()?;
         assert_eq!(self.arrays.arrow2, Some(arrays));
 
+        let mut builder = crate::Arrow2Builder::new(&fields)?;
+        builder.extend(items)?;
+        builder.extend(items)?;
+        let expected = builder.build_arrays()?;
+
+        let mut builder = crate::Arrow2Builder::new(&fields)?;
+        let mut other = crate::Arrow2Builder::new(&fields)?;
+        builder.extend(items)?;
+        other.extend(items)?;
+        builder.append(other)?;
+        let actual = builder.build_arrays()?;
+        assert_eq!(actual, expected);
+
         // TODO: test that the result arrays has the fields as the schema
 
         Ok(())
@@ -273,3 +299,32 @@ impl Test {
         self
     }
 }
+
+/// Build a column of the given type from the raw values and deserialize them
+/// as `T`
+pub fn deserialize_timestamps<T>(field: serde_json::Value, values: &[i64], expected: &[T])
+where
+    T: DeserializeOwned + std::fmt::Debug + PartialEq,
+{
+    use crate::_impl::arrow::array::Array as _;
+
+    let schema = SerdeArrowSchema::from_value(&[field]).unwrap();
+
+    let fields = schema.to_arrow_fields().unwrap();
+    let data = arrow::array::PrimitiveArray::<arrow::datatypes::Int64Type>::from(values.to_vec())
+        .into_data()
+        .into_builder()
+        .data_type(fields[0].data_type().clone())
+        .build()
+        .unwrap();
+    let arrays = vec![arrow::array::make_array(data)];
+    let actual: Vec<T> = crate::from_arrow(&fields, &arrays).unwrap();
+    assert_eq!(actual, expected);
+
+    let fields = schema.to_arrow2_fields().unwrap();
+    let array = arrow2::array::PrimitiveArray::<i64>::from_vec(values.to_vec())
+        .to(fields[0].data_type().clone());
+    let arrays: Vec<Box<dyn arrow2::array::Array>> = vec![array.boxed()];
+    let actual: Vec<T> = crate::from_arrow2(&fields, &arrays).unwrap();
+    assert_eq!(actual, expected);
+}
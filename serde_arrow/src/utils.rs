@@ -1,2 +1,7 @@
 //! Helpers that may be useful when using `serde_arrow`
-pub use crate::internal::generic::{Item, Items};
+pub use crate::internal::{
+    conversions::NumericConversion,
+    deserialization::row_iterator::RowIterator,
+    generic::{Item, Items},
+    serialization_ng::outer_sequence_builder::DictionaryDelta,
+};
@@ -24,7 +24,7 @@ all_arrow_features = [
     "arrow-37",
 ]
 all_arrow2_features = ["arrow2-0-17", "arrow2-0-16"]
-default_features = f"{all_arrow2_features[0]},{all_arrow_features[0]}"
+default_features = f"{all_arrow2_features[0]},{all_arrow_features[0]},rayon,json"
 
 CHECKS_PLACEHOLDER = "<<< checks >>>"
 